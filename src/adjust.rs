@@ -0,0 +1,242 @@
+use crate::linalg::Matrix;
+use crate::*;
+use std::fmt;
+
+/// Procedures for adjusting p-values for multiple comparisons. The first
+/// four control the family-wise error rate, the last two the false
+/// discovery rate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PAdjustMethod {
+    Bonferroni,
+    Holm,
+    /// Step-up procedure, valid for independent or positively dependent tests.
+    Hochberg,
+    /// Closed Simes procedure; never less powerful than Hochberg.
+    Hommel,
+    BenjaminiHochberg,
+    /// Benjamini-Yekutieli, valid under arbitrary dependence.
+    BenjaminiYekutieli,
+}
+
+impl PAdjustMethod {
+    pub const ALL: [PAdjustMethod; 6] = [
+        PAdjustMethod::Bonferroni,
+        PAdjustMethod::Holm,
+        PAdjustMethod::Hochberg,
+        PAdjustMethod::Hommel,
+        PAdjustMethod::BenjaminiHochberg,
+        PAdjustMethod::BenjaminiYekutieli,
+    ];
+}
+
+impl fmt::Display for PAdjustMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PAdjustMethod::Bonferroni => write!(f, "bonferroni"),
+            PAdjustMethod::Holm => write!(f, "holm"),
+            PAdjustMethod::Hochberg => write!(f, "hochberg"),
+            PAdjustMethod::Hommel => write!(f, "hommel"),
+            PAdjustMethod::BenjaminiHochberg => write!(f, "BH"),
+            PAdjustMethod::BenjaminiYekutieli => write!(f, "BY"),
+        }
+    }
+}
+
+/// Adjusted p-values in the original order, as in R's `p.adjust`.
+pub fn p_adjust(p: &[f64], method: PAdjustMethod) -> Result<Vec<f64>, StatustError> {
+    if let Some(bad) = p.iter().find(|v| v.is_nan() || **v < 0.0 || **v > 1.0) {
+        return Err(StatustError::invalid(format!(
+            "p-values must be in [0, 1], got {}",
+            bad
+        )));
+    }
+    let n = p.len();
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let nf = n as f64;
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| p[a].partial_cmp(&p[b]).unwrap());
+    // p-values in ascending order; `sorted[i]` has rank i + 1.
+    let sorted: Vec<f64> = order.iter().map(|&i| p[i]).collect();
+
+    let step_down = |factor: &dyn Fn(usize) -> f64| {
+        let mut running = 0.0_f64;
+        sorted
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                running = running.max(factor(i + 1) * v);
+                running.min(1.0)
+            })
+            .collect::<Vec<f64>>()
+    };
+    let step_up = |factor: &dyn Fn(usize) -> f64| {
+        let mut adjusted = vec![0.0; n];
+        let mut running = f64::INFINITY;
+        for i in (0..n).rev() {
+            running = running.min(factor(i + 1) * sorted[i]);
+            adjusted[i] = running.min(1.0);
+        }
+        adjusted
+    };
+
+    let adjusted = match method {
+        PAdjustMethod::Bonferroni => sorted.iter().map(|v| (nf * v).min(1.0)).collect(),
+        PAdjustMethod::Holm => step_down(&|rank| (n - rank + 1) as f64),
+        PAdjustMethod::Hochberg => step_up(&|rank| (n - rank + 1) as f64),
+        PAdjustMethod::Hommel => hommel(&sorted),
+        PAdjustMethod::BenjaminiHochberg => step_up(&|rank| nf / rank as f64),
+        PAdjustMethod::BenjaminiYekutieli => {
+            let q: f64 = (1..=n).map(|k| 1.0 / k as f64).sum();
+            step_up(&|rank| q * nf / rank as f64)
+        }
+    };
+    let mut out = vec![0.0; n];
+    for (rank, &i) in order.iter().enumerate() {
+        out[i] = adjusted[rank];
+    }
+    Ok(out)
+}
+
+/// Hommel adjustment of ascending p-values, following the algorithm of R's
+/// `p.adjust`.
+fn hommel(p: &[f64]) -> Vec<f64> {
+    let n = p.len();
+    let simes = (0..n)
+        .map(|i| n as f64 * p[i] / (i + 1) as f64)
+        .fold(f64::INFINITY, f64::min);
+    let mut adjusted = vec![simes; n];
+    let mut q = adjusted.clone();
+    for m in (2..n).rev() {
+        // Indices 0..=n-m may be rejected on their own; the rest share the
+        // Simes bound of the top m - 1 p-values.
+        let q1 = (n - m + 1..n)
+            .map(|i| m as f64 * p[i] / (i + m + 1 - n) as f64)
+            .fold(f64::INFINITY, f64::min);
+        for i in 0..=n - m {
+            q[i] = (m as f64 * p[i]).min(q1);
+        }
+        for i in n - m + 1..n {
+            q[i] = q[n - m];
+        }
+        for (a, v) in adjusted.iter_mut().zip(&q) {
+            *a = a.max(*v);
+        }
+    }
+    adjusted.iter().zip(p).map(|(a, v)| a.max(*v)).collect()
+}
+
+/// Adjusts the p-values of `tests` jointly with each of `methods`. Tests
+/// are labelled by their data, or by their method when the data is empty.
+pub fn adjust_tests(
+    tests: &[TestResult],
+    methods: &[PAdjustMethod],
+) -> Result<AdjustedPValues, StatustError> {
+    let p_values: Vec<f64> = tests.iter().map(|t| t.p_value).collect();
+    let mut adjusted = Matrix::zeros(tests.len(), methods.len());
+    for (j, method) in methods.iter().enumerate() {
+        for (i, v) in p_adjust(&p_values, *method)?.into_iter().enumerate() {
+            adjusted[(i, j)] = v;
+        }
+    }
+    Ok(AdjustedPValues {
+        labels: tests
+            .iter()
+            .map(|t| {
+                if t.data.is_empty() {
+                    t.method.clone()
+                } else {
+                    t.data.clone()
+                }
+            })
+            .collect(),
+        p_values,
+        methods: methods.to_vec(),
+        adjusted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_all_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() <= 1e-9,
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    const P: [f64; 8] = [0.01, 0.02, 0.03, 0.04, 0.05, 0.2, 0.001, 0.5];
+
+    // Reference values from the definitions of each procedure; Hommel by
+    // brute-force closed testing with Simes' test on every subset.
+
+    #[test]
+    fn test_p_adjust() {
+        let adjust = |method| p_adjust(&P, method).unwrap();
+        assert_all_close(
+            &adjust(PAdjustMethod::Bonferroni),
+            &[0.08, 0.16, 0.24, 0.32, 0.4, 1.0, 0.008, 1.0],
+        );
+        assert_all_close(
+            &adjust(PAdjustMethod::Holm),
+            &[0.07, 0.12, 0.15, 0.16, 0.16, 0.4, 0.008, 0.5],
+        );
+        assert_all_close(
+            &adjust(PAdjustMethod::Hochberg),
+            &[0.07, 0.12, 0.15, 0.15, 0.15, 0.4, 0.008, 0.5],
+        );
+        assert_all_close(
+            &adjust(PAdjustMethod::Hommel),
+            &[0.07, 0.25 / 3.0, 0.1, 0.12, 0.15, 0.4, 0.008, 0.5],
+        );
+        assert_all_close(
+            &adjust(PAdjustMethod::BenjaminiHochberg),
+            &[
+                0.04,
+                0.16 / 3.0,
+                0.06,
+                0.064,
+                0.2 / 3.0,
+                1.6 / 7.0,
+                0.008,
+                0.5,
+            ],
+        );
+        let q = 761.0 / 280.0;
+        let by = adjust(PAdjustMethod::BenjaminiYekutieli);
+        assert_all_close(&by[..2], &[0.04 * q, 0.16 / 3.0 * q]);
+        assert_eq!(by[7], 1.0);
+
+        assert!(p_adjust(&[], PAdjustMethod::Hommel).unwrap().is_empty());
+        assert_eq!(p_adjust(&[0.3], PAdjustMethod::Hommel).unwrap(), vec![0.3]);
+        assert!(p_adjust(&[0.1, 1.5], PAdjustMethod::Holm).is_err());
+    }
+
+    #[test]
+    fn test_adjust_tests() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let tests = df.normality_test(NormalityTest::ShapiroWilk).unwrap();
+        let result = adjust_tests(&tests, &PAdjustMethod::ALL).unwrap();
+        assert_eq!(result.labels.len(), 4);
+        let raw = result.p_values[0];
+        assert_eq!(
+            result.get("sepal.length", PAdjustMethod::Bonferroni),
+            Some((raw * 4.0).min(1.0))
+        );
+        for method in PAdjustMethod::ALL {
+            assert!(result.get("petal.width", method).unwrap() >= result.p_values[3]);
+        }
+        assert_eq!(result.get("variety", PAdjustMethod::Holm), None);
+        let text = result.to_string();
+        assert!(text.contains("hommel") && text.contains("BY"));
+        assert!(result.to_csv().starts_with(",p,bonferroni,holm"));
+    }
+}
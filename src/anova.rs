@@ -0,0 +1,726 @@
+use crate::distributions::{ContinuousDistribution, FisherF, StudentT};
+use crate::error::check_confidence;
+use crate::linalg::Matrix;
+use crate::*;
+use std::fmt;
+
+/// Multiple comparison procedure following an ANOVA.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PostHocMethod {
+    /// Tukey's honestly significant difference, using the pooled variance.
+    Tukey,
+    /// Pooled-variance t-tests with Bonferroni-adjusted p-values.
+    Bonferroni,
+    /// Games-Howell, for unequal variances and group sizes.
+    GamesHowell,
+}
+
+impl fmt::Display for PostHocMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PostHocMethod::Tukey => write!(f, "Tukey multiple comparisons of means"),
+            PostHocMethod::Bonferroni => write!(f, "Pairwise t-tests with Bonferroni adjustment"),
+            PostHocMethod::GamesHowell => write!(f, "Games-Howell multiple comparisons of means"),
+        }
+    }
+}
+
+fn pnorm(x: f64) -> f64 {
+    0.5 * special::erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Probability that the range of `cc` standard normal variables is below
+/// `w` (algorithm AS 190.2, as in R's `ptukey`).
+#[allow(clippy::excessive_precision)]
+fn wprob(w: f64, cc: f64) -> f64 {
+    const XLEG: [f64; 6] = [
+        0.981560634246719250690549090149,
+        0.904117256370474856678465866119,
+        0.769902674194304687036893833213,
+        0.587317954286617447296702418941,
+        0.367831498998180193752691536644,
+        0.125233408511468915472441369464,
+    ];
+    const ALEG: [f64; 6] = [
+        0.047175336386511827194615961485,
+        0.106939325995318430960254718194,
+        0.160078328543346226334652529543,
+        0.203167426723065921749064455810,
+        0.233492536538354808760849898925,
+        0.249147045813402785000562436043,
+    ];
+    let bb = 8.0;
+    let qsqz = w * 0.5;
+    if qsqz >= bb {
+        return 1.0;
+    }
+    // Probability that all values fall in [-w/2, w/2].
+    let mut pr_w = (2.0 * pnorm(qsqz) - 1.0).powf(cc);
+    let wincr = if w > 3.0 { 2 } else { 3 };
+    let binc = (bb - qsqz) / wincr as f64;
+    let (mut blb, mut bub) = (qsqz, qsqz + binc);
+    let cc1 = cc - 1.0;
+    for _ in 0..wincr {
+        let a = 0.5 * (bub + blb);
+        let b = 0.5 * (bub - blb);
+        let mut elsum = 0.0;
+        for jj in 0..12 {
+            let (j, xx) = if jj < 6 {
+                (jj, -XLEG[jj])
+            } else {
+                (11 - jj, XLEG[11 - jj])
+            };
+            let ac = a + b * xx;
+            let qexpo = ac * ac;
+            if qexpo > 60.0 {
+                break;
+            }
+            let rinsum = pnorm(ac) - pnorm(ac - w);
+            if rinsum >= (-30.0 / cc1).exp() {
+                elsum += ALEG[j] * (-0.5 * qexpo).exp() * rinsum.powf(cc1);
+            }
+        }
+        pr_w += elsum * 2.0 * b * cc / (2.0 * std::f64::consts::PI).sqrt();
+        blb = bub;
+        bub += binc;
+    }
+    pr_w.min(1.0)
+}
+
+/// Cdf of the studentized range for `k` groups and `df` degrees of freedom
+/// (algorithm AS 190, as in R's `ptukey`).
+#[allow(clippy::excessive_precision)]
+pub(crate) fn ptukey(q: f64, k: f64, df: f64) -> f64 {
+    const XLEGQ: [f64; 8] = [
+        0.989400934991649932596154173450,
+        0.944575023073232576077988415535,
+        0.865631202387831743880467897712,
+        0.755404408355003033895101194847,
+        0.617876244402643748446671764049,
+        0.458016777657227386342419442984,
+        0.281603550779258913230460501460,
+        0.950125098376374401853193354250e-1,
+    ];
+    const ALEGQ: [f64; 8] = [
+        0.271524594117540948517805724560e-1,
+        0.622535239386478928628438369944e-1,
+        0.951585116824927848099251076022e-1,
+        0.124628971255533872052476282192,
+        0.149595988816576732081501730547,
+        0.169156519395002538189312079030,
+        0.182603415044923588866763667969,
+        0.189450610455068496285396723208,
+    ];
+    if q.is_nan() || k < 2.0 || df < 2.0 {
+        return f64::NAN;
+    }
+    if q <= 0.0 {
+        return 0.0;
+    }
+    if q.is_infinite() {
+        return 1.0;
+    }
+    if df > 25000.0 {
+        return wprob(q, k);
+    }
+    // Integrate wprob over the distribution of s / sigma.
+    let f2 = df * 0.5;
+    let ulen: f64 = if df <= 100.0 {
+        1.0
+    } else if df <= 800.0 {
+        0.5
+    } else if df <= 5000.0 {
+        0.25
+    } else {
+        0.125
+    };
+    let f2lf = f2 * df.ln() - df * std::f64::consts::LN_2 - special::ln_gamma(f2) + ulen.ln();
+    let f21 = f2 - 1.0;
+    let ff4 = df * 0.25;
+    let mut ans = 0.0;
+    for i in 1..=50 {
+        let mut otsum = 0.0;
+        let twa1 = (2 * i - 1) as f64 * ulen;
+        for jj in 0..16 {
+            let (j, u) = if jj < 8 {
+                (jj, -XLEGQ[jj] * ulen)
+            } else {
+                (jj - 8, XLEGQ[jj - 8] * ulen)
+            };
+            let t1 = f2lf + f21 * (twa1 + u).ln() - (u + twa1) * ff4;
+            if t1 >= -30.0 {
+                let qsqz = q * ((twa1 + u) * 0.5).sqrt();
+                otsum += wprob(qsqz, k) * ALEGQ[j] * t1.exp();
+            }
+        }
+        if i as f64 * ulen >= 1.0 && otsum <= 1e-14 {
+            break;
+        }
+        ans += otsum;
+    }
+    ans.min(1.0)
+}
+
+/// Quantile of the studentized range distribution, found by bisection.
+pub(crate) fn qtukey(p: f64, k: f64, df: f64) -> f64 {
+    if !(0.0..1.0).contains(&p) {
+        return f64::NAN;
+    }
+    let (mut lo, mut hi) = (0.0, 8.0);
+    while ptukey(hi, k, df) < p {
+        lo = hi;
+        hi *= 2.0;
+    }
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if ptukey(mid, k, df) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo <= 1e-12 * hi {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+fn check_groups(groups: &[Vec<f64>]) -> Result<(), StatustError> {
+    if groups.len() < 2 {
+        return Err(StatustError::invalid(format!(
+            "ANOVA needs at least two groups, got {}",
+            groups.len()
+        )));
+    }
+    if groups.iter().any(|g| g.is_empty()) {
+        return Err(StatustError::invalid(
+            "ANOVA groups must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Builds an effect row tested against the residual mean square.
+fn effect_row(source: &str, df: f64, ss: f64, residual: &AnovaRow, ss_total: f64) -> AnovaRow {
+    let ms = ss / df;
+    let f = ms / residual.ms;
+    let p_value = match FisherF::new(df, residual.df) {
+        Ok(dist) => dist.sf(f),
+        Err(_) => f64::NAN,
+    };
+    AnovaRow {
+        source: source.to_string(),
+        df,
+        ss,
+        ms,
+        f,
+        p_value,
+        eta_sq: ss / ss_total,
+        omega_sq: (ss - df * residual.ms) / (ss_total + residual.ms),
+    }
+}
+
+fn residual_row(df: f64, ss: f64) -> AnovaRow {
+    AnovaRow {
+        source: "Residuals".to_string(),
+        df,
+        ss,
+        ms: ss / df,
+        f: f64::NAN,
+        p_value: f64::NAN,
+        eta_sq: f64::NAN,
+        omega_sq: f64::NAN,
+    }
+}
+
+fn sum_sq_dev(x: &[f64]) -> f64 {
+    let m = stats::mean(x);
+    x.iter().map(|v| (v - m).powi(2)).sum()
+}
+
+/// One-way ANOVA table comparing the means of `groups`.
+pub fn one_way_anova(groups: &[Vec<f64>]) -> Result<AnovaTable, StatustError> {
+    check_groups(groups)?;
+    let all: Vec<f64> = groups.concat();
+    let (n, k) = (all.len() as f64, groups.len() as f64);
+    if n <= k {
+        return Err(StatustError::invalid(
+            "ANOVA needs more observations than groups".to_string(),
+        ));
+    }
+    let ss_total = sum_sq_dev(&all);
+    let ss_within: f64 = groups.iter().map(|g| sum_sq_dev(g)).sum();
+    let residual = residual_row(n - k, ss_within);
+    let effect = effect_row("Groups", k - 1.0, ss_total - ss_within, &residual, ss_total);
+    Ok(AnovaTable {
+        title: "One-way ANOVA".to_string(),
+        rows: vec![effect, residual],
+    })
+}
+
+/// Welch's heteroscedastic one-way ANOVA, as in R's `oneway.test`.
+pub fn welch_anova(groups: &[Vec<f64>]) -> Result<TestResult, StatustError> {
+    check_groups(groups)?;
+    if groups.iter().any(|g| g.len() < 2) {
+        return Err(StatustError::invalid(
+            "Welch's ANOVA needs at least two observations per group".to_string(),
+        ));
+    }
+    let k = groups.len() as f64;
+    let means: Vec<f64> = groups.iter().map(|g| stats::mean(g)).collect();
+    let weights: Vec<f64> = groups
+        .iter()
+        .map(|g| g.len() as f64 / stats::variance(g))
+        .collect();
+    if weights.iter().any(|w| !w.is_finite()) {
+        return Err(StatustError::invalid(
+            "a group has zero variance".to_string(),
+        ));
+    }
+    let sum_w: f64 = weights.iter().sum();
+    let grand = weights.iter().zip(&means).map(|(w, m)| w * m).sum::<f64>() / sum_w;
+    let a = weights
+        .iter()
+        .zip(&means)
+        .map(|(w, m)| w * (m - grand).powi(2))
+        .sum::<f64>()
+        / (k - 1.0);
+    let tmp = weights
+        .iter()
+        .zip(groups)
+        .map(|(w, g)| (1.0 - w / sum_w).powi(2) / (g.len() as f64 - 1.0))
+        .sum::<f64>()
+        / (k * k - 1.0);
+    let f = a / (1.0 + 2.0 * (k - 2.0) * tmp);
+    let df2 = 1.0 / (3.0 * tmp);
+    let dist = FisherF::new(k - 1.0, df2)?;
+    let mut result = TestResult::new(
+        "One-way analysis of means (not assuming equal variances)",
+        f,
+        dist.sf(f),
+        Alternative::Greater,
+    );
+    result.parameters = vec![
+        ("num df".to_string(), k - 1.0),
+        ("denom df".to_string(), df2),
+    ];
+    Ok(result)
+}
+
+/// Pairwise comparisons of all groups after a one-way ANOVA. Differences
+/// are `mean(later group) - mean(earlier group)`.
+pub fn post_hoc(
+    groups: &[Vec<f64>],
+    labels: &[String],
+    method: PostHocMethod,
+    confidence: f64,
+) -> Result<PostHocResult, StatustError> {
+    check_groups(groups)?;
+    if labels.len() != groups.len() {
+        return Err(StatustError::LengthMismatch {
+            expected: groups.len(),
+            found: labels.len(),
+        });
+    }
+    check_confidence(confidence)?;
+    let k = groups.len();
+    let mse_row = &one_way_anova(groups)?.rows[1];
+    let (mse, df_error) = (mse_row.ms, mse_row.df);
+    if method != PostHocMethod::GamesHowell && mse == 0.0 {
+        return Err(StatustError::invalid(
+            "post-hoc comparisons need non-zero within-group variance".to_string(),
+        ));
+    }
+    if method == PostHocMethod::Tukey && df_error < 2.0 {
+        return Err(StatustError::invalid(format!(
+            "Tukey's method needs at least 2 residual degrees of freedom, got {}",
+            df_error
+        )));
+    }
+    let n: Vec<f64> = groups.iter().map(|g| g.len() as f64).collect();
+    let means: Vec<f64> = groups.iter().map(|g| stats::mean(g)).collect();
+    let vars: Vec<f64> = groups.iter().map(|g| stats::variance(g)).collect();
+    let n_pairs = (k * (k - 1) / 2) as f64;
+    let mut comparisons = Vec::new();
+    for i in 0..k {
+        for j in i + 1..k {
+            let diff = means[j] - means[i];
+            let (p_value, half) = match method {
+                PostHocMethod::Tukey => {
+                    let se = (mse / 2.0 * (1.0 / n[i] + 1.0 / n[j])).sqrt();
+                    let q = diff.abs() / se;
+                    (
+                        1.0 - ptukey(q, k as f64, df_error),
+                        qtukey(confidence, k as f64, df_error) * se,
+                    )
+                }
+                PostHocMethod::Bonferroni => {
+                    let se = (mse * (1.0 / n[i] + 1.0 / n[j])).sqrt();
+                    let dist = StudentT::new(df_error)?;
+                    let p = Alternative::TwoSided.p_value(&dist, diff / se);
+                    let alpha = (1.0 - confidence) / n_pairs;
+                    (
+                        (p * n_pairs).min(1.0),
+                        dist.quantile(1.0 - alpha / 2.0) * se,
+                    )
+                }
+                PostHocMethod::GamesHowell => {
+                    let (si, sj) = (vars[i] / n[i], vars[j] / n[j]);
+                    let df = (si + sj).powi(2) / (si * si / (n[i] - 1.0) + sj * sj / (n[j] - 1.0));
+                    // The studentized range is undefined below 2 df, and
+                    // constant groups leave the statistic undefined too.
+                    if df.is_nan() || df < 2.0 {
+                        return Err(StatustError::invalid(format!(
+                            "Games-Howell needs at least 2 Welch degrees of freedom, got {} for {} and {}",
+                            df, labels[i], labels[j]
+                        )));
+                    }
+                    let se = ((si + sj) / 2.0).sqrt();
+                    let q = diff.abs() / se;
+                    (
+                        1.0 - ptukey(q, k as f64, df),
+                        qtukey(confidence, k as f64, df) * se,
+                    )
+                }
+            };
+            comparisons.push(PairwiseComparison {
+                group1: labels[i].clone(),
+                group2: labels[j].clone(),
+                diff,
+                lower: diff - half,
+                upper: diff + half,
+                p_value,
+            });
+        }
+    }
+    Ok(PostHocResult {
+        method,
+        data: String::new(),
+        confidence,
+        comparisons,
+    })
+}
+
+/// Residual sum of squares of the least-squares fit of `y` on the columns
+/// of `x` through a QR decomposition, or an error if the columns are
+/// linearly dependent.
+fn rss(x: &Matrix, y: &[f64]) -> Result<f64, StatustError> {
+    let confounded =
+        || StatustError::invalid("factors are confounded; the design is not estimable".to_string());
+    let qr = x.qr().map_err(|_| confounded())?;
+    for j in 0..x.ncols() {
+        let norm = x.col(j).iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm == 0.0 || qr.r[(j, j)].abs() <= 1e-7 * norm {
+            return Err(confounded());
+        }
+    }
+    let beta = qr.solve(y)?;
+    let fitted = x.mul_vec(&beta)?;
+    Ok(y.iter().zip(fitted).map(|(a, b)| (a - b).powi(2)).sum())
+}
+
+/// Treatment-coded design matrix with an intercept and indicator columns
+/// for every level but the first of each factor.
+fn design(codes: &[&[usize]], n_levels: &[usize]) -> Matrix {
+    let n = codes.first().map_or(0, |c| c.len());
+    let p = 1 + n_levels.iter().map(|l| l - 1).sum::<usize>();
+    let mut x = Matrix::zeros(n, p);
+    for r in 0..n {
+        x[(r, 0)] = 1.0;
+        let mut offset = 1;
+        for (c, levels) in codes.iter().zip(n_levels) {
+            if c[r] > 0 {
+                x[(r, offset + c[r] - 1)] = 1.0;
+            }
+            offset += levels - 1;
+        }
+    }
+    x
+}
+
+/// Response and factor levels of the rows complete in every column.
+struct FactorData {
+    y: Vec<f64>,
+    /// Level index of each row, per factor.
+    codes: Vec<Vec<usize>>,
+    /// Level labels in sorted order, per factor.
+    labels: Vec<Vec<String>>,
+}
+
+impl DataFrame {
+    fn factor_data(&self, value: &str, factors: &[&str]) -> Result<FactorData, StatustError> {
+        let values = self.get_f64_col(value)?;
+        let columns = factors
+            .iter()
+            .map(|f| {
+                self.get_col_by_name(f)
+                    .ok_or_else(|| StatustError::ColumnNotFound(f.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let rows: Vec<usize> = (0..self.nrows())
+            .filter(|&r| values[r].is_some() && columns.iter().all(|c| !c[r].is_null()))
+            .collect();
+        let y = rows.iter().map(|&r| values[r].unwrap()).collect();
+        let mut codes = Vec::new();
+        let mut labels = Vec::new();
+        for c in &columns {
+            let observed: Vec<DataType> = rows.iter().map(|&r| c[r].clone()).collect();
+            let levels = contingency::levels(&observed);
+            codes.push(
+                observed
+                    .iter()
+                    .map(|v| levels.iter().position(|l| l == v).unwrap())
+                    .collect(),
+            );
+            labels.push(levels.iter().map(|l| l.to_string()).collect());
+        }
+        Ok(FactorData { y, codes, labels })
+    }
+
+    /// One-way ANOVA of the numeric column `value` across the levels of
+    /// `factor`. Rows with a null in either column are skipped.
+    pub fn one_way_anova(&self, value: &str, factor: &str) -> Result<AnovaTable, StatustError> {
+        let (_, groups) = self.grouped_f64(value, factor)?;
+        let mut table = one_way_anova(&groups)?;
+        table.title = format!("One-way ANOVA: {} by {}", value, factor);
+        table.rows[0].source = factor.to_string();
+        Ok(table)
+    }
+
+    /// Welch's one-way ANOVA of `value` across the levels of `factor`.
+    pub fn welch_anova(&self, value: &str, factor: &str) -> Result<TestResult, StatustError> {
+        let (_, groups) = self.grouped_f64(value, factor)?;
+        let mut result = welch_anova(&groups)?;
+        result.data = format!("{} by {}", value, factor);
+        Ok(result)
+    }
+
+    /// Two-way ANOVA of `value` with factors `a`, `b` and their
+    /// interaction. Sums of squares are sequential (type I, as R's `aov`),
+    /// which coincide with types II and III for balanced designs.
+    pub fn two_way_anova(&self, value: &str, a: &str, b: &str) -> Result<AnovaTable, StatustError> {
+        let FactorData { y, codes, labels } = self.factor_data(value, &[a, b])?;
+        let (la, lb) = (labels[0].len(), labels[1].len());
+        if la < 2 || lb < 2 {
+            return Err(StatustError::invalid(format!(
+                "{} and {} need at least two levels each",
+                a, b
+            )));
+        }
+        let mut cells: Vec<Vec<f64>> = vec![Vec::new(); la * lb];
+        for (r, v) in y.iter().enumerate() {
+            cells[codes[0][r] * lb + codes[1][r]].push(*v);
+        }
+        let n_cells = cells.iter().filter(|c| !c.is_empty()).count();
+        let n = y.len();
+        if n <= n_cells {
+            return Err(StatustError::invalid(
+                "two-way ANOVA needs more observations than cells".to_string(),
+            ));
+        }
+        let ss_total = sum_sq_dev(&y);
+        let rss_a = rss(&design(&[&codes[0]], &[la]), &y)?;
+        let rss_ab = rss(&design(&[&codes[0], &codes[1]], &[la, lb]), &y)?;
+        let ss_within: f64 = cells
+            .iter()
+            .filter(|c| !c.is_empty())
+            .map(|c| sum_sq_dev(c))
+            .sum();
+        let residual = residual_row((n - n_cells) as f64, ss_within);
+        let mut rows = vec![
+            effect_row(a, (la - 1) as f64, ss_total - rss_a, &residual, ss_total),
+            effect_row(b, (lb - 1) as f64, rss_a - rss_ab, &residual, ss_total),
+        ];
+        let df_interaction = n_cells + 1 - la - lb;
+        if df_interaction > 0 {
+            rows.push(effect_row(
+                &format!("{}:{}", a, b),
+                df_interaction as f64,
+                rss_ab - ss_within,
+                &residual,
+                ss_total,
+            ));
+        }
+        rows.push(residual);
+        Ok(AnovaTable {
+            title: format!("Two-way ANOVA: {} by {} and {}", value, a, b),
+            rows,
+        })
+    }
+
+    /// Post-hoc pairwise comparisons of `value` across the levels of
+    /// `factor`, in sorted level order.
+    pub fn post_hoc(
+        &self,
+        value: &str,
+        factor: &str,
+        method: PostHocMethod,
+        confidence: f64,
+    ) -> Result<PostHocResult, StatustError> {
+        let (labels, groups) = self.grouped_f64(value, factor)?;
+        let mut result = post_hoc(&groups, &labels, method, confidence)?;
+        result.data = format!("{} by {}", value, factor);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    fn warpbreaks(drop: &[usize]) -> DataFrame {
+        let breaks = [
+            26, 30, 54, 25, 70, 52, 51, 26, 67, 18, 21, 29, 17, 12, 18, 35, 30, 36, 36, 21, 24, 18,
+            10, 43, 28, 15, 26, 27, 14, 29, 19, 29, 31, 41, 20, 44, 42, 26, 19, 16, 39, 28, 21, 39,
+            29, 20, 21, 24, 17, 13, 15, 15, 16, 28,
+        ];
+        let keep: Vec<usize> = (0..54).filter(|i| !drop.contains(i)).collect();
+        let wool = keep
+            .iter()
+            .map(|i| DataType::from(if *i < 27 { "A" } else { "B" }))
+            .collect();
+        let tension = keep
+            .iter()
+            .map(|i| DataType::from(["L", "M", "H"][i % 27 / 9]))
+            .collect();
+        let breaks = keep.iter().map(|i| DataType::Int(breaks[*i])).collect();
+        DataFrame::from_columns(
+            vec![
+                "breaks".to_string(),
+                "wool".to_string(),
+                "tension".to_string(),
+            ],
+            vec![breaks, wool, tension],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ptukey() {
+        // Reference values from direct numerical integration with mpmath.
+        assert_close(ptukey(3.5, 3.0, 10.0), 0.9228966892, 1e-8);
+        assert_close(ptukey(2.0, 5.0, 20.0), 0.3739224876, 1e-8);
+        assert_close(ptukey(4.0, 4.0, 147.0), 0.9729355706, 1e-8);
+        assert_close(ptukey(3.0, 3.0, 5.0), 0.8201077382, 1e-8);
+        assert_eq!(ptukey(0.0, 3.0, 10.0), 0.0);
+        assert!(ptukey(1.0, 1.0, 10.0).is_nan());
+        let q = qtukey(0.95, 3.0, 147.0);
+        assert_close(ptukey(q, 3.0, 147.0), 0.95, 1e-10);
+    }
+
+    // Reference values from R's aov, oneway.test and TukeyHSD, checked by
+    // direct computation.
+
+    #[test]
+    fn test_one_way() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let table = df.one_way_anova("sepal.length", "variety").unwrap();
+        let effect = table.get("variety").unwrap();
+        assert_eq!(effect.df, 2.0);
+        assert_close(effect.ss, 63.212133, 1e-5);
+        assert_close(effect.f, 119.264502, 1e-4);
+        assert!(effect.p_value < 1e-16);
+        assert_close(effect.eta_sq, 0.6187057, 1e-6);
+        assert_close(effect.omega_sq, 0.6119308, 1e-6);
+        let residual = table.get("Residuals").unwrap();
+        assert_eq!(residual.df, 147.0);
+        assert_close(residual.ss, 38.9562, 1e-5);
+        assert!(table.to_string().contains("Residuals"));
+
+        let welch = df.welch_anova("sepal.length", "variety").unwrap();
+        assert_close(welch.statistic, 138.908285, 1e-4);
+        assert_close(welch.parameters[1].1, 92.211145, 1e-4);
+
+        assert!(one_way_anova(&[vec![1.0, 2.0]]).is_err());
+        assert!(welch_anova(&[vec![1.0, 1.0], vec![2.0, 3.0]]).is_err());
+    }
+
+    #[test]
+    fn test_post_hoc() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let tukey = df
+            .post_hoc("sepal.length", "variety", PostHocMethod::Tukey, 0.95)
+            .unwrap();
+        assert_eq!(tukey.comparisons.len(), 3);
+        let c = tukey.get("Setosa", "Versicolor").unwrap();
+        assert_close(c.diff, 0.93, 1e-6);
+        assert_close(c.lower, 0.6862273, 1e-6);
+        assert_close(c.upper, 1.1737727, 1e-6);
+        assert!(c.p_value < 1e-8);
+
+        let bonferroni = df
+            .post_hoc("sepal.length", "variety", PostHocMethod::Bonferroni, 0.95)
+            .unwrap();
+        let c = bonferroni.get("Versicolor", "Virginica").unwrap();
+        assert_close(c.diff, 0.652, 1e-6);
+        assert!(c.p_value < 1e-8 && c.lower > 0.0);
+
+        let gh = df
+            .post_hoc("sepal.length", "variety", PostHocMethod::GamesHowell, 0.95)
+            .unwrap();
+        let c = gh.get("Versicolor", "Virginica").unwrap();
+        let se = 0.08190089;
+        assert_close(c.p_value, 1.0 - ptukey(0.652 / se, 3.0, 94.025486), 1e-6);
+        assert_close(c.upper - c.diff, qtukey(0.95, 3.0, 94.025486) * se, 1e-6);
+        assert!(gh.to_string().contains("Virginica - Versicolor"));
+
+        let labels = ["a".to_string(), "b".to_string()];
+        let welch_df_below_2 = [vec![1.0, 2.0], vec![10.0, 30.0]];
+        assert!(post_hoc(&welch_df_below_2, &labels, PostHocMethod::GamesHowell, 0.95).is_err());
+        let constant = [vec![1.0, 1.0, 1.0], vec![2.0, 2.0, 2.0]];
+        for method in [
+            PostHocMethod::Tukey,
+            PostHocMethod::Bonferroni,
+            PostHocMethod::GamesHowell,
+        ] {
+            assert!(post_hoc(&constant, &labels, method, 0.95).is_err());
+        }
+        assert!(post_hoc(
+            &[vec![1.0, 2.0], vec![3.0]],
+            &labels,
+            PostHocMethod::Tukey,
+            0.95
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_two_way() {
+        let table = warpbreaks(&[])
+            .two_way_anova("breaks", "wool", "tension")
+            .unwrap();
+        let sources: Vec<&str> = table.rows.iter().map(|r| r.source.as_str()).collect();
+        assert_eq!(sources, ["wool", "tension", "wool:tension", "Residuals"]);
+        let expected = [
+            (1.0, 450.666667, 3.765288, 0.05821298),
+            (2.0, 2034.259259, 8.498047, 0.00069262),
+            (2.0, 1002.777778, 4.189069, 0.02104419),
+        ];
+        for (row, (df, ss, f, p)) in table.rows.iter().zip(expected) {
+            assert_eq!(row.df, df);
+            assert_close(row.ss, ss, 1e-5);
+            assert_close(row.f, f, 1e-5);
+            assert_close(row.p_value, p, 1e-7);
+        }
+        assert_close(table.rows[1].omega_sq, 0.1919143, 1e-6);
+        assert_close(table.rows[3].ss, 5745.111111, 1e-5);
+
+        // Unbalanced: sequential sums of squares from nested least-squares fits.
+        let table = warpbreaks(&[0, 1, 2, 3, 45, 46])
+            .two_way_anova("breaks", "wool", "tension")
+            .unwrap();
+        assert_close(table.rows[0].ss, 290.587826, 1e-5);
+        assert_close(table.rows[1].ss, 2136.983438, 1e-5);
+        assert_close(table.rows[2].ss, 1664.866831, 1e-5);
+        assert_close(table.rows[2].p_value, 0.00108674, 1e-7);
+        assert_eq!(table.rows[3].df, 42.0);
+
+        // A factor that repeats another leaves the design rank deficient.
+        let mut df = warpbreaks(&[]);
+        let batch = df.get_col_by_name("wool").unwrap();
+        df.set_col("batch", batch).unwrap();
+        let err = df.two_way_anova("breaks", "wool", "batch");
+        assert!(matches!(err, Err(StatustError::InvalidArgument(m)) if m.contains("confounded")));
+    }
+}
@@ -0,0 +1,725 @@
+use crate::distributions::{
+    ChiSquared, ContinuousDistribution, DiscreteDistribution, Hypergeometric,
+};
+use crate::error::check_confidence;
+use crate::linalg::Matrix;
+use crate::*;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Counts of the joint occurrences of two categorical variables.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContingencyTable {
+    pub row_name: String,
+    pub col_name: String,
+    pub row_labels: Vec<String>,
+    pub col_labels: Vec<String>,
+    pub counts: Matrix,
+}
+
+/// Distinct non-null values in their natural order. Mixed columns sort
+/// Bools first, then numbers, then Strings.
+pub(crate) fn levels(values: &[DataType]) -> Vec<DataType> {
+    let mut seen = HashSet::new();
+    let mut levels: Vec<DataType> = values
+        .iter()
+        .filter(|v| !v.is_null() && seen.insert((*v).clone()))
+        .cloned()
+        .collect();
+    let type_rank = |v: &DataType| match v {
+        DataType::Bool(_) => 0,
+        DataType::Int(_) | DataType::Float(_) => 1,
+        _ => 2,
+    };
+    levels.sort_by(|a, b| {
+        type_rank(a).cmp(&type_rank(b)).then_with(|| match (a, b) {
+            (DataType::Bool(x), DataType::Bool(y)) => x.cmp(y),
+            (DataType::String(x), DataType::String(y)) => x.cmp(y),
+            _ => a.as_f64().unwrap().total_cmp(&b.as_f64().unwrap()),
+        })
+    });
+    levels
+}
+
+impl ContingencyTable {
+    /// Cross-tabulates two equally long columns, skipping pairs where either
+    /// value is null.
+    pub fn from_values(
+        rows: &[DataType],
+        cols: &[DataType],
+    ) -> Result<ContingencyTable, StatustError> {
+        if rows.len() != cols.len() {
+            return Err(StatustError::LengthMismatch {
+                expected: rows.len(),
+                found: cols.len(),
+            });
+        }
+        let complete: Vec<(&DataType, &DataType)> = rows
+            .iter()
+            .zip(cols)
+            .filter(|(r, c)| !r.is_null() && !c.is_null())
+            .collect();
+        let row_levels = levels(&complete.iter().map(|p| p.0.clone()).collect::<Vec<_>>());
+        let col_levels = levels(&complete.iter().map(|p| p.1.clone()).collect::<Vec<_>>());
+        let mut counts = Matrix::zeros(row_levels.len(), col_levels.len());
+        for (r, c) in complete {
+            let i = row_levels.iter().position(|v| v == r).unwrap();
+            let j = col_levels.iter().position(|v| v == c).unwrap();
+            counts[(i, j)] += 1.0;
+        }
+        Ok(ContingencyTable {
+            row_name: String::new(),
+            col_name: String::new(),
+            row_labels: row_levels.iter().map(|v| v.to_string()).collect(),
+            col_labels: col_levels.iter().map(|v| v.to_string()).collect(),
+            counts,
+        })
+    }
+
+    pub fn total(&self) -> f64 {
+        self.counts.as_slice().iter().sum()
+    }
+
+    pub fn row_totals(&self) -> Vec<f64> {
+        (0..self.counts.nrows())
+            .map(|i| self.counts.row(i).iter().sum())
+            .collect()
+    }
+
+    pub fn col_totals(&self) -> Vec<f64> {
+        (0..self.counts.ncols())
+            .map(|j| self.counts.col(j).iter().sum())
+            .collect()
+    }
+
+    /// Expected counts under independence of rows and columns.
+    pub fn expected(&self) -> Matrix {
+        let (rows, cols, total) = (self.row_totals(), self.col_totals(), self.total());
+        let mut expected = Matrix::zeros(rows.len(), cols.len());
+        for (i, r) in rows.iter().enumerate() {
+            for (j, c) in cols.iter().enumerate() {
+                expected[(i, j)] = r * c / total;
+            }
+        }
+        expected
+    }
+
+    /// Pearson's chi-squared statistic for independence, without continuity
+    /// correction.
+    pub fn chi_square_statistic(&self) -> f64 {
+        let expected = self.expected();
+        self.counts
+            .as_slice()
+            .iter()
+            .zip(expected.as_slice())
+            .filter(|(_, e)| **e > 0.0)
+            .map(|(o, e)| (o - e).powi(2) / e)
+            .sum()
+    }
+
+    /// Cramér's V, `sqrt(chi2 / (n * (min(r, c) - 1)))`, between 0 and 1.
+    pub fn cramers_v(&self) -> f64 {
+        let k = self.counts.nrows().min(self.counts.ncols());
+        if k < 2 {
+            return f64::NAN;
+        }
+        (self.chi_square_statistic() / (self.total() * (k - 1) as f64)).sqrt()
+    }
+
+    /// Signed phi coefficient of a 2x2 table, `(ad - bc) / sqrt(r1 r2 c1 c2)`.
+    /// Returns NaN for other shapes.
+    pub fn phi(&self) -> f64 {
+        if self.counts.nrows() != 2 || self.counts.ncols() != 2 {
+            return f64::NAN;
+        }
+        let c = &self.counts;
+        let denom: f64 = self
+            .row_totals()
+            .iter()
+            .chain(self.col_totals().iter())
+            .product();
+        (c[(0, 0)] * c[(1, 1)] - c[(0, 1)] * c[(1, 0)]) / denom.sqrt()
+    }
+}
+
+/// Checks that a table can be tested: at least two rows and columns (unless
+/// `one_way`), no empty margins and non-negative counts.
+fn check_table(table: &ContingencyTable, one_way: bool) -> Result<(), StatustError> {
+    let (r, c) = (table.counts.nrows(), table.counts.ncols());
+    if (!one_way && (r < 2 || c < 2)) || r * c < 2 {
+        return Err(StatustError::invalid(format!(
+            "table of {} x {} is too small to test",
+            r, c
+        )));
+    }
+    if table
+        .counts
+        .as_slice()
+        .iter()
+        .any(|v| *v < 0.0 || !v.is_finite())
+    {
+        return Err(StatustError::invalid(
+            "counts must be non-negative".to_string(),
+        ));
+    }
+    if !one_way && (table.row_totals().contains(&0.0) || table.col_totals().contains(&0.0)) {
+        return Err(StatustError::invalid(
+            "table has an empty row or column".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Pearson and standardized residuals of an independence test. The
+/// standardized residuals are `(o - e) / sqrt(e (1 - r/n) (1 - c/n))`.
+fn independence_residuals(table: &ContingencyTable, expected: &Matrix) -> (Matrix, Matrix) {
+    let (rows, cols, n) = (table.row_totals(), table.col_totals(), table.total());
+    let mut pearson = Matrix::zeros(rows.len(), cols.len());
+    let mut standardized = Matrix::zeros(rows.len(), cols.len());
+    for i in 0..rows.len() {
+        for j in 0..cols.len() {
+            let (o, e) = (table.counts[(i, j)], expected[(i, j)]);
+            pearson[(i, j)] = (o - e) / e.sqrt();
+            standardized[(i, j)] = (o - e) / (e * (1.0 - rows[i] / n) * (1.0 - cols[j] / n)).sqrt();
+        }
+    }
+    (pearson, standardized)
+}
+
+/// Pearson's chi-squared test of independence of rows and columns. With
+/// `yates`, 2x2 tables get Yates' continuity correction (as in R's
+/// `chisq.test`); larger tables are never corrected.
+pub fn chi_square_test(
+    table: &ContingencyTable,
+    yates: bool,
+) -> Result<CountTestResult, StatustError> {
+    check_table(table, false)?;
+    let expected = table.expected();
+    let correct = yates && table.counts.nrows() == 2 && table.counts.ncols() == 2;
+    let statistic: f64 = table
+        .counts
+        .as_slice()
+        .iter()
+        .zip(expected.as_slice())
+        .map(|(o, e)| {
+            let d = (o - e).abs();
+            let d = if correct { d - d.min(0.5) } else { d };
+            d * d / e
+        })
+        .sum();
+    let df = ((table.counts.nrows() - 1) * (table.counts.ncols() - 1)) as f64;
+    let method = if correct {
+        "Pearson's Chi-squared test with Yates' continuity correction"
+    } else {
+        "Pearson's Chi-squared test"
+    };
+    let mut test = TestResult::new(
+        method,
+        statistic,
+        ChiSquared::new(df)?.sf(statistic),
+        Alternative::Greater,
+    );
+    test.data = format!("{} and {}", table.row_name, table.col_name);
+    test.parameters.push(("df".to_string(), df));
+    let (pearson_residuals, std_residuals) = independence_residuals(table, &expected);
+    Ok(CountTestResult {
+        test,
+        observed: table.clone(),
+        expected,
+        pearson_residuals,
+        std_residuals,
+    })
+}
+
+/// Likelihood-ratio (G) test of independence, `G = 2 sum o ln(o / e)`.
+pub fn g_test(table: &ContingencyTable) -> Result<CountTestResult, StatustError> {
+    check_table(table, false)?;
+    let expected = table.expected();
+    let statistic = 2.0
+        * table
+            .counts
+            .as_slice()
+            .iter()
+            .zip(expected.as_slice())
+            .filter(|(o, _)| **o > 0.0)
+            .map(|(o, e)| o * (o / e).ln())
+            .sum::<f64>();
+    let df = ((table.counts.nrows() - 1) * (table.counts.ncols() - 1)) as f64;
+    let mut test = TestResult::new(
+        "Log-likelihood ratio (G) test of independence",
+        statistic,
+        ChiSquared::new(df)?.sf(statistic),
+        Alternative::Greater,
+    );
+    test.data = format!("{} and {}", table.row_name, table.col_name);
+    test.parameters.push(("df".to_string(), df));
+    let (pearson_residuals, std_residuals) = independence_residuals(table, &expected);
+    Ok(CountTestResult {
+        test,
+        observed: table.clone(),
+        expected,
+        pearson_residuals,
+        std_residuals,
+    })
+}
+
+/// Chi-squared goodness-of-fit test of `counts` against the expected
+/// proportions `probs`, which must sum to one. The standardized residuals
+/// are `(o - n p) / sqrt(n p (1 - p))`.
+pub fn chi_square_gof(
+    labels: &[String],
+    counts: &[f64],
+    probs: &[f64],
+) -> Result<CountTestResult, StatustError> {
+    if counts.len() != probs.len() || labels.len() != counts.len() {
+        return Err(StatustError::LengthMismatch {
+            expected: counts.len(),
+            found: probs.len(),
+        });
+    }
+    if probs.iter().any(|p| p.is_nan() || *p <= 0.0)
+        || (probs.iter().sum::<f64>() - 1.0).abs() > 1e-8
+    {
+        return Err(StatustError::invalid(
+            "expected proportions must be positive and sum to 1".to_string(),
+        ));
+    }
+    let k = counts.len();
+    let observed = ContingencyTable {
+        row_name: String::new(),
+        col_name: "count".to_string(),
+        row_labels: labels.to_vec(),
+        col_labels: vec!["count".to_string()],
+        counts: Matrix::new(k, 1, counts.to_vec())?,
+    };
+    check_table(&observed, true)?;
+    let n = observed.total();
+    let expected = Matrix::new(k, 1, probs.iter().map(|p| n * p).collect())?;
+    let mut pearson_residuals = Matrix::zeros(k, 1);
+    let mut std_residuals = Matrix::zeros(k, 1);
+    for i in 0..k {
+        let (o, e) = (counts[i], expected[(i, 0)]);
+        pearson_residuals[(i, 0)] = (o - e) / e.sqrt();
+        std_residuals[(i, 0)] = (o - e) / (e * (1.0 - probs[i])).sqrt();
+    }
+    let statistic: f64 = pearson_residuals.as_slice().iter().map(|r| r * r).sum();
+    let df = (k - 1) as f64;
+    let mut test = TestResult::new(
+        "Chi-squared test for given probabilities",
+        statistic,
+        ChiSquared::new(df)?.sf(statistic),
+        Alternative::Greater,
+    );
+    test.parameters.push(("df".to_string(), df));
+    Ok(CountTestResult {
+        test,
+        observed,
+        expected,
+        pearson_residuals,
+        std_residuals,
+    })
+}
+
+/// Distribution of the top-left cell of a 2x2 table with fixed margins
+/// under odds ratio `psi` (Fisher's noncentral hypergeometric), over
+/// `lo..=hi`.
+struct NoncentralHypergeometric {
+    lo: i64,
+    log_central: Vec<f64>,
+}
+
+impl NoncentralHypergeometric {
+    fn probs(&self, psi: f64) -> Vec<f64> {
+        let logs: Vec<f64> = self
+            .log_central
+            .iter()
+            .enumerate()
+            .map(|(i, l)| l + (self.lo + i as i64) as f64 * psi.ln())
+            .collect();
+        let max = logs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = logs.iter().map(|l| (l - max).exp()).collect();
+        let total: f64 = weights.iter().sum();
+        weights.iter().map(|w| w / total).collect()
+    }
+
+    fn mean(&self, psi: f64) -> f64 {
+        self.probs(psi)
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (self.lo + i as i64) as f64 * p)
+            .sum()
+    }
+
+    /// `P(X <= x)`, or `P(X >= x)` when `upper`.
+    fn cdf(&self, x: i64, psi: f64, upper: bool) -> f64 {
+        self.probs(psi)
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                let k = self.lo + *i as i64;
+                if upper {
+                    k >= x
+                } else {
+                    k <= x
+                }
+            })
+            .map(|(_, p)| p)
+            .sum()
+    }
+}
+
+/// Solves `f(psi) = target` for an `f` increasing in `psi` by bisection on
+/// `ln(psi)`.
+fn solve_odds_ratio(f: impl Fn(f64) -> f64, target: f64) -> f64 {
+    let (mut lo, mut hi) = (-50.0_f64, 50.0_f64);
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if f(mid.exp()) < target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    (0.5 * (lo + hi)).exp()
+}
+
+/// Fisher's exact test for a 2x2 table. The estimate is the conditional
+/// maximum likelihood odds ratio with its exact confidence interval, as in
+/// R's `fisher.test`.
+pub fn fisher_exact(
+    table: &ContingencyTable,
+    alternative: Alternative,
+    confidence: f64,
+) -> Result<TestResult, StatustError> {
+    if table.counts.nrows() != 2 || table.counts.ncols() != 2 {
+        return Err(StatustError::invalid(format!(
+            "Fisher's exact test needs a 2 x 2 table, got {} x {}",
+            table.counts.nrows(),
+            table.counts.ncols()
+        )));
+    }
+    if table
+        .counts
+        .as_slice()
+        .iter()
+        .any(|v| *v < 0.0 || v.fract() != 0.0)
+    {
+        return Err(StatustError::invalid(
+            "counts must be non-negative integers".to_string(),
+        ));
+    }
+    check_confidence(confidence)?;
+    let c = &table.counts;
+    let x = c[(0, 0)] as i64;
+    let m = (c[(0, 0)] + c[(1, 0)]) as u64;
+    let n = (c[(0, 1)] + c[(1, 1)]) as u64;
+    let k = (c[(0, 0)] + c[(0, 1)]) as u64;
+    let central = Hypergeometric::new(m + n, m, k)?;
+    let lo = k.saturating_sub(n) as i64;
+    let hi = k.min(m) as i64;
+    let dist = NoncentralHypergeometric {
+        lo,
+        log_central: (lo..=hi).map(|i| central.log_pmf(i)).collect(),
+    };
+
+    let p_value = match alternative {
+        Alternative::Less => dist.cdf(x, 1.0, false),
+        Alternative::Greater => dist.cdf(x, 1.0, true),
+        Alternative::TwoSided => {
+            let d = dist.probs(1.0);
+            let observed = d[(x - lo) as usize] * (1.0 + 1e-7);
+            d.iter().filter(|p| **p <= observed).sum::<f64>().min(1.0)
+        }
+    };
+
+    let estimate = if x == lo && x == hi {
+        f64::NAN
+    } else if x == lo {
+        0.0
+    } else if x == hi {
+        f64::INFINITY
+    } else {
+        solve_odds_ratio(|psi| dist.mean(psi), x as f64)
+    };
+    // The upper bound solves P(X <= x) = alpha, the lower P(X >= x) = alpha.
+    let upper = |alpha: f64| {
+        if x == hi {
+            f64::INFINITY
+        } else {
+            solve_odds_ratio(|psi| -dist.cdf(x, psi, false), -alpha)
+        }
+    };
+    let lower = |alpha: f64| {
+        if x == lo {
+            0.0
+        } else {
+            solve_odds_ratio(|psi| dist.cdf(x, psi, true), alpha)
+        }
+    };
+    let (ci_lower, ci_upper) = match alternative {
+        Alternative::Less => (0.0, upper(1.0 - confidence)),
+        Alternative::Greater => (lower(1.0 - confidence), f64::INFINITY),
+        Alternative::TwoSided => {
+            let alpha = (1.0 - confidence) / 2.0;
+            (lower(alpha), upper(alpha))
+        }
+    };
+
+    let mut result = TestResult::new(
+        "Fisher's Exact Test for Count Data",
+        x as f64,
+        p_value,
+        alternative,
+    );
+    result.data = format!("{} and {}", table.row_name, table.col_name);
+    result.estimates.push(("odds ratio".to_string(), estimate));
+    result.conf_int = Some(ConfidenceInterval {
+        lower: ci_lower,
+        upper: ci_upper,
+        level: confidence,
+    });
+    Ok(result)
+}
+
+impl fmt::Display for ContingencyTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "{} x {}:", self.row_name, self.col_name).ok();
+        let short = |s: &str| s.chars().take(10).collect::<String>();
+        write!(f, "{: <12}", "").ok();
+        for c in &self.col_labels {
+            write!(f, " {: >10}", short(c)).ok();
+        }
+        for (i, r) in self.row_labels.iter().enumerate() {
+            write!(f, "\n{: <12}", short(r))?;
+            for j in 0..self.col_labels.len() {
+                write!(f, " {: >10}", self.counts[(i, j)])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DataFrame {
+    /// Contingency table of the columns `row` and `col`. Values of any type
+    /// are treated as categories; rows with a null in either column are
+    /// skipped.
+    pub fn crosstab(&self, row: &str, col: &str) -> Result<ContingencyTable, StatustError> {
+        let rows = self
+            .get_col_by_name(row)
+            .ok_or_else(|| StatustError::ColumnNotFound(row.to_string()))?;
+        let cols = self
+            .get_col_by_name(col)
+            .ok_or_else(|| StatustError::ColumnNotFound(col.to_string()))?;
+        let mut table = ContingencyTable::from_values(&rows, &cols)?;
+        table.row_name = row.to_string();
+        table.col_name = col.to_string();
+        Ok(table)
+    }
+
+    /// Chi-squared test of independence of two categorical columns.
+    pub fn chi_square_test(
+        &self,
+        row: &str,
+        col: &str,
+        yates: bool,
+    ) -> Result<CountTestResult, StatustError> {
+        chi_square_test(&self.crosstab(row, col)?, yates)
+    }
+
+    /// G-test of independence of two categorical columns.
+    pub fn g_test(&self, row: &str, col: &str) -> Result<CountTestResult, StatustError> {
+        g_test(&self.crosstab(row, col)?)
+    }
+
+    /// Fisher's exact test for two columns with two categories each.
+    pub fn fisher_exact(
+        &self,
+        row: &str,
+        col: &str,
+        alternative: Alternative,
+        confidence: f64,
+    ) -> Result<TestResult, StatustError> {
+        fisher_exact(&self.crosstab(row, col)?, alternative, confidence)
+    }
+
+    /// Goodness-of-fit test of the categories of `col` against expected
+    /// proportions given per category. Every observed category must have a
+    /// proportion; categories that never occur count as zero.
+    pub fn chi_square_gof(
+        &self,
+        col: &str,
+        expected: &[(&str, f64)],
+    ) -> Result<CountTestResult, StatustError> {
+        let values = self
+            .get_col_by_name(col)
+            .ok_or_else(|| StatustError::ColumnNotFound(col.to_string()))?;
+        let labels: Vec<String> = expected.iter().map(|(l, _)| l.to_string()).collect();
+        let mut counts = vec![0.0; labels.len()];
+        for v in values.iter().filter(|v| !v.is_null()) {
+            let label = v.to_string();
+            let i = labels.iter().position(|l| *l == label).ok_or_else(|| {
+                StatustError::invalid(format!("no expected proportion for {} in {}", label, col))
+            })?;
+            counts[i] += 1.0;
+        }
+        let probs: Vec<f64> = expected.iter().map(|(_, p)| *p).collect();
+        let mut result = chi_square_gof(&labels, &counts, &probs)?;
+        result.test.data = col.to_string();
+        result.observed.row_name = col.to_string();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    fn table(counts: &[Vec<f64>]) -> ContingencyTable {
+        let counts = Matrix::from_rows(counts).unwrap();
+        ContingencyTable {
+            row_name: "r".to_string(),
+            col_name: "c".to_string(),
+            row_labels: (0..counts.nrows()).map(|i| i.to_string()).collect(),
+            col_labels: (0..counts.ncols()).map(|i| i.to_string()).collect(),
+            counts,
+        }
+    }
+
+    #[test]
+    fn test_crosstab() {
+        let df = DataFrame::read("./examples/MOCK_DATA.csv").unwrap();
+        let name = df.header()[0].clone();
+        let t = df.crosstab(&name, &name).unwrap();
+        assert_eq!(t.row_labels, t.col_labels);
+
+        let a: Vec<DataType> = ["x", "y", "x", "", "y", "x"]
+            .iter()
+            .map(|s| DataType::from(*s))
+            .collect();
+        let b = vec![
+            DataType::Int(10),
+            DataType::Int(9),
+            DataType::Int(10),
+            DataType::Int(9),
+            DataType::None,
+            DataType::Int(9),
+        ];
+        let t = ContingencyTable::from_values(&a, &b).unwrap();
+        assert_eq!(t.row_labels, ["x", "y"]);
+        assert_eq!(t.col_labels, ["9", "10"]);
+        assert_eq!(t.counts.as_slice(), &[1.0, 2.0, 1.0, 0.0]);
+        assert_eq!(t.total(), 4.0);
+
+        let mixed = vec![
+            DataType::String("b".to_string()),
+            DataType::Int(10),
+            DataType::Float(2.5),
+            DataType::String("10".to_string()),
+            DataType::Bool(true),
+            DataType::Int(9),
+            DataType::Bool(false),
+        ];
+        let labels: Vec<String> = levels(&mixed).iter().map(|v| v.to_string()).collect();
+        assert_eq!(labels, ["false", "true", "2.5", "9", "10", "10", "b"]);
+    }
+
+    #[test]
+    fn test_association_measures() {
+        let t = table(&[vec![10.0, 20.0], vec![30.0, 40.0]]);
+        // chi2 = 0.7936508 for this table.
+        assert!((t.chi_square_statistic() - 0.79365079365).abs() < 1e-10);
+        assert!((t.phi() - -0.0890870806).abs() < 1e-9);
+        assert!((t.cramers_v() - 0.0890870806).abs() < 1e-9);
+
+        let t = table(&[
+            vec![5.0, 0.0, 0.0],
+            vec![0.0, 5.0, 0.0],
+            vec![0.0, 0.0, 5.0],
+        ]);
+        assert!((t.cramers_v() - 1.0).abs() < 1e-12);
+        assert!(t.phi().is_nan());
+    }
+
+    // Reference values computed independently with mpmath; they agree with
+    // R's chisq.test and fisher.test.
+
+    #[test]
+    fn test_chi_square_credit() {
+        let df = DataFrame::read("./examples/credit_scoring.csv").unwrap();
+        let result = df.chi_square_test("purpose", "score", true).unwrap();
+        assert_eq!(result.observed.row_labels.len(), 10);
+        assert_eq!(result.observed.col_labels, ["bad", "good"]);
+        assert_close(result.test.statistic, 33.356447, 1e-6);
+        assert_eq!(result.test.parameters[0].1, 9.0);
+        assert_close(result.test.p_value, 0.0001157491, 1e-10);
+        assert_close(result.expected[(0, 0)], 29.1, 1e-10);
+        assert_close(result.std_residuals[(0, 0)], 1.1425013, 1e-7);
+        assert!(result.to_string().contains("Standardized residuals"));
+
+        let g = df.g_test("purpose", "score").unwrap();
+        assert_close(g.test.statistic, 34.509774, 1e-6);
+        assert_close(g.test.p_value, 7.268797e-5, 1e-10);
+
+        let yates = df.chi_square_test("foreign worker", "score", true).unwrap();
+        assert_close(yates.test.statistic, 5.8215759, 1e-7);
+        assert_close(yates.test.p_value, 0.01583075, 1e-8);
+        let plain = df
+            .chi_square_test("foreign worker", "score", false)
+            .unwrap();
+        assert_close(plain.test.statistic, 6.7370441, 1e-7);
+        assert_close(plain.std_residuals[(0, 0)], -2.5955817, 1e-7);
+
+        let fisher = df
+            .fisher_exact("foreign worker", "score", Alternative::TwoSided, 0.95)
+            .unwrap();
+        assert_close(fisher.p_value, 0.009422062, 1e-9);
+        assert_close(fisher.estimates[0].1, 0.2733977, 1e-6);
+        let ci = fisher.conf_int.unwrap();
+        assert_close(ci.lower, 0.06973477, 1e-7);
+        assert_close(ci.upper, 0.7787629, 1e-6);
+        assert!(df
+            .fisher_exact("purpose", "score", Alternative::TwoSided, 0.95)
+            .is_err());
+    }
+
+    #[test]
+    fn test_gof() {
+        let df = DataFrame::read("./examples/credit_scoring.csv").unwrap();
+        let result = df
+            .chi_square_gof("score", &[("good", 0.75), ("bad", 0.25)])
+            .unwrap();
+        assert_close(result.test.statistic, 13.333333, 1e-6);
+        assert_close(result.test.p_value, 0.0002607296, 1e-10);
+        assert_close(result.expected[(1, 0)], 250.0, 1e-10);
+        assert_close(result.std_residuals[(1, 0)], 3.6514837, 1e-7);
+        assert!(df.chi_square_gof("score", &[("good", 1.0)]).is_err());
+        assert!(df
+            .chi_square_gof("score", &[("good", 0.5), ("bad", 0.4)])
+            .is_err());
+    }
+
+    #[test]
+    fn test_fisher_tea_tasting() {
+        let t = table(&[vec![3.0, 1.0], vec![1.0, 3.0]]);
+        let two = fisher_exact(&t, Alternative::TwoSided, 0.95).unwrap();
+        assert_close(two.p_value, 0.4857143, 1e-7);
+        assert_close(two.estimates[0].1, 6.4083197, 1e-6);
+        let ci = two.conf_int.unwrap();
+        assert_close(ci.lower, 0.2117356, 1e-6);
+        assert_close(ci.upper, 626.2435306, 1e-4);
+        let greater = fisher_exact(&t, Alternative::Greater, 0.95).unwrap();
+        assert_close(greater.p_value, 0.2428571, 1e-7);
+        assert_eq!(greater.conf_int.unwrap().upper, f64::INFINITY);
+
+        let corner = table(&[vec![0.0, 5.0], vec![4.0, 1.0]]);
+        let result = fisher_exact(&corner, Alternative::TwoSided, 0.95).unwrap();
+        assert_eq!(result.estimates[0].1, 0.0);
+        assert_eq!(result.conf_int.unwrap().lower, 0.0);
+    }
+}
@@ -0,0 +1,729 @@
+use crate::dataframe::is_numeric_col;
+use crate::distributions::{ContinuousDistribution, Normal, StudentT};
+use crate::error::check_confidence;
+use crate::linalg::Matrix;
+use crate::*;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CorrMethod {
+    Pearson,
+    Spearman,
+    Kendall,
+}
+
+impl fmt::Display for CorrMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CorrMethod::Pearson => write!(f, "Pearson"),
+            CorrMethod::Spearman => write!(f, "Spearman"),
+            CorrMethod::Kendall => write!(f, "Kendall"),
+        }
+    }
+}
+
+/// Denominator used for covariances.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CovKind {
+    /// Divide by `n - 1`.
+    Sample,
+    /// Divide by `n`.
+    Population,
+}
+
+/// Measure picked by [`DataFrame::association`] for a pair of column types.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AssociationMeasure {
+    Pearson,
+    PointBiserial,
+    Phi,
+    CramersV,
+}
+
+impl fmt::Display for AssociationMeasure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssociationMeasure::Pearson => write!(f, "Pearson r"),
+            AssociationMeasure::PointBiserial => write!(f, "Point-biserial r"),
+            AssociationMeasure::Phi => write!(f, "Phi"),
+            AssociationMeasure::CramersV => write!(f, "Cramér's V"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Association {
+    pub measure: AssociationMeasure,
+    pub value: f64,
+    /// Number of rows where both values are present.
+    pub n: usize,
+}
+
+impl fmt::Display for Association {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {:.4} (n = {})", self.measure, self.value, self.n)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ColumnKind {
+    Numeric,
+    Bool,
+    Categorical,
+}
+
+/// Kind of a column from its non-null values, `None` if there are none.
+fn column_kind(col: &[DataType]) -> Option<ColumnKind> {
+    let mut values = col.iter().filter(|v| !v.is_null()).peekable();
+    values.peek()?;
+    if is_numeric_col(col) {
+        Some(ColumnKind::Numeric)
+    } else if values.all(|v| matches!(v, DataType::Bool(_))) {
+        Some(ColumnKind::Bool)
+    } else {
+        Some(ColumnKind::Categorical)
+    }
+}
+
+pub fn covariance(x: &[f64], y: &[f64], kind: CovKind) -> f64 {
+    let (mx, my) = (stats::mean(x), stats::mean(y));
+    let sxy: f64 = x.iter().zip(y).map(|(a, b)| (a - mx) * (b - my)).sum();
+    match kind {
+        CovKind::Sample => sxy / (x.len() as f64 - 1.0),
+        CovKind::Population => sxy / x.len() as f64,
+    }
+}
+
+/// Point-biserial correlation: Pearson's r with `x` coded as 0/1.
+pub fn point_biserial(x: &[bool], y: &[f64]) -> f64 {
+    let coded: Vec<f64> = x.iter().map(|&b| if b { 1.0 } else { 0.0 }).collect();
+    pearson(&coded, y)
+}
+
+pub fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    let (mx, my) = (stats::mean(x), stats::mean(y));
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y) {
+        sxy += (a - mx) * (b - my);
+        sxx += (a - mx).powi(2);
+        syy += (b - my).powi(2);
+    }
+    (sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0)
+}
+
+pub fn spearman(x: &[f64], y: &[f64]) -> f64 {
+    pearson(&stats::rank(x), &stats::rank(y))
+}
+
+/// Kendall's tau-b, which corrects for ties in either variable.
+pub fn kendall(x: &[f64], y: &[f64]) -> f64 {
+    kendall_stats(x, y).0
+}
+
+/// Returns tau-b, the score `S` (concordant minus discordant pairs) and the
+/// tie-corrected variance of `S`.
+fn kendall_stats(x: &[f64], y: &[f64]) -> (f64, f64, f64) {
+    let n = x.len();
+    let mut s = 0.0;
+    let (mut ties_x, mut ties_y) = (0.0, 0.0);
+    for i in 0..n {
+        for j in i + 1..n {
+            let a = sign(x[i] - x[j]);
+            let b = sign(y[i] - y[j]);
+            s += a * b;
+            if a == 0.0 {
+                ties_x += 1.0;
+            }
+            if b == 0.0 {
+                ties_y += 1.0;
+            }
+        }
+    }
+    let nf = n as f64;
+    let n0 = nf * (nf - 1.0) / 2.0;
+    let tau = s / ((n0 - ties_x) * (n0 - ties_y)).sqrt();
+
+    let groups = |v: &[f64]| {
+        let mut sorted = v.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut sizes = Vec::new();
+        let mut i = 0;
+        while i < sorted.len() {
+            let j = sorted[i..].iter().take_while(|w| **w == sorted[i]).count();
+            if j > 1 {
+                sizes.push(j as f64);
+            }
+            i += j;
+        }
+        sizes
+    };
+    let (tx, ty) = (groups(x), groups(y));
+    let sum = |t: &[f64], f: &dyn Fn(f64) -> f64| t.iter().map(|v| f(*v)).sum::<f64>();
+    let v0 = nf * (nf - 1.0) * (2.0 * nf + 5.0);
+    let vt = sum(&tx, &|t| t * (t - 1.0) * (2.0 * t + 5.0));
+    let vu = sum(&ty, &|t| t * (t - 1.0) * (2.0 * t + 5.0));
+    let v1 = sum(&tx, &|t| t * (t - 1.0)) * sum(&ty, &|t| t * (t - 1.0));
+    let v2 = sum(&tx, &|t| t * (t - 1.0) * (t - 2.0)) * sum(&ty, &|t| t * (t - 1.0) * (t - 2.0));
+    let var_s = (v0 - vt - vu) / 18.0
+        + v1 / (2.0 * nf * (nf - 1.0))
+        + v2 / (9.0 * nf * (nf - 1.0) * (nf - 2.0));
+    (tau, s, var_s)
+}
+
+fn sign(v: f64) -> f64 {
+    if v > 0.0 {
+        1.0
+    } else if v < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+pub fn correlation(x: &[f64], y: &[f64], method: CorrMethod) -> f64 {
+    match method {
+        CorrMethod::Pearson => pearson(x, y),
+        CorrMethod::Spearman => spearman(x, y),
+        CorrMethod::Kendall => kendall(x, y),
+    }
+}
+
+/// Tests for a non-zero correlation. Pearson and Spearman use the t
+/// statistic with `n - 2` degrees of freedom, Kendall the normal
+/// approximation of `S` with tie-corrected variance.
+pub fn corr_test(
+    x: &[f64],
+    y: &[f64],
+    method: CorrMethod,
+    alternative: Alternative,
+) -> Result<TestResult, StatustError> {
+    if x.len() != y.len() {
+        return Err(StatustError::LengthMismatch {
+            expected: x.len(),
+            found: y.len(),
+        });
+    }
+    if x.len() < 3 {
+        return Err(StatustError::invalid(format!(
+            "correlation test requires at least 3 pairs, got {}",
+            x.len()
+        )));
+    }
+    let n = x.len() as f64;
+    let result = match method {
+        CorrMethod::Pearson | CorrMethod::Spearman => {
+            let r = correlation(x, y, method);
+            let t = r * ((n - 2.0) / (1.0 - r * r)).sqrt();
+            let p = alternative.p_value(&StudentT::new(n - 2.0)?, t);
+            let name = if method == CorrMethod::Pearson {
+                "Pearson's product-moment correlation"
+            } else {
+                "Spearman's rank correlation rho"
+            };
+            let mut result = TestResult::new(name, t, p, alternative);
+            result.parameters.push(("df".to_string(), n - 2.0));
+            result
+        }
+        CorrMethod::Kendall => {
+            let (_, s, var_s) = kendall_stats(x, y);
+            let z = s / var_s.sqrt();
+            let p = alternative.p_value(&Normal::standard(), z);
+            TestResult::new("Kendall's rank correlation tau", z, p, alternative)
+        }
+    };
+    Ok(result)
+}
+
+/// Confidence interval from Fisher's z transform. Spearman and Kendall use
+/// the variance corrections of Fieller, Hartley and Pearson (1957).
+pub fn fisher_ci(r: f64, n: usize, method: CorrMethod, confidence: f64) -> (f64, f64) {
+    let n = n as f64;
+    let var = match method {
+        CorrMethod::Pearson => 1.0 / (n - 3.0),
+        CorrMethod::Spearman => 1.06 / (n - 3.0),
+        CorrMethod::Kendall => 0.437 / (n - 4.0),
+    };
+    if !var.is_finite() || var <= 0.0 || r.is_nan() {
+        return (f64::NAN, f64::NAN);
+    }
+    let q = Normal::standard().quantile(0.5 + confidence / 2.0);
+    let z = r.atanh();
+    let half = q * var.sqrt();
+    ((z - half).tanh(), (z + half).tanh())
+}
+
+impl DataFrame {
+    /// Correlation matrix of all numeric columns with 95% confidence
+    /// intervals. See [`DataFrame::corr_with_confidence`].
+    pub fn corr(&self, method: CorrMethod) -> Result<CorrelationMatrix, StatustError> {
+        self.corr_with_confidence(method, 0.95)
+    }
+
+    /// Correlation matrix of all numeric columns. Each pair uses the rows
+    /// where both values are present (pairwise-complete), so entries may be
+    /// based on different numbers of observations.
+    pub fn corr_with_confidence(
+        &self,
+        method: CorrMethod,
+        confidence: f64,
+    ) -> Result<CorrelationMatrix, StatustError> {
+        check_confidence(confidence)?;
+        let columns = self.numeric_col_names();
+        let values = columns
+            .iter()
+            .map(|c| self.get_f64_col(c))
+            .collect::<Result<Vec<_>, _>>()?;
+        let k = columns.len();
+        let mut coef = Matrix::identity(k);
+        let mut p_values = Matrix::zeros(k, k);
+        let mut ci_lower = Matrix::identity(k);
+        let mut ci_upper = Matrix::identity(k);
+        let mut n_obs = vec![vec![0; k]; k];
+        for i in 0..k {
+            n_obs[i][i] = values[i].iter().flatten().count();
+            for j in i + 1..k {
+                let (x, y): (Vec<f64>, Vec<f64>) = values[i]
+                    .iter()
+                    .zip(&values[j])
+                    .filter_map(|(a, b)| Some(((*a)?, (*b)?)))
+                    .unzip();
+                let n = x.len();
+                let r = if n >= 2 {
+                    correlation(&x, &y, method)
+                } else {
+                    f64::NAN
+                };
+                let p = if n >= 3 && !r.is_nan() {
+                    corr_test(&x, &y, method, Alternative::TwoSided)?.p_value
+                } else {
+                    f64::NAN
+                };
+                let (lo, hi) = fisher_ci(r, n, method, confidence);
+                for (a, b) in [(i, j), (j, i)] {
+                    coef[(a, b)] = r;
+                    p_values[(a, b)] = p;
+                    ci_lower[(a, b)] = lo;
+                    ci_upper[(a, b)] = hi;
+                    n_obs[a][b] = n;
+                }
+            }
+        }
+        Ok(CorrelationMatrix {
+            method,
+            columns,
+            coef,
+            p_values,
+            ci_lower,
+            ci_upper,
+            n_obs,
+            confidence,
+            controls: Vec::new(),
+        })
+    }
+
+    /// Covariance matrix of all numeric columns, pairwise complete.
+    pub fn cov(&self, kind: CovKind) -> Result<LabelledMatrix, StatustError> {
+        let columns = self.numeric_col_names();
+        let values = columns
+            .iter()
+            .map(|c| self.get_f64_col(c))
+            .collect::<Result<Vec<_>, _>>()?;
+        let k = columns.len();
+        let mut cov = Matrix::zeros(k, k);
+        for i in 0..k {
+            for j in i..k {
+                let (x, y): (Vec<f64>, Vec<f64>) = values[i]
+                    .iter()
+                    .zip(&values[j])
+                    .filter_map(|(a, b)| Some(((*a)?, (*b)?)))
+                    .unzip();
+                cov[(i, j)] = covariance(&x, &y, kind);
+                cov[(j, i)] = cov[(i, j)];
+            }
+        }
+        let title = match kind {
+            CovKind::Sample => "Sample covariance (pairwise complete)",
+            CovKind::Population => "Population covariance (pairwise complete)",
+        };
+        Ok(LabelledMatrix {
+            title: title.to_string(),
+            columns,
+            values: cov,
+        })
+    }
+
+    /// Pearson partial correlations between the numeric columns not in
+    /// `controls`, with the linear effect of `controls` removed. Only rows
+    /// complete in every involved column are used. Tests use `n - 2 - k`
+    /// degrees of freedom for `k` controls, and the Fisher z intervals at
+    /// level `confidence` use `n - k` observations.
+    pub fn partial_corr(
+        &self,
+        controls: &[&str],
+        confidence: f64,
+    ) -> Result<CorrelationMatrix, StatustError> {
+        check_confidence(confidence)?;
+        for c in controls {
+            self.get_f64_col(c)?;
+        }
+        let columns: Vec<String> = self
+            .numeric_col_names()
+            .into_iter()
+            .filter(|c| !controls.contains(&c.as_str()))
+            .collect();
+        if columns.len() < 2 {
+            return Err(StatustError::invalid(
+                "partial correlation needs two numeric columns besides the controls".to_string(),
+            ));
+        }
+        let names: Vec<&str> = columns
+            .iter()
+            .map(String::as_str)
+            .chain(controls.iter().copied())
+            .collect();
+        let values = names
+            .iter()
+            .map(|c| self.get_f64_col(c))
+            .collect::<Result<Vec<_>, _>>()?;
+        let rows: Vec<Vec<f64>> = (0..self.nrows())
+            .filter_map(|r| values.iter().map(|v| v[r]).collect())
+            .collect();
+        let (n, v, k) = (rows.len(), columns.len(), controls.len());
+        if n < k + 3 {
+            return Err(StatustError::invalid(format!(
+                "partial correlation with {} controls needs at least {} complete rows, got {}",
+                k,
+                k + 3,
+                n
+            )));
+        }
+        let data = Matrix::from_rows(&rows)?;
+        let p = names.len();
+        let mut cov = Matrix::zeros(p, p);
+        for i in 0..p {
+            for j in i..p {
+                cov[(i, j)] = covariance(&data.col(i), &data.col(j), CovKind::Sample);
+                cov[(j, i)] = cov[(i, j)];
+            }
+        }
+        let vars: Vec<usize> = (0..v).collect();
+        let ctrl: Vec<usize> = (v..p).collect();
+        let mut cond = cov.select(&vars, &vars);
+        if k > 0 {
+            let s_vc = cov.select(&vars, &ctrl);
+            let s_cc_inv = cov.select(&ctrl, &ctrl).inverse()?;
+            let adjust = s_vc.matmul(&s_cc_inv)?.matmul(&s_vc.transpose())?;
+            for i in 0..v {
+                for j in 0..v {
+                    cond[(i, j)] -= adjust[(i, j)];
+                }
+            }
+        }
+        let df = (n - 2 - k) as f64;
+        let t_dist = StudentT::new(df)?;
+        let mut coef = Matrix::identity(v);
+        let mut p_values = Matrix::zeros(v, v);
+        let mut ci_lower = Matrix::identity(v);
+        let mut ci_upper = Matrix::identity(v);
+        for i in 0..v {
+            for j in i + 1..v {
+                let r = cond[(i, j)] / (cond[(i, i)] * cond[(j, j)]).sqrt();
+                let t = r * (df / (1.0 - r * r)).sqrt();
+                let p = Alternative::TwoSided.p_value(&t_dist, t);
+                let (lo, hi) = fisher_ci(r, n - k, CorrMethod::Pearson, confidence);
+                for (a, b) in [(i, j), (j, i)] {
+                    coef[(a, b)] = r;
+                    p_values[(a, b)] = p;
+                    ci_lower[(a, b)] = lo;
+                    ci_upper[(a, b)] = hi;
+                }
+            }
+        }
+        Ok(CorrelationMatrix {
+            method: CorrMethod::Pearson,
+            columns,
+            coef,
+            p_values,
+            ci_lower,
+            ci_upper,
+            n_obs: vec![vec![n; v]; v],
+            confidence,
+            controls: controls.iter().map(|c| c.to_string()).collect(),
+        })
+    }
+
+    /// Association between two columns, with the measure chosen from their
+    /// types: Pearson for two numeric columns, point-biserial for a Bool and
+    /// a numeric column, and phi (2x2) or Cramér's V for Bool and String
+    /// columns. Rows with a null in either column are skipped.
+    pub fn association(&self, a: &str, b: &str) -> Result<Association, StatustError> {
+        let col_a = self
+            .get_col_by_name(a)
+            .ok_or_else(|| StatustError::ColumnNotFound(a.to_string()))?;
+        let col_b = self
+            .get_col_by_name(b)
+            .ok_or_else(|| StatustError::ColumnNotFound(b.to_string()))?;
+        let (kind_a, kind_b) = match (column_kind(&col_a), column_kind(&col_b)) {
+            (Some(ka), Some(kb)) => (ka, kb),
+            _ => {
+                return Err(StatustError::invalid(format!(
+                    "{} or {} has no non-null values",
+                    a, b
+                )))
+            }
+        };
+        let pairs: Vec<(&DataType, &DataType)> = col_a
+            .iter()
+            .zip(&col_b)
+            .filter(|(x, y)| !x.is_null() && !y.is_null())
+            .collect();
+        let n = pairs.len();
+        let as_f64 = |v: &DataType| v.as_f64().unwrap();
+        let as_bool = |v: &DataType| matches!(v, DataType::Bool(true));
+        use ColumnKind::*;
+        let (measure, value) = match (kind_a, kind_b) {
+            (Numeric, Numeric) => {
+                let (x, y): (Vec<f64>, Vec<f64>) =
+                    pairs.iter().map(|(x, y)| (as_f64(x), as_f64(y))).unzip();
+                (AssociationMeasure::Pearson, pearson(&x, &y))
+            }
+            (Bool, Numeric) | (Numeric, Bool) => {
+                let (flags, y): (Vec<bool>, Vec<f64>) = pairs
+                    .iter()
+                    .map(|(x, y)| match kind_a {
+                        Bool => (as_bool(x), as_f64(y)),
+                        _ => (as_bool(y), as_f64(x)),
+                    })
+                    .unzip();
+                (
+                    AssociationMeasure::PointBiserial,
+                    point_biserial(&flags, &y),
+                )
+            }
+            (Numeric, Categorical) | (Categorical, Numeric) => {
+                return Err(StatustError::invalid(format!(
+                    "no association measure between numeric and categorical columns {} and {}",
+                    a, b
+                )))
+            }
+            _ => {
+                let table = ContingencyTable::from_values(&col_a, &col_b)?;
+                if table.counts.nrows() == 2 && table.counts.ncols() == 2 {
+                    (AssociationMeasure::Phi, table.phi())
+                } else {
+                    (AssociationMeasure::CramersV, table.cramers_v())
+                }
+            }
+        };
+        Ok(Association { measure, value, n })
+    }
+
+    /// Matrix of [`DataFrame::association`] over every column with at least
+    /// one value. Pairs without a defined measure are NaN.
+    pub fn association_matrix(&self) -> Result<LabelledMatrix, StatustError> {
+        let columns: Vec<String> = self
+            .header()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| column_kind(&self.get_col(*i).unwrap()).is_some())
+            .map(|(_, name)| name.clone())
+            .collect();
+        let k = columns.len();
+        let mut values = Matrix::identity(k);
+        for i in 0..k {
+            for j in i + 1..k {
+                let v = match self.association(&columns[i], &columns[j]) {
+                    Ok(a) => a.value,
+                    Err(StatustError::InvalidArgument(_)) => f64::NAN,
+                    Err(e) => return Err(e),
+                };
+                values[(i, j)] = v;
+                values[(j, i)] = v;
+            }
+        }
+        Ok(LabelledMatrix {
+            title: "Association (Pearson, point-biserial, phi or Cramér's V by column type)"
+                .to_string(),
+            columns,
+            values,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    // Reference values from R's cor and cor.test on the iris data.
+
+    #[test]
+    fn test_iris_corr() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let pearson = df.corr(CorrMethod::Pearson).unwrap();
+        assert_eq!(pearson.columns.len(), 4);
+        assert_close(
+            pearson.get("sepal.length", "petal.length").unwrap(),
+            0.8717538,
+            1e-6,
+        );
+        let (i, j) = (0, 1);
+        assert_close(pearson.coef[(i, j)], -0.1175698, 1e-6);
+        assert_close(pearson.p_values[(i, j)], 0.1519, 1e-4);
+        assert_close(pearson.ci_lower[(i, j)], -0.27269325, 1e-6);
+        assert_close(pearson.ci_upper[(i, j)], 0.04351158, 1e-6);
+        assert_eq!(pearson.n_obs[i][j], 150);
+        assert_eq!(pearson.coef[(2, 2)], 1.0);
+
+        let spearman = df.corr(CorrMethod::Spearman).unwrap();
+        assert_close(spearman.coef[(0, 1)], -0.1667777, 1e-6);
+        assert_close(spearman.coef[(0, 2)], 0.8818981, 1e-6);
+        assert_close(spearman.p_values[(0, 1)], 0.04137, 1e-4);
+
+        let kendall = df.corr(CorrMethod::Kendall).unwrap();
+        assert_close(kendall.coef[(0, 1)], -0.07699679, 1e-6);
+        assert_close(kendall.coef[(0, 2)], 0.7185159, 1e-6);
+        assert_close(kendall.p_values[(0, 1)], 0.1829, 1e-4);
+    }
+
+    #[test]
+    fn test_corr_test() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = [2.0, 1.0, 4.0, 3.0, 5.0];
+        assert_close(pearson(&x, &y), 0.8, 1e-12);
+        assert_close(kendall(&x, &y), 0.6, 1e-12);
+        let r = corr_test(&x, &y, CorrMethod::Pearson, Alternative::Greater).unwrap();
+        let two = corr_test(&x, &y, CorrMethod::Pearson, Alternative::TwoSided).unwrap();
+        assert_close(r.p_value * 2.0, two.p_value, 1e-12);
+        assert!(corr_test(&x, &y[..4], CorrMethod::Pearson, Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_pairwise_complete() {
+        let df = DataFrame::from_columns(
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            vec![
+                vec![1.0, 2.0, 3.0, 4.0, 5.0]
+                    .into_iter()
+                    .map(DataType::from)
+                    .collect(),
+                vec![
+                    DataType::Int(2),
+                    DataType::None,
+                    DataType::Int(6),
+                    DataType::Int(8),
+                    DataType::Int(11),
+                ],
+                vec!["x", "y", "z", "w", "v"]
+                    .into_iter()
+                    .map(DataType::from)
+                    .collect(),
+            ],
+        )
+        .unwrap();
+        let m = df.corr(CorrMethod::Pearson).unwrap();
+        assert_eq!(m.columns, ["a", "b"]);
+        assert_eq!(m.n_obs, vec![vec![5, 4], vec![4, 4]]);
+        let expected = pearson(&[1.0, 3.0, 4.0, 5.0], &[2.0, 6.0, 8.0, 11.0]);
+        assert_close(m.coef[(0, 1)], expected, 1e-12);
+
+        let csv = m.to_csv();
+        assert!(csv.starts_with("var1,var2,n,coef,p_value,ci_lower,ci_upper\na,b,4,"));
+        let printed = m.to_string();
+        assert!(printed.contains("Pearson correlation"));
+        assert!(printed.contains("a ~ b"));
+    }
+
+    // Reference values computed independently from the iris data: partial
+    // correlations from the recursive formula and from regression residuals.
+
+    #[test]
+    fn test_cov_and_partial_corr() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let cov = df.cov(CovKind::Sample).unwrap();
+        assert_close(
+            cov.get("sepal.length", "sepal.width").unwrap(),
+            -0.042434,
+            1e-6,
+        );
+        assert_close(
+            cov.get("sepal.length", "sepal.length").unwrap(),
+            0.6856935,
+            1e-6,
+        );
+        let pop = df.cov(CovKind::Population).unwrap();
+        assert_close(
+            pop.get("sepal.length", "sepal.width").unwrap(),
+            -0.0421511,
+            1e-6,
+        );
+        assert!(cov.to_csv().starts_with(",sepal.length,sepal.width"));
+
+        let partial = df.partial_corr(&["petal.length"], 0.95).unwrap();
+        assert_eq!(
+            partial.columns,
+            ["sepal.length", "sepal.width", "petal.width"]
+        );
+        assert_close(partial.coef[(0, 1)], 0.5781005, 1e-6);
+        assert_eq!(partial.n_obs[0][1], 150);
+        assert!(partial.p_values[(0, 1)] < 1e-13);
+        assert!(partial.to_string().contains("controlling for petal.length"));
+
+        let partial = df
+            .partial_corr(&["petal.length", "petal.width"], 0.95)
+            .unwrap();
+        assert_close(
+            partial.get("sepal.length", "sepal.width").unwrap(),
+            0.6285707,
+            1e-6,
+        );
+
+        let none = df.partial_corr(&[], 0.9).unwrap();
+        let plain = df.corr_with_confidence(CorrMethod::Pearson, 0.9).unwrap();
+        assert_close(none.coef[(0, 2)], plain.coef[(0, 2)], 1e-12);
+        assert_close(none.ci_lower[(0, 2)], plain.ci_lower[(0, 2)], 1e-12);
+        assert_eq!(none.confidence, 0.9);
+        assert!(df.partial_corr(&["petal.length"], 1.0).is_err());
+        assert!(df.partial_corr(&["variety"], 0.95).is_err());
+    }
+
+    #[test]
+    fn test_association() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let setosa: Vec<DataType> = df
+            .get_col_by_name("variety")
+            .unwrap()
+            .iter()
+            .map(|v| DataType::Bool(v.to_string() == "Setosa"))
+            .collect();
+        let mut df = df;
+        df.set_col("setosa", setosa).unwrap();
+
+        let pb = df.association("setosa", "sepal.length").unwrap();
+        assert_eq!(pb.measure, AssociationMeasure::PointBiserial);
+        assert_close(pb.value, -0.7174157, 1e-6);
+        assert_eq!(pb.n, 150);
+        assert_close(
+            df.association("sepal.length", "setosa").unwrap().value,
+            pb.value,
+            1e-12,
+        );
+
+        let r = df.association("sepal.length", "petal.length").unwrap();
+        assert_eq!(r.measure, AssociationMeasure::Pearson);
+        assert_close(r.value, 0.8717538, 1e-6);
+
+        let v = df.association("variety", "setosa").unwrap();
+        assert_eq!(v.measure, AssociationMeasure::CramersV);
+        assert_close(v.value, 1.0, 1e-12);
+        let phi = df.association("setosa", "setosa").unwrap();
+        assert_eq!(phi.measure, AssociationMeasure::Phi);
+        assert_close(phi.value, 1.0, 1e-12);
+
+        assert!(df.association("variety", "sepal.width").is_err());
+        let m = df.association_matrix().unwrap();
+        assert_eq!(m.columns.len(), 6);
+        assert!(m.get("variety", "sepal.width").unwrap().is_nan());
+        assert_close(m.get("setosa", "sepal.length").unwrap(), pb.value, 1e-12);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::linalg::Matrix;
 use crate::*;
 use std::collections::HashMap;
 
@@ -6,10 +7,25 @@ use std::fs::File;
 use std::io;
 use std::io::{BufRead, BufReader};
 
+/// What `DataFrame::to_matrix` does with rows holding a null.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NullHandling {
+    /// Fail on the first null.
+    Reject,
+    /// Leave out rows with a null in any selected column.
+    Drop,
+}
+
 #[derive(Debug, Clone)]
 pub struct DataFrame {
-    header: Vec<String>,
-    data: Vec<Vec<DataType>>,
+    pub(crate) header: Vec<String>,
+    pub(crate) data: Vec<Vec<DataType>>,
+}
+
+impl Default for DataFrame {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DataFrame {
@@ -26,18 +42,17 @@ impl DataFrame {
         let file = File::open(fname)?;
         let reader = BufReader::new(file);
 
-        for (i, line) in reader.lines().into_iter().enumerate() {
+        for (i, line) in reader.lines().enumerate() {
             if i == 0 {
-                header = line
-                    .unwrap()
-                    .split(',')
-                    .map(|s| s.trim().to_lowercase().replace('\"', ""))
+                header = split_csv_line(&line.unwrap())
+                    .iter()
+                    .map(|s| s.trim().to_lowercase())
                     .collect();
             } else {
                 let line = line.unwrap();
                 let mut row = Vec::new();
-                for s in line.split(',') {
-                    row.push(predict_type(s));
+                for s in split_csv_line(&line) {
+                    row.push(predict_type(&s));
                 }
                 data.push(row);
             }
@@ -50,7 +65,10 @@ impl DataFrame {
 
         for (i, col) in self.header.iter().enumerate() {
             let col_data = &self.get_col(i).unwrap();
-            let dtype = &col_data[0];
+            let dtype = col_data
+                .iter()
+                .find(|v| !v.is_null())
+                .unwrap_or(&DataType::None);
             match dtype {
                 DataType::None => (),
                 DataType::Bool(_) => {
@@ -144,6 +162,8 @@ impl DataFrame {
                                     categorical_result.most_freq_count += 1;
                                 }
                             }
+                        } else if row.is_null() {
+                            categorical_result.null_count += 1;
                         }
                     }
                     result.insert(col.clone(), DescribeResult::Categorical(categorical_result));
@@ -182,78 +202,428 @@ impl DataFrame {
         }
     }
 
+    pub fn from_columns(
+        header: Vec<String>,
+        columns: Vec<Vec<DataType>>,
+    ) -> Result<DataFrame, StatustError> {
+        if header.len() != columns.len() {
+            return Err(StatustError::LengthMismatch {
+                expected: header.len(),
+                found: columns.len(),
+            });
+        }
+        for (i, name) in header.iter().enumerate() {
+            if header[..i].contains(name) {
+                return Err(StatustError::DuplicateColumn(name.clone()));
+            }
+        }
+        let nrows = columns.first().map_or(0, |c| c.len());
+        if let Some(col) = columns.iter().find(|c| c.len() != nrows) {
+            return Err(StatustError::LengthMismatch {
+                expected: nrows,
+                found: col.len(),
+            });
+        }
+        let data = (0..nrows)
+            .map(|r| columns.iter().map(|c| c[r].clone()).collect())
+            .collect();
+        Ok(DataFrame { header, data })
+    }
+
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.header.len()
+    }
+
+    pub fn col_index(&self, name: &str) -> Option<usize> {
+        self.header.iter().position(|h| h == name)
+    }
+
+    pub fn get_col_by_name(&self, name: &str) -> Option<Vec<DataType>> {
+        self.col_index(name).and_then(|i| self.get_col(i))
+    }
+
+    /// Values of a numeric column as `f64`, with nulls as `None`. Fails if
+    /// the column does not exist or holds non-numeric values.
+    pub fn get_f64_col(&self, name: &str) -> Result<Vec<Option<f64>>, StatustError> {
+        let col = self
+            .get_col_by_name(name)
+            .ok_or_else(|| StatustError::ColumnNotFound(name.to_string()))?;
+        if !is_numeric_col(&col) {
+            return Err(StatustError::invalid(format!(
+                "column {} is not numeric",
+                name
+            )));
+        }
+        Ok(col.iter().map(|v| v.as_f64()).collect())
+    }
+
+    /// Numeric columns `cols` as a matrix with one row per data frame row,
+    /// in column order. Fails on a missing or non-numeric column, and on a
+    /// null unless `nulls` is `NullHandling::Drop`.
+    pub fn to_matrix(&self, cols: &[&str], nulls: NullHandling) -> Result<Matrix, StatustError> {
+        let values = cols
+            .iter()
+            .map(|c| self.get_f64_col(c))
+            .collect::<Result<Vec<_>, StatustError>>()?;
+        let mut data = Vec::with_capacity(self.nrows() * cols.len());
+        let mut nrows = 0;
+        for i in 0..self.nrows() {
+            let row: Option<Vec<f64>> = values.iter().map(|col| col[i]).collect();
+            match (row, nulls) {
+                (Some(row), _) => {
+                    data.extend(row);
+                    nrows += 1;
+                }
+                (None, NullHandling::Drop) => {}
+                (None, NullHandling::Reject) => {
+                    let j = values.iter().position(|col| col[i].is_none()).unwrap();
+                    return Err(StatustError::invalid(format!(
+                        "column {} has a null in row {}",
+                        cols[j], i
+                    )));
+                }
+            }
+        }
+        Matrix::new(nrows, cols.len(), data)
+    }
+
+    /// Names of the columns holding only numbers and nulls.
+    pub fn numeric_col_names(&self) -> Vec<String> {
+        self.header
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| is_numeric_col(&self.get_col(*i).unwrap()))
+            .map(|(_, name)| name.clone())
+            .collect()
+    }
+
+    /// Replaces the column `name` with `values`, or appends it if it does not exist.
+    pub fn set_col(&mut self, name: &str, values: Vec<DataType>) -> Result<(), StatustError> {
+        if self.ncols() > 0 && values.len() != self.nrows() {
+            return Err(StatustError::LengthMismatch {
+                expected: self.nrows(),
+                found: values.len(),
+            });
+        }
+        if self.ncols() == 0 {
+            self.data = vec![Vec::new(); values.len()];
+        }
+        match self.col_index(name) {
+            Some(i) => {
+                for (row, v) in self.data.iter_mut().zip(values) {
+                    row[i] = v;
+                }
+            }
+            None => {
+                self.header.push(name.to_string());
+                for (row, v) in self.data.iter_mut().zip(values) {
+                    row.push(v);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stacks frames vertically. Columns are matched by name, columns missing
+    /// from a frame are filled with `DataType::None` and mixed column types
+    /// are reconciled (`Int` with `Float` becomes `Float`, anything else
+    /// incompatible becomes `String`).
+    pub fn concat(frames: &[DataFrame]) -> DataFrame {
+        let mut header: Vec<String> = Vec::new();
+        for frame in frames {
+            for name in &frame.header {
+                if !header.contains(name) {
+                    header.push(name.clone());
+                }
+            }
+        }
+
+        let mut data = Vec::new();
+        for frame in frames {
+            let indices: Vec<Option<usize>> =
+                header.iter().map(|name| frame.col_index(name)).collect();
+            for row in &frame.data {
+                data.push(
+                    indices
+                        .iter()
+                        .map(|i| i.map_or(DataType::None, |i| row[i].clone()))
+                        .collect(),
+                );
+            }
+        }
+
+        let mut df = DataFrame { header, data };
+        for i in 0..df.ncols() {
+            df.reconcile_col(i);
+        }
+        df
+    }
+
+    /// Appends the rows of `other` to this frame, see [`DataFrame::concat`].
+    pub fn append(&mut self, other: &DataFrame) {
+        *self = DataFrame::concat(&[self.clone(), other.clone()]);
+    }
+
+    /// Joins frames side by side. All frames must have the same number of rows
+    /// and column names must be unique across frames.
+    pub fn hconcat(frames: &[DataFrame]) -> Result<DataFrame, StatustError> {
+        let frames: Vec<&DataFrame> = frames.iter().filter(|f| f.ncols() > 0).collect();
+        let nrows = frames.first().map_or(0, |f| f.nrows());
+        let mut header: Vec<String> = Vec::new();
+        for frame in &frames {
+            if frame.nrows() != nrows {
+                return Err(StatustError::LengthMismatch {
+                    expected: nrows,
+                    found: frame.nrows(),
+                });
+            }
+            for name in &frame.header {
+                if header.contains(name) {
+                    return Err(StatustError::DuplicateColumn(name.clone()));
+                }
+                header.push(name.clone());
+            }
+        }
+        let data = (0..nrows)
+            .map(|r| {
+                frames
+                    .iter()
+                    .flat_map(|f| f.data[r].iter().cloned())
+                    .collect()
+            })
+            .collect();
+        Ok(DataFrame { header, data })
+    }
+
+    fn reconcile_col(&mut self, col: usize) {
+        let (mut bools, mut ints, mut floats, mut strings) = (false, false, false, false);
+        for row in &self.data {
+            match &row[col] {
+                DataType::Bool(_) => bools = true,
+                DataType::Int(_) => ints = true,
+                DataType::Float(_) => floats = true,
+                DataType::String(s) if !s.is_empty() => strings = true,
+                _ => (),
+            }
+        }
+        if strings || (bools && (ints || floats)) {
+            for row in self.data.iter_mut() {
+                if !row[col].is_null() {
+                    row[col] = DataType::String(row[col].to_string());
+                }
+            }
+        } else if ints && floats {
+            for row in self.data.iter_mut() {
+                if let DataType::Int(i) = row[col] {
+                    row[col] = DataType::Float(i as f32);
+                }
+            }
+        }
+    }
+
     pub fn print(&self) -> io::Result<String> {
+        // Show at most five columns: the first four and the last one.
+        let ncols = self.header.len();
+        let cols: Vec<usize> = if ncols <= 5 {
+            (0..ncols).collect()
+        } else {
+            vec![0, 1, 2, 3, ncols - 1]
+        };
+        let format_row = |cells: Vec<String>| {
+            cells
+                .iter()
+                .map(|c| format!("{: <10}", c.chars().take(8).collect::<String>()))
+                .collect::<Vec<String>>()
+                .join(" | ")
+        };
         let mut buffer = String::new();
         writeln!(
             buffer,
             "==============================================================="
-        );
+        )
+        .ok();
         writeln!(
             buffer,
-            "{0: <10} | {1: <10} | {2: <10} | {3: <10} | {4: <10}",
-            &self.header[0]
-                .chars()
-                .into_iter()
-                .take(8)
-                .collect::<String>(),
-            &self.header[1]
-                .chars()
-                .into_iter()
-                .take(8)
-                .collect::<String>(),
-            &self.header[2]
-                .chars()
-                .into_iter()
-                .take(8)
-                .collect::<String>(),
-            &self.header[3]
-                .chars()
-                .into_iter()
-                .take(8)
-                .collect::<String>(),
-            &self.header[self.header.len() - 1]
-                .chars()
-                .into_iter()
-                .take(8)
-                .collect::<String>()
-        );
+            "{}",
+            format_row(cols.iter().map(|&i| self.header[i].clone()).collect())
+        )
+        .ok();
         for row in self.data.iter().take(5) {
             writeln!(
                 buffer,
-                "{0: <10} | {1: <10} | {2: <10} | {3: <10} | {4: <10}",
-                &row[0]
-                    .to_string()
-                    .chars()
-                    .into_iter()
-                    .take(8)
-                    .collect::<String>(),
-                &row[1]
-                    .to_string()
-                    .chars()
-                    .into_iter()
-                    .take(8)
-                    .collect::<String>(),
-                &row[2]
-                    .to_string()
-                    .chars()
-                    .into_iter()
-                    .take(8)
-                    .collect::<String>(),
-                &row[3]
-                    .to_string()
-                    .chars()
-                    .into_iter()
-                    .take(8)
-                    .collect::<String>(),
-                &row[row.len() - 1]
-                    .to_string()
-                    .chars()
-                    .into_iter()
-                    .take(8)
-                    .collect::<String>()
-            );
+                "{}",
+                format_row(cols.iter().map(|&i| row[i].to_string()).collect())
+            )
+            .ok();
         }
         println!("{}", buffer);
         Ok(buffer)
     }
 }
+
+/// Splits a CSV line on commas outside of double quotes. Quotes are removed
+/// and `""` inside a quoted field becomes a literal quote.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+pub(crate) fn is_numeric_col(col: &[DataType]) -> bool {
+    col.iter().any(|v| v.as_f64().is_some())
+        && col.iter().all(|v| v.is_null() || v.as_f64().is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(header: &[&str], columns: Vec<Vec<DataType>>) -> DataFrame {
+        DataFrame::from_columns(header.iter().map(|h| h.to_string()).collect(), columns).unwrap()
+    }
+
+    #[test]
+    fn test_concat() {
+        let df1 = frame(
+            &["a", "b"],
+            vec![
+                vec![DataType::Int(1), DataType::Int(2)],
+                vec![DataType::Bool(true), DataType::Bool(false)],
+            ],
+        );
+        let df2 = frame(
+            &["a", "c"],
+            vec![
+                vec![DataType::Float(2.5)],
+                vec![DataType::String("x".to_string())],
+            ],
+        );
+        let df = DataFrame::concat(&[df1, df2]);
+        assert_eq!(df.header(), &["a", "b", "c"]);
+        assert_eq!(df.nrows(), 3);
+        assert_eq!(
+            df.get_col_by_name("a").unwrap(),
+            vec![
+                DataType::Float(1.0),
+                DataType::Float(2.0),
+                DataType::Float(2.5)
+            ]
+        );
+        assert_eq!(
+            df.get_col_by_name("b").unwrap(),
+            vec![DataType::Bool(true), DataType::Bool(false), DataType::None]
+        );
+        assert_eq!(df.get_row(0).unwrap()[2], DataType::None);
+    }
+
+    #[test]
+    fn test_append_iris() {
+        let mut df = DataFrame::read("./examples/iris.csv").unwrap();
+        let other = DataFrame::read("./examples/iris.csv").unwrap();
+        df.append(&other);
+        assert_eq!(df.nrows(), 300);
+        assert_eq!(df.ncols(), 5);
+        assert_eq!(df.get_row(150).unwrap()[1], DataType::Float(3.5));
+    }
+
+    #[test]
+    fn test_read_quoted_fields() {
+        let df = DataFrame::read("./examples/credit_scoring.csv").unwrap();
+        assert_eq!(df.ncols(), 21);
+        assert_eq!(df.nrows(), 1000);
+        assert!(df.data.iter().all(|row| row.len() == 21));
+        assert_eq!(
+            df.get_row(0).unwrap()[18],
+            DataType::from("yes, registered under the customers name")
+        );
+        assert_eq!(df.header()[20], "score");
+        assert_eq!(
+            split_csv_line(r#"a,"b, ""c""",,d"#),
+            ["a", r#"b, "c""#, "", "d"]
+        );
+    }
+
+    #[test]
+    fn test_hconcat() {
+        let df1 = frame(&["a"], vec![vec![DataType::Int(1), DataType::Int(2)]]);
+        let df2 = frame(&["b"], vec![vec![DataType::Int(3), DataType::Int(4)]]);
+        let df = DataFrame::hconcat(&[df1.clone(), df2]).unwrap();
+        assert_eq!(
+            df.get_row(1).unwrap(),
+            vec![DataType::Int(2), DataType::Int(4)]
+        );
+
+        assert_eq!(
+            DataFrame::hconcat(&[df1.clone(), df1.clone()]).unwrap_err(),
+            StatustError::DuplicateColumn("a".to_string())
+        );
+        let df3 = frame(&["c"], vec![vec![DataType::Int(5)]]);
+        assert_eq!(
+            DataFrame::hconcat(&[df1, df3]).unwrap_err(),
+            StatustError::LengthMismatch {
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_print_narrow_and_wide() {
+        let df = frame(
+            &["a", "b"],
+            vec![vec![DataType::Int(1)], vec![DataType::Int(2)]],
+        );
+        let out = df.print().unwrap();
+        assert!(out.contains("a          | b         \n1          | 2"));
+
+        let iris = DataFrame::read("./examples/iris.csv").unwrap();
+        let out = iris.print().unwrap();
+        assert_eq!(out.lines().nth(1).unwrap().matches(" | ").count(), 4);
+    }
+
+    #[test]
+    fn test_to_matrix() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let m = df
+            .to_matrix(&["petal.width", "sepal.length"], NullHandling::Reject)
+            .unwrap();
+        assert_eq!((m.nrows(), m.ncols()), (150, 2));
+        assert_eq!(m.row(0), &[0.2_f32 as f64, 5.1_f32 as f64]);
+        assert!(df.to_matrix(&["variety"], NullHandling::Drop).is_err());
+        assert!(df.to_matrix(&["petal"], NullHandling::Drop).is_err());
+
+        let mut df = df;
+        let mut col = df.get_col_by_name("sepal.width").unwrap();
+        col[1] = DataType::None;
+        col[4] = DataType::None;
+        df.set_col("sepal.width", col).unwrap();
+        let cols = ["sepal.length", "sepal.width"];
+        let err = df.to_matrix(&cols, NullHandling::Reject);
+        assert!(matches!(err, Err(StatustError::InvalidArgument(m)) if m.contains("row 1")));
+        let m = df.to_matrix(&cols, NullHandling::Drop).unwrap();
+        assert_eq!(m.nrows(), 148);
+        assert_eq!(m.row(1), &[4.7_f32 as f64, 3.2_f32 as f64]);
+    }
+}
@@ -1,9 +1,16 @@
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::ops::{Add, Div, Mul, Neg, Sub};
 
-#[derive(Clone, Debug, PartialEq)]
+/// Values are compared structurally: `Int(1)` and `Float(1.0)` are different
+/// values. Floats compare by bit pattern after folding every NaN into one value
+/// and `-0.0` into `0.0`, which makes `DataType` usable as a hash key.
+#[derive(Clone, Debug)]
 pub enum DataType {
     None,
     Bool(bool),
@@ -12,6 +19,44 @@ pub enum DataType {
     String(String),
 }
 
+fn canonical_bits(f: f32) -> u32 {
+    if f.is_nan() {
+        f32::NAN.to_bits()
+    } else if f == 0.0 {
+        0
+    } else {
+        f.to_bits()
+    }
+}
+
+impl PartialEq for DataType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DataType::None, DataType::None) => true,
+            (DataType::Bool(a), DataType::Bool(b)) => a == b,
+            (DataType::Int(a), DataType::Int(b)) => a == b,
+            (DataType::Float(a), DataType::Float(b)) => canonical_bits(*a) == canonical_bits(*b),
+            (DataType::String(a), DataType::String(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for DataType {}
+
+impl Hash for DataType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            DataType::None => (),
+            DataType::Bool(b) => b.hash(state),
+            DataType::Int(i) => i.hash(state),
+            DataType::Float(f) => canonical_bits(*f).hash(state),
+            DataType::String(s) => s.hash(state),
+        }
+    }
+}
+
 impl Display for DataType {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
@@ -24,6 +69,206 @@ impl Display for DataType {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DType {
+    Bool,
+    Int,
+    Float,
+    String,
+}
+
+impl From<bool> for DataType {
+    fn from(b: bool) -> Self {
+        DataType::Bool(b)
+    }
+}
+
+impl From<i32> for DataType {
+    fn from(i: i32) -> Self {
+        DataType::Int(i)
+    }
+}
+
+impl From<f32> for DataType {
+    fn from(f: f32) -> Self {
+        DataType::Float(f)
+    }
+}
+
+impl From<f64> for DataType {
+    fn from(f: f64) -> Self {
+        DataType::Float(f as f32)
+    }
+}
+
+impl From<&str> for DataType {
+    fn from(s: &str) -> Self {
+        DataType::String(s.to_string())
+    }
+}
+
+impl From<String> for DataType {
+    fn from(s: String) -> Self {
+        DataType::String(s)
+    }
+}
+
+impl DataType {
+    pub fn is_null(&self) -> bool {
+        match self {
+            DataType::None => true,
+            DataType::String(s) => s.is_empty(),
+            _ => false,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DataType::Int(i) => Some(*i as f64),
+            DataType::Float(f) => Some(*f as f64),
+            _ => None,
+        }
+    }
+
+    pub fn dtype(&self) -> Option<DType> {
+        match self {
+            DataType::None => None,
+            DataType::Bool(_) => Some(DType::Bool),
+            DataType::Int(_) => Some(DType::Int),
+            DataType::Float(_) => Some(DType::Float),
+            DataType::String(_) => Some(DType::String),
+        }
+    }
+
+    /// Converts the value to another type, returning `DataType::None` for
+    /// nulls and values that cannot be represented in the target type.
+    pub fn cast(&self, to: DType) -> DataType {
+        if self.is_null() {
+            return DataType::None;
+        }
+        match (self, to) {
+            (DataType::Bool(b), DType::Int) => DataType::Int(*b as i32),
+            (DataType::Bool(b), DType::Float) => DataType::Float(*b as i32 as f32),
+            (DataType::Int(i), DType::Bool) => DataType::Bool(*i != 0),
+            (DataType::Int(i), DType::Float) => DataType::Float(*i as f32),
+            (DataType::Float(f), DType::Bool) => DataType::Bool(*f != 0.0),
+            (DataType::Float(f), DType::Int) => {
+                // `as` would saturate out-of-range values and map NaN to 0.
+                let t = f.trunc();
+                if t >= i32::MIN as f32 && t < -(i32::MIN as f32) {
+                    DataType::Int(t as i32)
+                } else {
+                    DataType::None
+                }
+            }
+            (DataType::String(s), DType::Bool) => match s.trim().to_lowercase().as_str() {
+                "true" => DataType::Bool(true),
+                "false" => DataType::Bool(false),
+                _ => DataType::None,
+            },
+            (DataType::String(s), DType::Int) => s
+                .trim()
+                .parse::<i32>()
+                .map_or(DataType::None, DataType::Int),
+            (DataType::String(s), DType::Float) => s
+                .trim()
+                .parse::<f32>()
+                .map_or(DataType::None, DataType::Float),
+            (v, DType::String) => DataType::String(v.to_string()),
+            (v, _) => v.clone(),
+        }
+    }
+
+    /// Compares two values, treating `Int` and `Float` as comparable numbers.
+    /// Returns `None` if either value is null or the types are incompatible.
+    pub fn compare(&self, other: &DataType) -> Option<Ordering> {
+        match (self, other) {
+            (DataType::Int(a), DataType::Int(b)) => Some(a.cmp(b)),
+            (DataType::Bool(a), DataType::Bool(b)) => Some(a.cmp(b)),
+            (DataType::String(a), DataType::String(b)) if !a.is_empty() && !b.is_empty() => {
+                Some(a.cmp(b))
+            }
+            _ => match (self.as_f64(), other.as_f64()) {
+                (Some(a), Some(b)) => a.partial_cmp(&b),
+                _ => None,
+            },
+        }
+    }
+
+    fn arithmetic(
+        self,
+        rhs: DataType,
+        int_op: fn(i32, i32) -> Option<i32>,
+        float_op: fn(f32, f32) -> f32,
+    ) -> DataType {
+        match (&self, &rhs) {
+            (DataType::Int(a), DataType::Int(b)) => int_op(*a, *b).map_or_else(
+                || DataType::Float(float_op(*a as f32, *b as f32)),
+                DataType::Int,
+            ),
+            _ => match (self.as_f64(), rhs.as_f64()) {
+                (Some(a), Some(b)) => DataType::Float(float_op(a as f32, b as f32)),
+                _ => DataType::None,
+            },
+        }
+    }
+}
+
+/// Arithmetic on `DataType` propagates nulls: any operation involving a null or
+/// a non-numeric value yields `DataType::None`, except `String + String` which
+/// concatenates. Integer overflow falls back to `Float`.
+impl Add for DataType {
+    type Output = DataType;
+
+    fn add(self, rhs: DataType) -> DataType {
+        match (&self, &rhs) {
+            (DataType::String(a), DataType::String(b)) if !a.is_empty() && !b.is_empty() => {
+                DataType::String(format!("{}{}", a, b))
+            }
+            _ => self.arithmetic(rhs, i32::checked_add, |a, b| a + b),
+        }
+    }
+}
+
+impl Sub for DataType {
+    type Output = DataType;
+
+    fn sub(self, rhs: DataType) -> DataType {
+        self.arithmetic(rhs, i32::checked_sub, |a, b| a - b)
+    }
+}
+
+impl Mul for DataType {
+    type Output = DataType;
+
+    fn mul(self, rhs: DataType) -> DataType {
+        self.arithmetic(rhs, i32::checked_mul, |a, b| a * b)
+    }
+}
+
+/// Division always produces a `Float`, even for two `Int` operands.
+impl Div for DataType {
+    type Output = DataType;
+
+    fn div(self, rhs: DataType) -> DataType {
+        self.arithmetic(rhs, |_, _| None, |a, b| a / b)
+    }
+}
+
+impl Neg for DataType {
+    type Output = DataType;
+
+    fn neg(self) -> DataType {
+        match self {
+            DataType::Int(i) => i
+                .checked_neg()
+                .map_or(DataType::Float(-(i as f32)), DataType::Int),
+            DataType::Float(f) => DataType::Float(-f),
+            _ => DataType::None,
+        }
+    }
+}
+
 pub fn predict_type(s: &str) -> DataType {
     lazy_static! {
         static ref BOOL_RE: Regex = Regex::new(r"^(true|false)$").unwrap();
@@ -57,4 +302,84 @@ mod tests {
             super::DataType::String("hello".to_string())
         );
     }
+
+    #[test]
+    fn test_arithmetic() {
+        use super::DataType;
+        assert_eq!(DataType::Int(2) + DataType::Int(3), DataType::Int(5));
+        assert_eq!(
+            DataType::Int(2) * DataType::Float(1.5),
+            DataType::Float(3.0)
+        );
+        assert_eq!(DataType::Int(3) / DataType::Int(2), DataType::Float(1.5));
+        assert_eq!(DataType::Int(1) - DataType::None, DataType::None);
+        assert_eq!(
+            DataType::Int(i32::MAX) + DataType::Int(1),
+            DataType::Float(i32::MAX as f32 + 1.0)
+        );
+        assert_eq!(
+            DataType::from("a") + DataType::from("b"),
+            DataType::from("ab")
+        );
+        assert_eq!(DataType::Bool(true) + DataType::Int(1), DataType::None);
+        assert_eq!(-DataType::Float(1.5), DataType::Float(-1.5));
+    }
+
+    #[test]
+    fn test_compare_and_cast() {
+        use super::{DType, DataType};
+        use std::cmp::Ordering;
+        assert_eq!(
+            DataType::Int(1).compare(&DataType::Float(1.5)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(DataType::Int(1).compare(&DataType::None), None);
+        assert_eq!(DataType::from("3").cast(DType::Int), DataType::Int(3));
+        assert_eq!(DataType::from("x").cast(DType::Float), DataType::None);
+        assert_eq!(DataType::Float(2.7).cast(DType::Int), DataType::Int(2));
+        assert_eq!(DataType::Float(-2.7).cast(DType::Int), DataType::Int(-2));
+        assert_eq!(
+            DataType::Float(-2147483648.0).cast(DType::Int),
+            DataType::Int(i32::MIN)
+        );
+        assert_eq!(
+            DataType::Float(2147483648.0).cast(DType::Int),
+            DataType::None
+        );
+        assert_eq!(DataType::Float(-3e9).cast(DType::Int), DataType::None);
+        assert_eq!(DataType::Float(f32::NAN).cast(DType::Int), DataType::None);
+        assert_eq!(
+            DataType::Float(f32::INFINITY).cast(DType::Int),
+            DataType::None
+        );
+        assert_eq!(DataType::Int(0).cast(DType::Bool), DataType::Bool(false));
+        assert_eq!(DataType::Int(7).cast(DType::String), DataType::from("7"));
+    }
+
+    #[test]
+    fn test_float_eq_hash() {
+        use super::DataType;
+        use std::collections::HashSet;
+        assert_eq!(DataType::Float(f32::NAN), DataType::Float(-f32::NAN));
+        assert_eq!(DataType::Float(0.0), DataType::Float(-0.0));
+        assert_ne!(DataType::Int(1), DataType::Float(1.0));
+        let set: HashSet<DataType> = [
+            DataType::Float(f32::NAN),
+            DataType::Float(f32::NAN),
+            DataType::Float(-0.0),
+            DataType::Float(0.0),
+            DataType::Int(0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_is_null() {
+        assert!(super::DataType::None.is_null());
+        assert!(super::DataType::String("".to_string()).is_null());
+        assert!(!super::DataType::String("a".to_string()).is_null());
+        assert!(!super::DataType::Int(0).is_null());
+    }
 }
@@ -0,0 +1,448 @@
+use crate::distributions::{ChiSquared, ContinuousDistribution, StudentT};
+use crate::error::check_confidence;
+use crate::linalg::Matrix;
+use crate::*;
+use std::fmt;
+
+/// Heteroscedasticity-consistent covariance estimators, weighting each
+/// squared residual `e_i^2` differently.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RobustCov {
+    /// White's original estimator, `e_i^2`.
+    Hc0,
+    /// `e_i^2 * n / (n - p)`.
+    Hc1,
+    /// `e_i^2 / (1 - h_i)`.
+    Hc2,
+    /// `e_i^2 / (1 - h_i)^2`, recommended for small samples.
+    Hc3,
+}
+
+impl fmt::Display for RobustCov {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RobustCov::Hc0 => write!(f, "HC0"),
+            RobustCov::Hc1 => write!(f, "HC1"),
+            RobustCov::Hc2 => write!(f, "HC2"),
+            RobustCov::Hc3 => write!(f, "HC3"),
+        }
+    }
+}
+
+fn from_columns(columns: &[&[f64]]) -> Matrix {
+    let n = columns.first().map_or(0, |c| c.len());
+    let mut m = Matrix::zeros(n, columns.len());
+    for (j, col) in columns.iter().enumerate() {
+        for (i, v) in col.iter().enumerate() {
+            m[(i, j)] = *v;
+        }
+    }
+    m
+}
+
+/// Centered R-squared of regressing `y` on an intercept and `columns`,
+/// skipping columns that are collinear with earlier ones. Also returns the
+/// number of columns kept.
+fn auxiliary_r_squared(columns: &[Vec<f64>], y: &[f64]) -> Result<(f64, usize), StatustError> {
+    let ones = vec![1.0; y.len()];
+    let mut kept: Vec<&[f64]> = vec![&ones];
+    for col in columns {
+        let norm = col.iter().map(|v| v * v).sum::<f64>().sqrt();
+        let mut candidate = kept.clone();
+        candidate.push(col);
+        let r = from_columns(&candidate).qr()?.r;
+        let last = candidate.len() - 1;
+        if norm > 0.0 && r[(last, last)].abs() > 1e-7 * norm {
+            kept = candidate;
+        }
+    }
+    if kept.len() >= y.len() {
+        return Err(StatustError::invalid(
+            "auxiliary regression has more columns than observations".to_string(),
+        ));
+    }
+    let x = from_columns(&kept);
+    let fitted = x.mul_vec(&x.qr()?.solve(y)?)?;
+    let mean = stats::mean(y);
+    let rss: f64 = y.iter().zip(&fitted).map(|(a, b)| (a - b).powi(2)).sum();
+    let tss: f64 = y.iter().map(|v| (v - mean).powi(2)).sum();
+    Ok((1.0 - rss / tss, kept.len() - 1))
+}
+
+impl OlsResult {
+    fn predictor_columns(&self) -> Vec<Vec<f64>> {
+        (0..self.design.x.ncols())
+            .filter(|&j| self.design.columns[j] != "(Intercept)")
+            .map(|j| self.design.x.col(j))
+            .collect()
+    }
+
+    fn n_coefficients(&self) -> f64 {
+        self.coefficients.len() as f64
+    }
+
+    /// Diagonal of the hat matrix `X (X^T X)^-1 X^T`.
+    pub fn leverage(&self) -> Vec<f64> {
+        let (x, c) = (&self.design.x, &self.cov_unscaled);
+        (0..x.nrows())
+            .map(|i| {
+                let row = x.row(i);
+                let cx = c.mul_vec(row).unwrap();
+                row.iter().zip(&cx).map(|(a, b)| a * b).sum()
+            })
+            .collect()
+    }
+
+    /// Residuals divided by their estimated standard deviation,
+    /// `e_i / (sigma * sqrt(1 - h_i))` (internally studentized).
+    pub fn standardized_residuals(&self) -> Vec<f64> {
+        self.residuals
+            .iter()
+            .zip(self.leverage())
+            .map(|(e, h)| e / (self.sigma * (1.0 - h).sqrt()))
+            .collect()
+    }
+
+    /// Externally studentized residuals, using the residual variance of the
+    /// fit without observation `i`. They follow a t distribution on
+    /// `df_residual - 1` degrees of freedom.
+    pub fn studentized_residuals(&self) -> Vec<f64> {
+        let rss = self.sigma.powi(2) * self.df_residual;
+        self.residuals
+            .iter()
+            .zip(self.leverage())
+            .map(|(e, h)| {
+                let s2 = (rss - e * e / (1.0 - h)) / (self.df_residual - 1.0);
+                e / (s2 * (1.0 - h)).sqrt()
+            })
+            .collect()
+    }
+
+    pub fn cooks_distance(&self) -> Vec<f64> {
+        let p = self.n_coefficients();
+        self.residuals
+            .iter()
+            .zip(self.leverage())
+            .map(|(e, h)| e * e * h / (p * self.sigma.powi(2) * (1.0 - h).powi(2)))
+            .collect()
+    }
+
+    /// Variance inflation factor `1 / (1 - R_j^2)` of each design column
+    /// except the intercept, where `R_j^2` comes from regressing the column
+    /// on an intercept and the other columns.
+    pub fn vif(&self) -> Result<Vec<(String, f64)>, StatustError> {
+        let names: Vec<&String> = self
+            .design
+            .columns
+            .iter()
+            .filter(|c| *c != "(Intercept)")
+            .collect();
+        let columns = self.predictor_columns();
+        (0..columns.len())
+            .map(|j| {
+                let others: Vec<Vec<f64>> = columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(k, _)| *k != j)
+                    .map(|(_, c)| c.clone())
+                    .collect();
+                let (r2, _) = auxiliary_r_squared(&others, &columns[j])?;
+                Ok((names[j].clone(), 1.0 / (1.0 - r2)))
+            })
+            .collect()
+    }
+
+    /// Durbin-Watson statistic for first-order autocorrelation of the
+    /// residuals in row order; values near 2 indicate none.
+    pub fn durbin_watson(&self) -> f64 {
+        let e = &self.residuals;
+        let num: f64 = e.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum();
+        num / e.iter().map(|v| v * v).sum::<f64>()
+    }
+
+    fn lm_test(&self, method: &str, columns: &[Vec<f64>]) -> Result<TestResult, StatustError> {
+        if columns.is_empty() {
+            return Err(StatustError::invalid(format!(
+                "{} needs at least one predictor",
+                method
+            )));
+        }
+        let squared: Vec<f64> = self.residuals.iter().map(|e| e * e).collect();
+        let (r2, df) = auxiliary_r_squared(columns, &squared)?;
+        let statistic = self.nobs as f64 * r2;
+        let df = df as f64;
+        let mut result = TestResult::new(
+            method,
+            statistic,
+            ChiSquared::new(df)?.sf(statistic),
+            Alternative::Greater,
+        );
+        result.data = self.formula.to_string();
+        result.parameters.push(("df".to_string(), df));
+        Ok(result)
+    }
+
+    /// Koenker's studentized Breusch-Pagan test: `n R^2` of regressing the
+    /// squared residuals on the model's predictors.
+    pub fn breusch_pagan(&self) -> Result<TestResult, StatustError> {
+        self.lm_test("Studentized Breusch-Pagan test", &self.predictor_columns())
+    }
+
+    /// White's test: like Breusch-Pagan, with the squares and cross-products
+    /// of the predictors added. Redundant columns, such as squared
+    /// indicators, are dropped.
+    pub fn white_test(&self) -> Result<TestResult, StatustError> {
+        let mut columns = self.predictor_columns();
+        let k = columns.len();
+        for a in 0..k {
+            for b in a..k {
+                let product = columns[a]
+                    .iter()
+                    .zip(&columns[b])
+                    .map(|(u, v)| u * v)
+                    .collect();
+                columns.push(product);
+            }
+        }
+        self.lm_test("White test for heteroscedasticity", &columns)
+    }
+
+    /// Sandwich estimate of the coefficient covariance,
+    /// `(X^T X)^-1 X^T diag(w) X (X^T X)^-1`.
+    pub fn robust_cov(&self, kind: RobustCov) -> Result<Matrix, StatustError> {
+        let x = &self.design.x;
+        let (n, p) = (x.nrows() as f64, self.n_coefficients());
+        let leverage = self.leverage();
+        let mut meat = Matrix::zeros(x.ncols(), x.ncols());
+        for (i, e) in self.residuals.iter().enumerate() {
+            let w = match kind {
+                RobustCov::Hc0 => e * e,
+                RobustCov::Hc1 => e * e * n / (n - p),
+                RobustCov::Hc2 => e * e / (1.0 - leverage[i]),
+                RobustCov::Hc3 => e * e / (1.0 - leverage[i]).powi(2),
+            };
+            let row = x.row(i);
+            for a in 0..row.len() {
+                for b in 0..row.len() {
+                    meat[(a, b)] += w * row[a] * row[b];
+                }
+            }
+        }
+        self.cov_unscaled.matmul(&meat)?.matmul(&self.cov_unscaled)
+    }
+
+    /// Coefficient table with heteroscedasticity-consistent standard
+    /// errors; t-tests and intervals keep the residual degrees of freedom.
+    pub fn robust_coefficients(&self, kind: RobustCov) -> Result<Vec<Coefficient>, StatustError> {
+        let cov = self.robust_cov(kind)?;
+        let dist = StudentT::new(self.df_residual)?;
+        let q = dist.quantile(0.5 + self.confidence / 2.0);
+        Ok(self
+            .coefficients
+            .iter()
+            .enumerate()
+            .map(|(j, c)| {
+                let std_error = cov[(j, j)].sqrt();
+                let t = c.estimate / std_error;
+                Coefficient {
+                    name: c.name.clone(),
+                    estimate: c.estimate,
+                    std_error,
+                    statistic: t,
+                    p_value: Alternative::TwoSided.p_value(&dist, t),
+                    lower: c.estimate - q * std_error,
+                    upper: c.estimate + q * std_error,
+                }
+            })
+            .collect())
+    }
+
+    /// Runs all residual diagnostics. Observations with a Cook's distance
+    /// above `4 / n` are reported as influential.
+    pub fn diagnostics(&self) -> Result<RegressionDiagnostics, StatustError> {
+        let cooks_distance = self.cooks_distance();
+        let cutoff = 4.0 / self.nobs as f64;
+        Ok(RegressionDiagnostics {
+            formula: self.formula.to_string(),
+            leverage: self.leverage(),
+            studentized_residuals: self.studentized_residuals(),
+            influential: cooks_distance
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| **d > cutoff)
+                .map(|(i, _)| self.design.rows[i])
+                .collect(),
+            cooks_distance,
+            vif: self.vif()?,
+            durbin_watson: self.durbin_watson(),
+            breusch_pagan: self.breusch_pagan()?,
+            white: self.white_test()?,
+        })
+    }
+
+    /// Predictions for the rows of `df` without nulls in the predictors,
+    /// with confidence intervals for the mean response and prediction
+    /// intervals for a new observation.
+    pub fn predict(&self, df: &DataFrame, confidence: f64) -> Result<Prediction, StatustError> {
+        check_confidence(confidence)?;
+        let design = self.formula.design_like(df, &self.design)?;
+        let beta: Vec<f64> = self.coefficients.iter().map(|c| c.estimate).collect();
+        let q = StudentT::new(self.df_residual)?.quantile(0.5 + confidence / 2.0);
+        let s2 = self.sigma.powi(2);
+        let mut prediction = Prediction {
+            confidence,
+            rows: design.rows.clone(),
+            fit: design.x.mul_vec(&beta)?,
+            se_fit: Vec::new(),
+            conf_lower: Vec::new(),
+            conf_upper: Vec::new(),
+            pred_lower: Vec::new(),
+            pred_upper: Vec::new(),
+        };
+        for (i, fit) in prediction.fit.iter().enumerate() {
+            let row = design.x.row(i);
+            let cx = self.cov_unscaled.mul_vec(row)?;
+            let se = (s2 * row.iter().zip(&cx).map(|(a, b)| a * b).sum::<f64>()).sqrt();
+            let se_pred = (s2 + se * se).sqrt();
+            prediction.se_fit.push(se);
+            prediction.conf_lower.push(fit - q * se);
+            prediction.conf_upper.push(fit + q * se);
+            prediction.pred_lower.push(fit - q * se_pred);
+            prediction.pred_upper.push(fit + q * se_pred);
+        }
+        Ok(prediction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    fn iris_fit() -> OlsResult {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        df.ols("sepal.length ~ sepal.width + variety", 0.95)
+            .unwrap()
+    }
+
+    // Reference values computed in 50-digit arithmetic with mpmath on the f32
+    // values the CSV reader stores, from the textbook formulas, as in R's hatvalues, rstandard, rstudent,
+    // cooks.distance, lmtest::bptest and sandwich::vcovHC.
+
+    #[test]
+    fn test_influence() {
+        let fit = iris_fit();
+        let h = fit.leverage();
+        assert_close(h[0], 0.0203056242834, 1e-12);
+        assert_close(h.iter().sum(), 4.0, 1e-10);
+        assert_close(fit.standardized_residuals()[0], 0.08337852895683, 1e-10);
+        let rstudent = fit.studentized_residuals();
+        assert_close(rstudent[0], 0.08309447377349, 1e-10);
+        assert_close(rstudent[106], -3.125265811101, 1e-9);
+        let cooks = fit.cooks_distance();
+        assert_close(cooks[0], 3.602252879749e-5, 1e-15);
+        assert_close(cooks[106], 0.07921527317028, 1e-11);
+
+        let vif = fit.vif().unwrap();
+        assert_eq!(vif[0].0, "sepal.width");
+        assert_close(vif[0].1, 1.66884409276, 1e-9);
+        assert_close(vif[1].1, 2.184184256835, 1e-9);
+        assert_close(vif[2].1, 1.738387800361, 1e-9);
+        assert_close(fit.durbin_watson(), 2.065712825408, 1e-10);
+    }
+
+    #[test]
+    fn test_heteroscedasticity() {
+        let fit = iris_fit();
+        let bp = fit.breusch_pagan().unwrap();
+        assert_close(bp.statistic, 19.36433952402, 1e-8);
+        assert_close(bp.p_value, 0.0002298430756256, 1e-12);
+        assert_eq!(bp.parameters[0].1, 3.0);
+        let white = fit.white_test().unwrap();
+        assert_close(white.statistic, 22.295941639, 1e-8);
+        assert_close(white.p_value, 0.001070060310209, 1e-12);
+        assert_eq!(white.parameters[0].1, 6.0);
+
+        let diagnostics = fit.diagnostics().unwrap();
+        assert_eq!(
+            diagnostics.influential,
+            vec![68, 105, 106, 118, 122, 130, 131, 135]
+        );
+        assert!(diagnostics.to_string().contains("Durbin-Watson"));
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let intercept_only = df.ols("sepal.length ~ 1", 0.95).unwrap();
+        assert!(intercept_only.breusch_pagan().is_err());
+    }
+
+    #[test]
+    fn test_robust_standard_errors() {
+        let fit = iris_fit();
+        let expected = [
+            (
+                RobustCov::Hc0,
+                0.104763270503,
+                0.09097442583517,
+                2.231022042132e-12,
+            ),
+            (
+                RobustCov::Hc1,
+                0.1061886866433,
+                0.09221223002288,
+                3.947116558634e-12,
+            ),
+            (
+                RobustCov::Hc2,
+                0.1069388669508,
+                0.09254603265486,
+                5.290539800193e-12,
+            ),
+            (
+                RobustCov::Hc3,
+                0.1091814440756,
+                0.09416466325792,
+                1.233877997064e-11,
+            ),
+        ];
+        for (kind, se_width, se_virginica, p_width) in expected {
+            let coefs = fit.robust_coefficients(kind).unwrap();
+            assert_close(coefs[1].std_error, se_width, 1e-11);
+            assert_close(coefs[3].std_error, se_virginica, 1e-11);
+            assert_close(coefs[1].p_value, p_width, 1e-18);
+            assert_eq!(coefs[1].estimate, fit.coefficients[1].estimate);
+        }
+        assert_close(
+            fit.robust_coefficients(RobustCov::Hc0).unwrap()[0].std_error,
+            0.3620716198811,
+            1e-11,
+        );
+    }
+
+    #[test]
+    fn test_predict() {
+        let fit = iris_fit();
+        let new = DataFrame::from_columns(
+            vec!["sepal.width".to_string(), "variety".to_string()],
+            vec![
+                vec![DataType::Float(3.0), DataType::Float(2.5), DataType::None],
+                vec![
+                    DataType::String("Virginica".to_string()),
+                    DataType::String("Setosa".to_string()),
+                    DataType::String("Setosa".to_string()),
+                ],
+            ],
+        )
+        .unwrap();
+        let pred = fit.predict(&new, 0.95).unwrap();
+        assert_eq!(pred.rows, vec![0, 1]);
+        assert_close(pred.fit[0], 6.608892589935, 1e-10);
+        assert_close(pred.se_fit[0], 0.06199808521561, 1e-11);
+        assert_close(pred.conf_lower[0], 6.486362943626, 1e-9);
+        assert_close(pred.conf_upper[0], 6.731422236244, 1e-9);
+        assert_close(pred.pred_lower[0], 5.734709350745, 1e-9);
+        assert_close(pred.pred_upper[0], 7.483075829125, 1e-9);
+        assert_close(pred.fit[1], 4.260295564255, 1e-10);
+        assert_close(pred.pred_upper[1], 5.155953712236, 1e-9);
+        assert!(pred.to_string().contains("95% CI"));
+        assert!(fit.predict(&new, 0.0).is_err());
+    }
+}
@@ -0,0 +1,1205 @@
+use super::{check, ContinuousDistribution};
+use crate::special::{beta_inc, erfc, gamma, gamma_p, gamma_q, ln_beta, ln_gamma, ndtri};
+use crate::*;
+use std::f64::consts::{PI, SQRT_2};
+
+const LN_SQRT_2PI: f64 = 0.918_938_533_204_672_8;
+
+pub(crate) fn standard_normal(rng: &mut dyn Rng) -> f64 {
+    // Marsaglia's polar method, discarding the second variate.
+    loop {
+        let u = 2.0 * rng.next_f64() - 1.0;
+        let v = 2.0 * rng.next_f64() - 1.0;
+        let s = u * u + v * v;
+        if s > 0.0 && s < 1.0 {
+            return u * (-2.0 * s.ln() / s).sqrt();
+        }
+    }
+}
+
+/// Gamma variate with unit rate (Marsaglia and Tsang).
+pub(crate) fn standard_gamma(shape: f64, rng: &mut dyn Rng) -> f64 {
+    if shape < 1.0 {
+        return standard_gamma(shape + 1.0, rng) * rng.next_f64_open().powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = standard_normal(rng);
+        let v = 1.0 + c * x;
+        if v <= 0.0 {
+            continue;
+        }
+        let v = v * v * v;
+        let u = rng.next_f64_open();
+        if u < 1.0 - 0.0331 * x.powi(4) || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+/// Numerically inverts the cdf on the support `[lower, upper]` with a
+/// Newton iteration safeguarded by bisection. Upper-tail probabilities are
+/// matched through `sf` to keep their precision.
+pub(crate) fn invert_cdf<D: ContinuousDistribution + ?Sized>(
+    d: &D,
+    p: f64,
+    lower: f64,
+    upper: f64,
+) -> f64 {
+    if p.is_nan() || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return lower;
+    }
+    if p == 1.0 {
+        return upper;
+    }
+    let upper_tail = p > 0.5;
+    // Increasing in x, zero at the quantile.
+    let f = |x: f64| {
+        if upper_tail {
+            (1.0 - p) - d.sf(x)
+        } else {
+            d.cdf(x) - p
+        }
+    };
+
+    let mut lo = lower;
+    if !lo.is_finite() {
+        lo = -1.0;
+        while f(lo) > 0.0 && lo.is_finite() {
+            lo *= 2.0;
+        }
+    }
+    let mut hi = upper;
+    if !hi.is_finite() {
+        hi = lo.max(0.0) + 1.0;
+        while f(hi) < 0.0 && hi.is_finite() {
+            hi *= 2.0;
+        }
+    }
+
+    let mut x = 0.5 * (lo + hi);
+    for _ in 0..500 {
+        let fx = f(x);
+        if fx == 0.0 {
+            return x;
+        }
+        if fx < 0.0 {
+            lo = x;
+        } else {
+            hi = x;
+        }
+        let mut next = x - fx / d.pdf(x);
+        if !next.is_finite() || next <= lo || next >= hi {
+            next = 0.5 * (lo + hi);
+        }
+        if (next - x).abs() <= 1e-15 * x.abs() || hi - lo <= 1e-15 * hi.abs().max(lo.abs()) {
+            return next;
+        }
+        x = next;
+    }
+    x
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Normal {
+    mean: f64,
+    sd: f64,
+}
+
+impl Normal {
+    pub fn new(mean: f64, sd: f64) -> Result<Normal, StatustError> {
+        check(mean.is_finite(), "normal mean must be finite")?;
+        check(sd > 0.0 && sd.is_finite(), "normal sd must be positive")?;
+        Ok(Normal { mean, sd })
+    }
+
+    pub fn standard() -> Normal {
+        Normal { mean: 0.0, sd: 1.0 }
+    }
+}
+
+impl ContinuousDistribution for Normal {
+    fn pdf(&self, x: f64) -> f64 {
+        self.log_pdf(x).exp()
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        let z = (x - self.mean) / self.sd;
+        -0.5 * z * z - LN_SQRT_2PI - self.sd.ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        0.5 * erfc(-(x - self.mean) / (self.sd * SQRT_2))
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        0.5 * erfc((x - self.mean) / (self.sd * SQRT_2))
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        self.mean + self.sd * ndtri(p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    fn variance(&self) -> f64 {
+        self.sd * self.sd
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        self.mean + self.sd * standard_normal(rng)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogNormal {
+    mu: f64,
+    sigma: f64,
+}
+
+impl LogNormal {
+    /// `mu` and `sigma` are the mean and standard deviation of `ln(X)`.
+    pub fn new(mu: f64, sigma: f64) -> Result<LogNormal, StatustError> {
+        check(mu.is_finite(), "log-normal mu must be finite")?;
+        check(
+            sigma > 0.0 && sigma.is_finite(),
+            "log-normal sigma must be positive",
+        )?;
+        Ok(LogNormal { mu, sigma })
+    }
+}
+
+impl ContinuousDistribution for LogNormal {
+    fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            self.log_pdf(x).exp()
+        }
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        let z = (x.ln() - self.mu) / self.sigma;
+        -0.5 * z * z - LN_SQRT_2PI - self.sigma.ln() - x.ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            0.5 * erfc(-(x.ln() - self.mu) / (self.sigma * SQRT_2))
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            0.5 * erfc((x.ln() - self.mu) / (self.sigma * SQRT_2))
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        (self.mu + self.sigma * ndtri(p)).exp()
+    }
+
+    fn mean(&self) -> f64 {
+        (self.mu + 0.5 * self.sigma * self.sigma).exp()
+    }
+
+    fn variance(&self) -> f64 {
+        let s2 = self.sigma * self.sigma;
+        s2.exp_m1() * (2.0 * self.mu + s2).exp()
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        (self.mu + self.sigma * standard_normal(rng)).exp()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Uniform {
+    low: f64,
+    high: f64,
+}
+
+impl Uniform {
+    pub fn new(low: f64, high: f64) -> Result<Uniform, StatustError> {
+        check(
+            low.is_finite() && high.is_finite() && low < high,
+            "uniform bounds must be finite with low < high",
+        )?;
+        Ok(Uniform { low, high })
+    }
+}
+
+impl ContinuousDistribution for Uniform {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.low || x > self.high {
+            0.0
+        } else {
+            1.0 / (self.high - self.low)
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        ((x - self.low) / (self.high - self.low)).clamp(0.0, 1.0)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        self.low + p * (self.high - self.low)
+    }
+
+    fn mean(&self) -> f64 {
+        0.5 * (self.low + self.high)
+    }
+
+    fn variance(&self) -> f64 {
+        (self.high - self.low).powi(2) / 12.0
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        rng.gen_range_f64(self.low, self.high)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Exponential {
+    rate: f64,
+}
+
+impl Exponential {
+    pub fn new(rate: f64) -> Result<Exponential, StatustError> {
+        check(
+            rate > 0.0 && rate.is_finite(),
+            "exponential rate must be positive",
+        )?;
+        Ok(Exponential { rate })
+    }
+}
+
+impl ContinuousDistribution for Exponential {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            0.0
+        } else {
+            self.rate * (-self.rate * x).exp()
+        }
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            self.rate.ln() - self.rate * x
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            -(-self.rate * x).exp_m1()
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            (-self.rate * x).exp()
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        -(-p).ln_1p() / self.rate
+    }
+
+    fn mean(&self) -> f64 {
+        1.0 / self.rate
+    }
+
+    fn variance(&self) -> f64 {
+        1.0 / (self.rate * self.rate)
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        -rng.next_f64_open().ln() / self.rate
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gamma {
+    shape: f64,
+    rate: f64,
+}
+
+impl Gamma {
+    /// Gamma distribution with density proportional to `x^(shape-1) e^(-rate x)`.
+    pub fn new(shape: f64, rate: f64) -> Result<Gamma, StatustError> {
+        check(
+            shape > 0.0 && shape.is_finite(),
+            "gamma shape must be positive",
+        )?;
+        check(
+            rate > 0.0 && rate.is_finite(),
+            "gamma rate must be positive",
+        )?;
+        Ok(Gamma { shape, rate })
+    }
+}
+
+impl ContinuousDistribution for Gamma {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            0.0
+        } else if x == 0.0 {
+            if self.shape < 1.0 {
+                f64::INFINITY
+            } else if self.shape == 1.0 {
+                self.rate
+            } else {
+                0.0
+            }
+        } else {
+            self.log_pdf(x).exp()
+        }
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        let power = if self.shape == 1.0 {
+            0.0
+        } else {
+            (self.shape - 1.0) * x.ln()
+        };
+        self.shape * self.rate.ln() + power - self.rate * x - ln_gamma(self.shape)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            gamma_p(self.shape, self.rate * x)
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            gamma_q(self.shape, self.rate * x)
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        invert_cdf(self, p, 0.0, f64::INFINITY)
+    }
+
+    fn mean(&self) -> f64 {
+        self.shape / self.rate
+    }
+
+    fn variance(&self) -> f64 {
+        self.shape / (self.rate * self.rate)
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        standard_gamma(self.shape, rng) / self.rate
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Beta {
+    a: f64,
+    b: f64,
+}
+
+impl Beta {
+    pub fn new(a: f64, b: f64) -> Result<Beta, StatustError> {
+        check(
+            a > 0.0 && b > 0.0 && a.is_finite() && b.is_finite(),
+            "beta parameters must be positive",
+        )?;
+        Ok(Beta { a, b })
+    }
+}
+
+impl ContinuousDistribution for Beta {
+    fn pdf(&self, x: f64) -> f64 {
+        if !(0.0..=1.0).contains(&x) {
+            0.0
+        } else {
+            self.log_pdf(x).exp()
+        }
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        if !(0.0..=1.0).contains(&x) {
+            return f64::NEG_INFINITY;
+        }
+        // Skip a term whose exponent is zero so the boundaries avoid 0 * ln(0).
+        let left = if self.a == 1.0 {
+            0.0
+        } else {
+            (self.a - 1.0) * x.ln()
+        };
+        let right = if self.b == 1.0 {
+            0.0
+        } else {
+            (self.b - 1.0) * (-x).ln_1p()
+        };
+        left + right - ln_beta(self.a, self.b)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        beta_inc(self.a, self.b, x)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        beta_inc(self.b, self.a, 1.0 - x)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        invert_cdf(self, p, 0.0, 1.0)
+    }
+
+    fn mean(&self) -> f64 {
+        self.a / (self.a + self.b)
+    }
+
+    fn variance(&self) -> f64 {
+        let s = self.a + self.b;
+        self.a * self.b / (s * s * (s + 1.0))
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let x = standard_gamma(self.a, rng);
+        let y = standard_gamma(self.b, rng);
+        x / (x + y)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChiSquared {
+    df: f64,
+    gamma: Gamma,
+}
+
+impl ChiSquared {
+    pub fn new(df: f64) -> Result<ChiSquared, StatustError> {
+        check(
+            df > 0.0 && df.is_finite(),
+            "chi-squared degrees of freedom must be positive",
+        )?;
+        Ok(ChiSquared {
+            df,
+            gamma: Gamma {
+                shape: df / 2.0,
+                rate: 0.5,
+            },
+        })
+    }
+}
+
+impl ContinuousDistribution for ChiSquared {
+    fn pdf(&self, x: f64) -> f64 {
+        self.gamma.pdf(x)
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        self.gamma.log_pdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        self.gamma.cdf(x)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.gamma.sf(x)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        self.gamma.quantile(p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.df
+    }
+
+    fn variance(&self) -> f64 {
+        2.0 * self.df
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        self.gamma.sample(rng)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StudentT {
+    df: f64,
+}
+
+impl StudentT {
+    pub fn new(df: f64) -> Result<StudentT, StatustError> {
+        check(
+            df > 0.0 && df.is_finite(),
+            "t degrees of freedom must be positive",
+        )?;
+        Ok(StudentT { df })
+    }
+}
+
+impl ContinuousDistribution for StudentT {
+    fn pdf(&self, x: f64) -> f64 {
+        self.log_pdf(x).exp()
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        let v = self.df;
+        ln_gamma((v + 1.0) / 2.0)
+            - ln_gamma(v / 2.0)
+            - 0.5 * (v * PI).ln()
+            - (v + 1.0) / 2.0 * (x * x / v).ln_1p()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_infinite() {
+            return if x > 0.0 { 1.0 } else { 0.0 };
+        }
+        let v = self.df;
+        let tail = 0.5 * beta_inc(v / 2.0, 0.5, v / (v + x * x));
+        if x > 0.0 {
+            1.0 - tail
+        } else {
+            tail
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.cdf(-x)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if p == 0.5 {
+            return 0.0;
+        }
+        invert_cdf(self, p, f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn mean(&self) -> f64 {
+        if self.df > 1.0 {
+            0.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        if self.df > 2.0 {
+            self.df / (self.df - 2.0)
+        } else if self.df > 1.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let z = standard_normal(rng);
+        let chi2 = 2.0 * standard_gamma(self.df / 2.0, rng);
+        z / (chi2 / self.df).sqrt()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FisherF {
+    d1: f64,
+    d2: f64,
+}
+
+impl FisherF {
+    pub fn new(d1: f64, d2: f64) -> Result<FisherF, StatustError> {
+        check(
+            d1 > 0.0 && d2 > 0.0 && d1.is_finite() && d2.is_finite(),
+            "F degrees of freedom must be positive",
+        )?;
+        Ok(FisherF { d1, d2 })
+    }
+}
+
+impl ContinuousDistribution for FisherF {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            0.0
+        } else {
+            self.log_pdf(x).exp()
+        }
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        let (d1, d2) = (self.d1, self.d2);
+        let power = if d1 == 2.0 {
+            0.0
+        } else {
+            (0.5 * d1 - 1.0) * x.ln()
+        };
+        0.5 * (d1 * d1.ln() + d2 * d2.ln() - (d1 + d2) * (d1 * x + d2).ln()) + power
+            - ln_beta(d1 / 2.0, d2 / 2.0)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            let dx = self.d1 * x;
+            beta_inc(self.d1 / 2.0, self.d2 / 2.0, dx / (dx + self.d2))
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            let dx = self.d1 * x;
+            beta_inc(self.d2 / 2.0, self.d1 / 2.0, self.d2 / (dx + self.d2))
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        invert_cdf(self, p, 0.0, f64::INFINITY)
+    }
+
+    fn mean(&self) -> f64 {
+        if self.d2 > 2.0 {
+            self.d2 / (self.d2 - 2.0)
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        let (d1, d2) = (self.d1, self.d2);
+        if d2 > 4.0 {
+            2.0 * d2 * d2 * (d1 + d2 - 2.0) / (d1 * (d2 - 2.0).powi(2) * (d2 - 4.0))
+        } else if d2 > 2.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let x1 = standard_gamma(self.d1 / 2.0, rng) / self.d1;
+        let x2 = standard_gamma(self.d2 / 2.0, rng) / self.d2;
+        x1 / x2
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cauchy {
+    location: f64,
+    scale: f64,
+}
+
+impl Cauchy {
+    pub fn new(location: f64, scale: f64) -> Result<Cauchy, StatustError> {
+        check(location.is_finite(), "Cauchy location must be finite")?;
+        check(
+            scale > 0.0 && scale.is_finite(),
+            "Cauchy scale must be positive",
+        )?;
+        Ok(Cauchy { location, scale })
+    }
+}
+
+impl ContinuousDistribution for Cauchy {
+    fn pdf(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+        1.0 / (PI * self.scale * (1.0 + z * z))
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        0.5 + ((x - self.location) / self.scale).atan() / PI
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        0.5 - ((x - self.location) / self.scale).atan() / PI
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        if p == 0.0 {
+            f64::NEG_INFINITY
+        } else if p == 1.0 {
+            f64::INFINITY
+        } else {
+            self.location + self.scale * (PI * (p - 0.5)).tan()
+        }
+    }
+
+    fn mean(&self) -> f64 {
+        f64::NAN
+    }
+
+    fn variance(&self) -> f64 {
+        f64::NAN
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        self.location + self.scale * (PI * (rng.next_f64_open() - 0.5)).tan()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Weibull {
+    shape: f64,
+    scale: f64,
+}
+
+impl Weibull {
+    pub fn new(shape: f64, scale: f64) -> Result<Weibull, StatustError> {
+        check(
+            shape > 0.0 && shape.is_finite(),
+            "Weibull shape must be positive",
+        )?;
+        check(
+            scale > 0.0 && scale.is_finite(),
+            "Weibull scale must be positive",
+        )?;
+        Ok(Weibull { shape, scale })
+    }
+}
+
+impl ContinuousDistribution for Weibull {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        let z = x / self.scale;
+        self.shape / self.scale * z.powf(self.shape - 1.0) * (-z.powf(self.shape)).exp()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            -(-(x / self.scale).powf(self.shape)).exp_m1()
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            (-(x / self.scale).powf(self.shape)).exp()
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        self.scale * (-(-p).ln_1p()).powf(1.0 / self.shape)
+    }
+
+    fn mean(&self) -> f64 {
+        self.scale * gamma(1.0 + 1.0 / self.shape)
+    }
+
+    fn variance(&self) -> f64 {
+        let g1 = gamma(1.0 + 1.0 / self.shape);
+        let g2 = gamma(1.0 + 2.0 / self.shape);
+        self.scale * self.scale * (g2 - g1 * g1)
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        self.scale * (-rng.next_f64_open().ln()).powf(1.0 / self.shape)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Laplace {
+    location: f64,
+    scale: f64,
+}
+
+impl Laplace {
+    pub fn new(location: f64, scale: f64) -> Result<Laplace, StatustError> {
+        check(location.is_finite(), "Laplace location must be finite")?;
+        check(
+            scale > 0.0 && scale.is_finite(),
+            "Laplace scale must be positive",
+        )?;
+        Ok(Laplace { location, scale })
+    }
+}
+
+impl ContinuousDistribution for Laplace {
+    fn pdf(&self, x: f64) -> f64 {
+        (-(x - self.location).abs() / self.scale).exp() / (2.0 * self.scale)
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        -(x - self.location).abs() / self.scale - (2.0 * self.scale).ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+        if z < 0.0 {
+            0.5 * z.exp()
+        } else {
+            1.0 - 0.5 * (-z).exp()
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+        if z > 0.0 {
+            0.5 * (-z).exp()
+        } else {
+            1.0 - 0.5 * z.exp()
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        if p < 0.5 {
+            self.location + self.scale * (2.0 * p).ln()
+        } else {
+            self.location - self.scale * (2.0 - 2.0 * p).ln()
+        }
+    }
+
+    fn mean(&self) -> f64 {
+        self.location
+    }
+
+    fn variance(&self) -> f64 {
+        2.0 * self.scale * self.scale
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        self.quantile(rng.next_f64_open())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Logistic {
+    location: f64,
+    scale: f64,
+}
+
+impl Logistic {
+    pub fn new(location: f64, scale: f64) -> Result<Logistic, StatustError> {
+        check(location.is_finite(), "logistic location must be finite")?;
+        check(
+            scale > 0.0 && scale.is_finite(),
+            "logistic scale must be positive",
+        )?;
+        Ok(Logistic { location, scale })
+    }
+}
+
+impl ContinuousDistribution for Logistic {
+    fn pdf(&self, x: f64) -> f64 {
+        let e = (-((x - self.location) / self.scale).abs()).exp();
+        e / (self.scale * (1.0 + e) * (1.0 + e))
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        1.0 / (1.0 + (-(x - self.location) / self.scale).exp())
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        1.0 / (1.0 + ((x - self.location) / self.scale).exp())
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        self.location + self.scale * (p / (1.0 - p)).ln()
+    }
+
+    fn mean(&self) -> f64 {
+        self.location
+    }
+
+    fn variance(&self) -> f64 {
+        self.scale * self.scale * PI * PI / 3.0
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        self.quantile(rng.next_f64_open())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_rel_close;
+
+    /// Checks `(x, pdf, cdf)` triples and `(p, quantile)` pairs.
+    fn check_table(d: &dyn ContinuousDistribution, values: &[(f64, f64, f64)], q: &[(f64, f64)]) {
+        for &(x, pdf, cdf) in values {
+            assert_rel_close(d.pdf(x), pdf, 1e-12);
+            assert_rel_close(d.log_pdf(x), pdf.ln(), 1e-12);
+            assert_rel_close(d.cdf(x), cdf, 1e-12);
+            assert_rel_close(d.sf(x), 1.0 - cdf, 1e-10);
+        }
+        for &(p, x) in q {
+            assert_rel_close(d.quantile(p), x, 1e-10);
+        }
+    }
+
+    /// Compares the sample mean and variance against the theoretical ones.
+    fn check_moments(d: &dyn ContinuousDistribution, seed: u64) {
+        let mut rng = Xoshiro256::seed_from_u64(seed);
+        let x = d.sample_n(200_000, &mut rng);
+        let (m, v) = (stats::mean(&x), stats::variance(&x));
+        let se = (d.variance() / x.len() as f64).sqrt();
+        assert!(
+            (m - d.mean()).abs() < 5.0 * se,
+            "sample mean {} vs {}",
+            m,
+            d.mean()
+        );
+        assert!(
+            (v / d.variance() - 1.0).abs() < 0.03,
+            "sample variance {} vs {}",
+            v,
+            d.variance()
+        );
+    }
+
+    #[test]
+    fn test_normal() {
+        let d = Normal::new(1.0, 2.0).unwrap();
+        check_table(
+            &d,
+            &[
+                (0.5, 0.1933340584014246, 0.40129367431707628),
+                (3.5, 0.091324542694510952, 0.89435022633314474),
+                (-4.0, 0.0087641502467842687, 0.0062096653257761352),
+            ],
+            &[
+                (0.025, -2.9199279690801084),
+                (0.975, 4.9199279690801077),
+                (1e-10, -11.722681804808112),
+            ],
+        );
+        assert_rel_close(Normal::standard().quantile(0.975), 1.959963984540054, 1e-14);
+        assert!(Normal::new(0.0, 0.0).is_err());
+        check_moments(&d, 1);
+    }
+
+    #[test]
+    fn test_student_t() {
+        check_table(
+            &StudentT::new(10.0).unwrap(),
+            &[
+                (0.5, 0.33969513635207777, 0.68605319712851353),
+                (2.228, 0.042394624706439937, 0.97499411409144432),
+                (-3.0, 0.011400549464542524, 0.0066718275112847886),
+            ],
+            &[(0.975, 2.2281388519862742), (0.005, -3.1692726726169512)],
+        );
+        check_table(
+            &StudentT::new(1.5).unwrap(),
+            &[
+                (0.3, 0.31679942946113957, 0.59976390159820757),
+                (-7.0, 0.0042016180829163746, 0.0200339300616452),
+            ],
+            &[(0.9, 2.1963984175655378)],
+        );
+        check_moments(&StudentT::new(12.0).unwrap(), 2);
+        assert!(StudentT::new(f64::INFINITY).is_err());
+        assert!(StudentT::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_chi_squared() {
+        let d = ChiSquared::new(3.0).unwrap();
+        check_table(
+            &d,
+            &[
+                (0.5, 0.2196956447338612, 0.081108588345324141),
+                (7.81, 0.022455132960121388, 0.94989394364999406),
+                (20.0, 8.0999109560891174e-5, 0.99983025756444717),
+            ],
+            &[
+                (0.95, 7.814727903251178),
+                (0.01, 0.11483180189911704),
+                (0.999999, 30.664849706154268),
+            ],
+        );
+        check_moments(&d, 3);
+    }
+
+    #[test]
+    fn test_gamma() {
+        let d = Gamma::new(2.5, 1.5).unwrap();
+        check_table(
+            &d,
+            &[
+                (0.3, 0.2171907440521872, 0.029778361590214442),
+                (1.7, 0.35876857953338254, 0.59620154289579183),
+                (6.0, 0.0037598324025621921, 0.99705359541211971),
+            ],
+            &[(0.5, 1.4504867303651758), (0.99, 5.0287574897963293)],
+        );
+        check_moments(&d, 4);
+        let d = Gamma::new(0.3, 2.0).unwrap();
+        check_table(
+            &d,
+            &[
+                (0.01, 10.132674382040825, 0.34299751585698793),
+                (1.0, 0.055695616103440982, 0.97797401917285299),
+            ],
+            &[(0.1, 0.00016186231091171637), (0.9, 0.44240538668012202)],
+        );
+        check_moments(&d, 5);
+        assert_rel_close(
+            ChiSquared::new(2.0).unwrap().log_pdf(0.0),
+            0.5f64.ln(),
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn test_beta() {
+        let d = Beta::new(2.0, 5.0).unwrap();
+        check_table(
+            &d,
+            &[
+                (0.1, 1.9683, 0.114265),
+                (0.3, 2.1609, 0.579825),
+                (0.8, 0.0384, 0.9984),
+            ],
+            &[(0.5, 0.26444998329565996), (0.95, 0.58180340925202583)],
+        );
+        check_moments(&d, 6);
+        check_table(
+            &Beta::new(0.5, 0.5).unwrap(),
+            &[
+                (0.01, 3.1991347258556543, 0.063768560858519849),
+                (0.7, 0.69460911804285658, 0.63098988043445459),
+            ],
+            &[(0.2, 0.095491502812526298), (0.99, 0.99975328018286578)],
+        );
+        let d = Beta::new(1.0, 3.0).unwrap();
+        assert_rel_close(d.pdf(0.0), 3.0, 1e-14);
+        assert_rel_close(d.log_pdf(0.0), 3f64.ln(), 1e-14);
+        assert_eq!(d.pdf(1.0), 0.0);
+        assert_rel_close(Beta::new(3.0, 1.0).unwrap().pdf(1.0), 3.0, 1e-14);
+        assert_rel_close(Beta::new(1.0, 1.0).unwrap().pdf(0.0), 1.0, 1e-14);
+        assert_eq!(Beta::new(2.0, 1.0).unwrap().pdf(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_fisher_f() {
+        let d = FisherF::new(5.0, 10.0).unwrap();
+        check_table(
+            &d,
+            &[
+                (0.5, 0.68760700277062333, 0.22997511934989837),
+                (3.33, 0.040424776931905673, 0.9501687242027787),
+                (8.0, 0.0013429824272863737, 0.99714320961434622),
+            ],
+            &[(0.95, 3.3258345304130109), (0.05, 0.21119042878234494)],
+        );
+        check_moments(&FisherF::new(5.0, 20.0).unwrap(), 7);
+        assert_rel_close(FisherF::new(2.0, 5.0).unwrap().pdf(0.0), 1.0, 1e-14);
+        assert!(FisherF::new(2.0, 5.0).unwrap().log_pdf(0.0).abs() < 1e-14);
+        assert_eq!(FisherF::new(4.0, 5.0).unwrap().pdf(0.0), 0.0);
+        assert_eq!(FisherF::new(1.0, 5.0).unwrap().pdf(0.0), f64::INFINITY);
+        assert!(FisherF::new(f64::INFINITY, 5.0).is_err());
+        assert!(FisherF::new(5.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_closed_form() {
+        let d = Exponential::new(2.0).unwrap();
+        assert_rel_close(d.cdf(1.0), 1.0 - (-2.0f64).exp(), 1e-15);
+        assert_rel_close(d.quantile(0.5), 2f64.ln() / 2.0, 1e-15);
+        check_moments(&d, 8);
+
+        let d = Uniform::new(-1.0, 3.0).unwrap();
+        assert_eq!(d.cdf(0.0), 0.25);
+        assert_eq!(d.quantile(0.75), 2.0);
+        check_moments(&d, 9);
+
+        let d = LogNormal::new(0.5, 0.4).unwrap();
+        assert_rel_close(d.cdf(0.5f64.exp()), 0.5, 1e-15);
+        assert_rel_close(
+            d.quantile(0.975),
+            (0.5 + 0.4 * 1.959963984540054f64).exp(),
+            1e-13,
+        );
+        check_moments(&d, 10);
+
+        let d = Cauchy::new(1.0, 2.0).unwrap();
+        assert_rel_close(d.cdf(3.0), 0.75, 1e-15);
+        assert_rel_close(d.quantile(0.75), 3.0, 1e-14);
+        assert!(d.mean().is_nan());
+
+        let d = Weibull::new(1.5, 2.0).unwrap();
+        assert_rel_close(d.cdf(2.0), 1.0 - (-1.0f64).exp(), 1e-15);
+        assert_rel_close(d.mean(), 2.0 * 0.9027452929509336, 1e-12);
+        check_moments(&d, 11);
+
+        let d = Laplace::new(1.0, 0.5).unwrap();
+        assert_rel_close(d.cdf(1.5), 1.0 - 0.5 * (-1.0f64).exp(), 1e-15);
+        assert_rel_close(d.quantile(d.cdf(0.2)), 0.2, 1e-14);
+        check_moments(&d, 12);
+
+        let d = Logistic::new(2.0, 1.5).unwrap();
+        assert_rel_close(d.cdf(2.0 + 1.5 * 3f64.ln()), 0.75, 1e-15);
+        assert_rel_close(d.quantile(0.75), 2.0 + 1.5 * 3f64.ln(), 1e-14);
+        check_moments(&d, 13);
+    }
+
+    #[test]
+    fn test_quantile_roundtrip() {
+        let dists: Vec<Box<dyn ContinuousDistribution>> = vec![
+            Box::new(Gamma::new(7.0, 0.5).unwrap()),
+            Box::new(Beta::new(3.0, 0.7).unwrap()),
+            Box::new(StudentT::new(3.0).unwrap()),
+            Box::new(FisherF::new(2.0, 3.0).unwrap()),
+            Box::new(LogNormal::new(0.0, 1.0).unwrap()),
+        ];
+        for d in dists {
+            for p in [1e-6, 0.01, 0.3, 0.5, 0.8, 0.999] {
+                assert_rel_close(d.cdf(d.quantile(p)), p, 1e-10);
+            }
+            assert!(d.quantile(1.5).is_nan());
+        }
+    }
+}
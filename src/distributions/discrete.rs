@@ -0,0 +1,761 @@
+use super::{check, DiscreteDistribution};
+use crate::special::{beta_inc, gamma_p, gamma_q, ln_gamma, ndtri};
+use crate::*;
+
+fn ln_choose(n: f64, k: f64) -> f64 {
+    ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0)
+}
+
+/// Finds the smallest `k` in `[lower, upper]` with `cdf(k) >= p` by walking
+/// from `guess`. Upper-tail probabilities are compared through `sf`.
+fn search_quantile<D: DiscreteDistribution + ?Sized>(
+    d: &D,
+    p: f64,
+    lower: i64,
+    upper: i64,
+    guess: f64,
+) -> Option<i64> {
+    if p.is_nan() || !(0.0..=1.0).contains(&p) {
+        return None;
+    }
+    if p == 0.0 {
+        return Some(lower);
+    }
+    if p == 1.0 {
+        return if upper == i64::MAX { None } else { Some(upper) };
+    }
+    let tol = 64.0 * f64::EPSILON;
+    let reached = |k: i64| {
+        if p > 0.5 {
+            d.sf(k) <= (1.0 - p) * (1.0 + tol)
+        } else {
+            d.cdf(k) >= p * (1.0 - tol)
+        }
+    };
+    let mut k = if guess.is_finite() {
+        (guess.round() as i64).clamp(lower, upper)
+    } else {
+        lower
+    };
+    if reached(k) {
+        while k > lower && reached(k - 1) {
+            k -= 1;
+        }
+    } else {
+        while k < upper && !reached(k) {
+            k += 1;
+        }
+    }
+    Some(k)
+}
+
+fn normal_guess<D: DiscreteDistribution + ?Sized>(d: &D, p: f64) -> f64 {
+    d.mean() + d.std_dev() * ndtri(p)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bernoulli {
+    p: f64,
+}
+
+impl Bernoulli {
+    pub fn new(p: f64) -> Result<Bernoulli, StatustError> {
+        check(
+            (0.0..=1.0).contains(&p),
+            "Bernoulli probability must be in [0, 1]",
+        )?;
+        Ok(Bernoulli { p })
+    }
+}
+
+impl DiscreteDistribution for Bernoulli {
+    fn pmf(&self, k: i64) -> f64 {
+        match k {
+            0 => 1.0 - self.p,
+            1 => self.p,
+            _ => 0.0,
+        }
+    }
+
+    fn cdf(&self, k: i64) -> f64 {
+        match k {
+            k if k < 0 => 0.0,
+            0 => 1.0 - self.p,
+            _ => 1.0,
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Option<i64> {
+        search_quantile(self, p, 0, 1, 0.0)
+    }
+
+    fn mean(&self) -> f64 {
+        self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.p * (1.0 - self.p)
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> i64 {
+        rng.gen_bool(self.p) as i64
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Binomial {
+    n: u64,
+    p: f64,
+}
+
+impl Binomial {
+    pub fn new(n: u64, p: f64) -> Result<Binomial, StatustError> {
+        check(
+            (0.0..=1.0).contains(&p),
+            "binomial probability must be in [0, 1]",
+        )?;
+        Ok(Binomial { n, p })
+    }
+}
+
+impl DiscreteDistribution for Binomial {
+    fn pmf(&self, k: i64) -> f64 {
+        self.log_pmf(k).exp()
+    }
+
+    fn log_pmf(&self, k: i64) -> f64 {
+        let n = self.n as i64;
+        if k < 0 || k > n {
+            return f64::NEG_INFINITY;
+        }
+        if self.p == 0.0 || self.p == 1.0 {
+            let mode = if self.p == 0.0 { 0 } else { n };
+            return if k == mode { 0.0 } else { f64::NEG_INFINITY };
+        }
+        let (n, k) = (n as f64, k as f64);
+        ln_choose(n, k) + k * self.p.ln() + (n - k) * (-self.p).ln_1p()
+    }
+
+    fn cdf(&self, k: i64) -> f64 {
+        if k < 0 {
+            0.0
+        } else if k >= self.n as i64 {
+            1.0
+        } else {
+            beta_inc((self.n as i64 - k) as f64, (k + 1) as f64, 1.0 - self.p)
+        }
+    }
+
+    fn sf(&self, k: i64) -> f64 {
+        if k < 0 {
+            1.0
+        } else if k >= self.n as i64 {
+            0.0
+        } else {
+            beta_inc((k + 1) as f64, (self.n as i64 - k) as f64, self.p)
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Option<i64> {
+        search_quantile(self, p, 0, self.n as i64, normal_guess(self, p))
+    }
+
+    fn mean(&self) -> f64 {
+        self.n as f64 * self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.n as f64 * self.p * (1.0 - self.p)
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> i64 {
+        if self.n < 32 {
+            (0..self.n).filter(|_| rng.gen_bool(self.p)).count() as i64
+        } else {
+            self.quantile(rng.next_f64()).unwrap()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Poisson {
+    lambda: f64,
+}
+
+impl Poisson {
+    pub fn new(lambda: f64) -> Result<Poisson, StatustError> {
+        check(
+            lambda > 0.0 && lambda.is_finite(),
+            "Poisson rate must be positive",
+        )?;
+        Ok(Poisson { lambda })
+    }
+}
+
+impl DiscreteDistribution for Poisson {
+    fn pmf(&self, k: i64) -> f64 {
+        self.log_pmf(k).exp()
+    }
+
+    fn log_pmf(&self, k: i64) -> f64 {
+        if k < 0 {
+            return f64::NEG_INFINITY;
+        }
+        let k = k as f64;
+        k * self.lambda.ln() - self.lambda - ln_gamma(k + 1.0)
+    }
+
+    fn cdf(&self, k: i64) -> f64 {
+        if k < 0 {
+            0.0
+        } else {
+            gamma_q((k + 1) as f64, self.lambda)
+        }
+    }
+
+    fn sf(&self, k: i64) -> f64 {
+        if k < 0 {
+            1.0
+        } else {
+            gamma_p((k + 1) as f64, self.lambda)
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Option<i64> {
+        search_quantile(self, p, 0, i64::MAX, normal_guess(self, p))
+    }
+
+    fn mean(&self) -> f64 {
+        self.lambda
+    }
+
+    fn variance(&self) -> f64 {
+        self.lambda
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> i64 {
+        if self.lambda < 30.0 {
+            // Knuth's multiplication method.
+            let limit = (-self.lambda).exp();
+            let mut k = 0;
+            let mut prod = rng.next_f64();
+            while prod > limit {
+                k += 1;
+                prod *= rng.next_f64();
+            }
+            k
+        } else {
+            self.quantile(rng.next_f64()).unwrap()
+        }
+    }
+}
+
+/// Number of failures before the first success.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Geometric {
+    p: f64,
+}
+
+impl Geometric {
+    pub fn new(p: f64) -> Result<Geometric, StatustError> {
+        check(
+            p > 0.0 && p <= 1.0,
+            "geometric probability must be in (0, 1]",
+        )?;
+        Ok(Geometric { p })
+    }
+}
+
+impl DiscreteDistribution for Geometric {
+    fn pmf(&self, k: i64) -> f64 {
+        self.log_pmf(k).exp()
+    }
+
+    fn log_pmf(&self, k: i64) -> f64 {
+        if k < 0 {
+            return f64::NEG_INFINITY;
+        }
+        if self.p == 1.0 {
+            return if k == 0 { 0.0 } else { f64::NEG_INFINITY };
+        }
+        self.p.ln() + k as f64 * (-self.p).ln_1p()
+    }
+
+    fn cdf(&self, k: i64) -> f64 {
+        if k < 0 {
+            0.0
+        } else {
+            -((k + 1) as f64 * (-self.p).ln_1p()).exp_m1()
+        }
+    }
+
+    fn sf(&self, k: i64) -> f64 {
+        if k < 0 {
+            1.0
+        } else {
+            ((k + 1) as f64 * (-self.p).ln_1p()).exp()
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Option<i64> {
+        if self.p == 1.0 && (0.0..=1.0).contains(&p) {
+            return Some(0);
+        }
+        let guess = ((-p).ln_1p() / (-self.p).ln_1p()).ceil() - 1.0;
+        search_quantile(self, p, 0, i64::MAX, guess)
+    }
+
+    fn mean(&self) -> f64 {
+        (1.0 - self.p) / self.p
+    }
+
+    fn variance(&self) -> f64 {
+        (1.0 - self.p) / (self.p * self.p)
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> i64 {
+        if self.p == 1.0 {
+            return 0;
+        }
+        (rng.next_f64_open().ln() / (-self.p).ln_1p()).floor() as i64
+    }
+}
+
+/// Number of failures before the `r`-th success; `r` may be fractional.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NegativeBinomial {
+    r: f64,
+    p: f64,
+}
+
+impl NegativeBinomial {
+    pub fn new(r: f64, p: f64) -> Result<NegativeBinomial, StatustError> {
+        check(
+            r > 0.0 && r.is_finite(),
+            "negative binomial r must be positive",
+        )?;
+        check(
+            p > 0.0 && p <= 1.0,
+            "negative binomial probability must be in (0, 1]",
+        )?;
+        Ok(NegativeBinomial { r, p })
+    }
+}
+
+impl DiscreteDistribution for NegativeBinomial {
+    fn pmf(&self, k: i64) -> f64 {
+        self.log_pmf(k).exp()
+    }
+
+    fn log_pmf(&self, k: i64) -> f64 {
+        if k < 0 {
+            return f64::NEG_INFINITY;
+        }
+        if self.p == 1.0 {
+            return if k == 0 { 0.0 } else { f64::NEG_INFINITY };
+        }
+        let k = k as f64;
+        ln_gamma(k + self.r) - ln_gamma(k + 1.0) - ln_gamma(self.r)
+            + self.r * self.p.ln()
+            + k * (-self.p).ln_1p()
+    }
+
+    fn cdf(&self, k: i64) -> f64 {
+        if k < 0 {
+            0.0
+        } else {
+            beta_inc(self.r, (k + 1) as f64, self.p)
+        }
+    }
+
+    fn sf(&self, k: i64) -> f64 {
+        if k < 0 {
+            1.0
+        } else {
+            beta_inc((k + 1) as f64, self.r, 1.0 - self.p)
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Option<i64> {
+        search_quantile(self, p, 0, i64::MAX, normal_guess(self, p))
+    }
+
+    fn mean(&self) -> f64 {
+        self.r * (1.0 - self.p) / self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.r * (1.0 - self.p) / (self.p * self.p)
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> i64 {
+        self.quantile(rng.next_f64()).unwrap()
+    }
+}
+
+/// Number of successes in `draws` draws without replacement from a population
+/// of `population` items, `successes` of which count as successes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hypergeometric {
+    population: u64,
+    successes: u64,
+    draws: u64,
+}
+
+impl Hypergeometric {
+    pub fn new(
+        population: u64,
+        successes: u64,
+        draws: u64,
+    ) -> Result<Hypergeometric, StatustError> {
+        check(
+            successes <= population && draws <= population,
+            "hypergeometric successes and draws cannot exceed the population",
+        )?;
+        Ok(Hypergeometric {
+            population,
+            successes,
+            draws,
+        })
+    }
+
+    fn support(&self) -> (i64, i64) {
+        let lower = (self.draws + self.successes).saturating_sub(self.population);
+        let upper = self.draws.min(self.successes);
+        (lower as i64, upper as i64)
+    }
+}
+
+impl DiscreteDistribution for Hypergeometric {
+    fn pmf(&self, k: i64) -> f64 {
+        self.log_pmf(k).exp()
+    }
+
+    fn log_pmf(&self, k: i64) -> f64 {
+        let (lower, upper) = self.support();
+        if k < lower || k > upper {
+            return f64::NEG_INFINITY;
+        }
+        let (n, s, d, k) = (
+            self.population as f64,
+            self.successes as f64,
+            self.draws as f64,
+            k as f64,
+        );
+        ln_choose(s, k) + ln_choose(n - s, d - k) - ln_choose(n, d)
+    }
+
+    fn cdf(&self, k: i64) -> f64 {
+        let (lower, upper) = self.support();
+        if k < lower {
+            0.0
+        } else if k >= upper {
+            1.0
+        } else if k - lower <= upper - k {
+            (lower..=k).map(|i| self.pmf(i)).sum::<f64>().min(1.0)
+        } else {
+            1.0 - self.sf(k)
+        }
+    }
+
+    fn sf(&self, k: i64) -> f64 {
+        let (lower, upper) = self.support();
+        if k < lower {
+            1.0
+        } else if k >= upper {
+            0.0
+        } else if upper - k < k - lower {
+            (k + 1..=upper).map(|i| self.pmf(i)).sum::<f64>().min(1.0)
+        } else {
+            1.0 - self.cdf(k)
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Option<i64> {
+        let (lower, upper) = self.support();
+        search_quantile(self, p, lower, upper, normal_guess(self, p))
+    }
+
+    fn mean(&self) -> f64 {
+        self.draws as f64 * self.successes as f64 / self.population as f64
+    }
+
+    fn variance(&self) -> f64 {
+        let (n, s, d) = (
+            self.population as f64,
+            self.successes as f64,
+            self.draws as f64,
+        );
+        if n <= 1.0 {
+            return 0.0;
+        }
+        d * s / n * (n - s) / n * (n - d) / (n - 1.0)
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> i64 {
+        self.quantile(rng.next_f64()).unwrap()
+    }
+}
+
+/// Distribution over the categories `0..probs.len()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Categorical {
+    probs: Vec<f64>,
+    cumulative: Vec<f64>,
+}
+
+fn normalize(weights: &[f64], name: &str) -> Result<Vec<f64>, StatustError> {
+    check(
+        !weights.is_empty() && weights.iter().all(|w| *w >= 0.0 && w.is_finite()),
+        &format!("{} weights must be non-negative and finite", name),
+    )?;
+    let total: f64 = weights.iter().sum();
+    check(
+        total > 0.0,
+        &format!("{} weights must not all be zero", name),
+    )?;
+    Ok(weights.iter().map(|w| w / total).collect())
+}
+
+impl Categorical {
+    /// Weights are normalized to sum to one.
+    pub fn new(weights: &[f64]) -> Result<Categorical, StatustError> {
+        let probs = normalize(weights, "categorical")?;
+        let mut cumulative = Vec::with_capacity(probs.len());
+        let mut total = 0.0;
+        for p in &probs {
+            total += p;
+            cumulative.push(total);
+        }
+        Ok(Categorical { probs, cumulative })
+    }
+
+    pub fn probs(&self) -> &[f64] {
+        &self.probs
+    }
+}
+
+impl DiscreteDistribution for Categorical {
+    fn pmf(&self, k: i64) -> f64 {
+        if k < 0 {
+            0.0
+        } else {
+            self.probs.get(k as usize).copied().unwrap_or(0.0)
+        }
+    }
+
+    fn cdf(&self, k: i64) -> f64 {
+        if k < 0 {
+            0.0
+        } else if k as usize >= self.probs.len() - 1 {
+            1.0
+        } else {
+            self.cumulative[k as usize]
+        }
+    }
+
+    fn quantile(&self, p: f64) -> Option<i64> {
+        search_quantile(self, p, 0, self.probs.len() as i64 - 1, 0.0)
+    }
+
+    fn mean(&self) -> f64 {
+        self.probs
+            .iter()
+            .enumerate()
+            .map(|(k, p)| k as f64 * p)
+            .sum()
+    }
+
+    fn variance(&self) -> f64 {
+        let m = self.mean();
+        self.probs
+            .iter()
+            .enumerate()
+            .map(|(k, p)| (k as f64 - m).powi(2) * p)
+            .sum()
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> i64 {
+        let u = rng.next_f64();
+        let k = self.cumulative.partition_point(|c| *c <= u);
+        k.min(self.probs.len() - 1) as i64
+    }
+}
+
+/// Counts of each category in `trials` independent categorical draws.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Multinomial {
+    trials: u64,
+    probs: Vec<f64>,
+}
+
+impl Multinomial {
+    pub fn new(trials: u64, weights: &[f64]) -> Result<Multinomial, StatustError> {
+        Ok(Multinomial {
+            trials,
+            probs: normalize(weights, "multinomial")?,
+        })
+    }
+
+    pub fn pmf(&self, counts: &[u64]) -> f64 {
+        self.log_pmf(counts).exp()
+    }
+
+    pub fn log_pmf(&self, counts: &[u64]) -> f64 {
+        if counts.len() != self.probs.len() || counts.iter().sum::<u64>() != self.trials {
+            return f64::NEG_INFINITY;
+        }
+        let mut result = ln_gamma(self.trials as f64 + 1.0);
+        for (&c, &p) in counts.iter().zip(&self.probs) {
+            if c > 0 {
+                if p == 0.0 {
+                    return f64::NEG_INFINITY;
+                }
+                result += c as f64 * p.ln() - ln_gamma(c as f64 + 1.0);
+            }
+        }
+        result
+    }
+
+    pub fn mean(&self) -> Vec<f64> {
+        self.probs.iter().map(|p| self.trials as f64 * p).collect()
+    }
+
+    /// Draws category counts by conditional binomial sampling.
+    pub fn sample(&self, rng: &mut dyn Rng) -> Vec<u64> {
+        let mut remaining = self.trials;
+        let mut mass = 1.0;
+        let mut counts = Vec::with_capacity(self.probs.len());
+        for (i, &p) in self.probs.iter().enumerate() {
+            let c = if i == self.probs.len() - 1 || remaining == 0 {
+                remaining
+            } else {
+                let q = (p / mass).clamp(0.0, 1.0);
+                Binomial { n: remaining, p: q }.sample(rng) as u64
+            };
+            counts.push(c);
+            remaining -= c;
+            mass -= p;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_rel_close;
+
+    /// Checks that quantile inverts the cdf and that sampling matches the
+    /// theoretical mean.
+    fn check_consistency(d: &dyn DiscreteDistribution, seed: u64) {
+        for p in [0.001, 0.1, 0.5, 0.77, 0.999] {
+            let k = d.quantile(p).unwrap();
+            assert!(d.cdf(k) >= p - 1e-12);
+            assert!(d.cdf(k - 1) < p);
+        }
+        let mut rng = Xoshiro256::seed_from_u64(seed);
+        let x: Vec<f64> = d
+            .sample_n(100_000, &mut rng)
+            .into_iter()
+            .map(|k| k as f64)
+            .collect();
+        let se = (d.variance() / x.len() as f64).sqrt();
+        assert!((stats::mean(&x) - d.mean()).abs() < 5.0 * se);
+        assert!((stats::variance(&x) / d.variance() - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_binomial() {
+        let d = Binomial::new(10, 0.3).unwrap();
+        assert_rel_close(d.pmf(3), 0.266827932, 1e-12);
+        assert_rel_close(d.cdf(3), 0.6496107184, 1e-12);
+        assert_rel_close(d.sf(7), 0.0015903864, 1e-11);
+        assert_eq!(d.pmf(11), 0.0);
+        check_consistency(&d, 1);
+
+        let d = Binomial::new(200, 0.01).unwrap();
+        assert_rel_close(d.cdf(5), 0.9839770930908225, 1e-12);
+        assert_rel_close(d.pmf(0), 0.13397967485796194, 1e-12);
+        check_consistency(&d, 2);
+        check_consistency(&Binomial::new(1000, 0.4).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_poisson() {
+        let d = Poisson::new(3.0).unwrap();
+        assert_rel_close(d.pmf(2), 0.22404180765538774, 1e-13);
+        assert_rel_close(d.cdf(2), 0.42319008112684352, 1e-13);
+        assert_rel_close(d.cdf(10), 0.99970766304935266, 1e-13);
+        assert_eq!(d.quantile(1.0), None);
+        check_consistency(&d, 4);
+
+        let d = Poisson::new(100.0).unwrap();
+        assert_rel_close(d.cdf(90), 0.1713851193217614, 1e-12);
+        check_consistency(&d, 5);
+    }
+
+    #[test]
+    fn test_bernoulli_geometric() {
+        let d = Bernoulli::new(0.3).unwrap();
+        assert_eq!(d.pmf(0), 0.7);
+        assert_eq!(d.quantile(0.7), Some(0));
+        assert_eq!(d.quantile(0.71), Some(1));
+        check_consistency(&d, 6);
+
+        let d = Geometric::new(0.2).unwrap();
+        assert_rel_close(d.pmf(3), 0.2 * 0.8f64.powi(3), 1e-14);
+        assert_rel_close(d.cdf(3), 1.0 - 0.8f64.powi(4), 1e-14);
+        check_consistency(&d, 7);
+        assert!(Geometric::new(0.0).is_err());
+    }
+
+    #[test]
+    fn test_negative_binomial() {
+        let d = NegativeBinomial::new(2.5, 0.4).unwrap();
+        assert_rel_close(d.pmf(3), 0.14344091466523769, 1e-12);
+        assert_rel_close(d.cdf(3), 0.5558019215511944, 1e-12);
+        check_consistency(&d, 8);
+    }
+
+    #[test]
+    fn test_hypergeometric() {
+        let d = Hypergeometric::new(50, 10, 12).unwrap();
+        assert_rel_close(d.pmf(3), 0.2702863253945546, 1e-12);
+        assert_rel_close(d.cdf(3), 0.8209435227940288, 1e-12);
+        assert_rel_close(d.mean(), 2.4, 1e-15);
+        check_consistency(&d, 9);
+        assert!(Hypergeometric::new(5, 6, 2).is_err());
+
+        let d = Hypergeometric::new(10, 8, 5).unwrap();
+        assert_eq!(d.pmf(2), 0.0);
+        assert_eq!(d.quantile(0.0), Some(3));
+    }
+
+    #[test]
+    fn test_categorical_multinomial() {
+        let d = Categorical::new(&[1.0, 3.0, 6.0]).unwrap();
+        assert_eq!(d.probs(), &[0.1, 0.3, 0.6]);
+        assert_rel_close(d.cdf(1), 0.4, 1e-15);
+        assert_eq!(d.quantile(0.5), Some(2));
+        check_consistency(&d, 10);
+        assert!(Categorical::new(&[0.0, 0.0]).is_err());
+
+        let m = Multinomial::new(5, &[0.2, 0.3, 0.5]).unwrap();
+        assert_rel_close(m.pmf(&[1, 2, 2]), 0.135, 1e-13);
+        assert_eq!(m.pmf(&[1, 2, 1]), 0.0);
+        let mut rng = Xoshiro256::seed_from_u64(11);
+        let mut totals = [0u64; 3];
+        for _ in 0..10_000 {
+            let c = m.sample(&mut rng);
+            assert_eq!(c.iter().sum::<u64>(), 5);
+            for (t, v) in totals.iter_mut().zip(c) {
+                *t += v;
+            }
+        }
+        for (t, e) in totals.iter().zip(m.mean()) {
+            assert!((*t as f64 / 10_000.0 - e).abs() < 0.03);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+mod continuous;
+mod discrete;
+mod multivariate;
+
+pub use continuous::*;
+pub use discrete::*;
+pub use multivariate::*;
+
+use crate::*;
+
+/// A univariate continuous probability distribution.
+///
+/// `sample` takes a `dyn Rng` so that distributions can be used as trait
+/// objects, e.g. when the distribution is chosen at runtime.
+pub trait ContinuousDistribution {
+    fn pdf(&self, x: f64) -> f64;
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64;
+
+    /// Survival function, `1 - cdf(x)`.
+    fn sf(&self, x: f64) -> f64 {
+        1.0 - self.cdf(x)
+    }
+
+    /// Inverse of the cdf. Returns NaN for `p` outside `[0, 1]`.
+    fn quantile(&self, p: f64) -> f64;
+
+    fn mean(&self) -> f64;
+
+    fn variance(&self) -> f64;
+
+    fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> f64;
+
+    fn sample_n(&self, n: usize, rng: &mut dyn Rng) -> Vec<f64> {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+}
+
+/// A univariate probability distribution over the integers.
+pub trait DiscreteDistribution {
+    fn pmf(&self, k: i64) -> f64;
+
+    fn log_pmf(&self, k: i64) -> f64 {
+        self.pmf(k).ln()
+    }
+
+    /// `P(X <= k)`.
+    fn cdf(&self, k: i64) -> f64;
+
+    /// `P(X > k)`.
+    fn sf(&self, k: i64) -> f64 {
+        1.0 - self.cdf(k)
+    }
+
+    /// Smallest `k` with `cdf(k) >= p`. Returns `None` for `p` outside
+    /// `[0, 1]`, or for `p = 1` when the support is unbounded.
+    fn quantile(&self, p: f64) -> Option<i64>;
+
+    fn mean(&self) -> f64;
+
+    fn variance(&self) -> f64;
+
+    fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    fn sample(&self, rng: &mut dyn Rng) -> i64;
+
+    fn sample_n(&self, n: usize, rng: &mut dyn Rng) -> Vec<i64> {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+}
+
+fn check(cond: bool, msg: &str) -> Result<(), StatustError> {
+    if cond {
+        Ok(())
+    } else {
+        Err(StatustError::invalid(msg.to_string()))
+    }
+}
@@ -0,0 +1,345 @@
+use super::check;
+use super::continuous::{standard_gamma, standard_normal};
+use crate::linalg::Matrix;
+use crate::special::ln_gamma;
+use crate::*;
+use std::f64::consts::PI;
+
+/// Builds a DataFrame with one row per draw and one column per name.
+fn frame_from_draws(names: &[&str], draws: Vec<Vec<f64>>) -> Result<DataFrame, StatustError> {
+    let mut columns = vec![Vec::with_capacity(draws.len()); names.len()];
+    for draw in draws {
+        if draw.len() != names.len() {
+            return Err(StatustError::LengthMismatch {
+                expected: names.len(),
+                found: draw.len(),
+            });
+        }
+        for (col, v) in columns.iter_mut().zip(draw) {
+            col.push(DataType::from(v));
+        }
+    }
+    DataFrame::from_columns(names.iter().map(|n| n.to_string()).collect(), columns)
+}
+
+fn check_names(names: &[&str], dim: usize) -> Result<(), StatustError> {
+    if names.len() != dim {
+        return Err(StatustError::LengthMismatch {
+            expected: dim,
+            found: names.len(),
+        });
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultivariateNormal {
+    mean: Vec<f64>,
+    cov: Matrix,
+    chol: Matrix,
+}
+
+impl MultivariateNormal {
+    /// The covariance matrix must be symmetric positive definite.
+    pub fn new(mean: Vec<f64>, cov: Matrix) -> Result<MultivariateNormal, StatustError> {
+        check(!mean.is_empty(), "mean vector must not be empty")?;
+        if cov.nrows() != mean.len() || cov.ncols() != mean.len() {
+            return Err(StatustError::LengthMismatch {
+                expected: mean.len(),
+                found: cov.nrows(),
+            });
+        }
+        let chol = cov.cholesky()?;
+        Ok(MultivariateNormal { mean, cov, chol })
+    }
+
+    pub fn dim(&self) -> usize {
+        self.mean.len()
+    }
+
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    pub fn cov(&self) -> &Matrix {
+        &self.cov
+    }
+
+    /// Log-density at `x`. Returns NaN if `x` has the wrong dimension.
+    pub fn log_pdf(&self, x: &[f64]) -> f64 {
+        if x.len() != self.dim() {
+            return f64::NAN;
+        }
+        let diff: Vec<f64> = x.iter().zip(&self.mean).map(|(a, m)| a - m).collect();
+        let z = self.chol.solve_lower(&diff).unwrap();
+        let log_det: f64 = self.chol.diag().iter().map(|d| d.ln()).sum();
+        let q: f64 = z.iter().map(|v| v * v).sum();
+        -0.5 * (self.dim() as f64 * (2.0 * PI).ln() + q) - log_det
+    }
+
+    pub fn pdf(&self, x: &[f64]) -> f64 {
+        self.log_pdf(x).exp()
+    }
+
+    pub fn sample(&self, rng: &mut dyn Rng) -> Vec<f64> {
+        let z: Vec<f64> = (0..self.dim()).map(|_| standard_normal(rng)).collect();
+        let lz = self.chol.mul_vec(&z).unwrap();
+        lz.iter().zip(&self.mean).map(|(a, m)| a + m).collect()
+    }
+
+    /// Draws `n` vectors into a DataFrame with one column per name.
+    pub fn sample_frame(
+        &self,
+        n: usize,
+        names: &[&str],
+        rng: &mut dyn Rng,
+    ) -> Result<DataFrame, StatustError> {
+        check_names(names, self.dim())?;
+        frame_from_draws(names, (0..n).map(|_| self.sample(rng)).collect())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dirichlet {
+    alpha: Vec<f64>,
+}
+
+impl Dirichlet {
+    pub fn new(alpha: Vec<f64>) -> Result<Dirichlet, StatustError> {
+        check(
+            alpha.len() >= 2,
+            "Dirichlet needs at least two concentration parameters",
+        )?;
+        check(
+            alpha.iter().all(|a| *a > 0.0 && a.is_finite()),
+            "Dirichlet concentration parameters must be positive",
+        )?;
+        Ok(Dirichlet { alpha })
+    }
+
+    pub fn dim(&self) -> usize {
+        self.alpha.len()
+    }
+
+    pub fn mean(&self) -> Vec<f64> {
+        let total: f64 = self.alpha.iter().sum();
+        self.alpha.iter().map(|a| a / total).collect()
+    }
+
+    /// Log-density at `x`; `-inf` outside the simplex.
+    pub fn log_pdf(&self, x: &[f64]) -> f64 {
+        if x.len() != self.dim() {
+            return f64::NAN;
+        }
+        let sum: f64 = x.iter().sum();
+        if x.iter().any(|v| *v <= 0.0) || (sum - 1.0).abs() > 1e-10 {
+            return f64::NEG_INFINITY;
+        }
+        let total: f64 = self.alpha.iter().sum();
+        let norm = ln_gamma(total) - self.alpha.iter().map(|a| ln_gamma(*a)).sum::<f64>();
+        norm + self
+            .alpha
+            .iter()
+            .zip(x)
+            .map(|(a, v)| (a - 1.0) * v.ln())
+            .sum::<f64>()
+    }
+
+    pub fn pdf(&self, x: &[f64]) -> f64 {
+        self.log_pdf(x).exp()
+    }
+
+    pub fn sample(&self, rng: &mut dyn Rng) -> Vec<f64> {
+        let g: Vec<f64> = self.alpha.iter().map(|a| standard_gamma(*a, rng)).collect();
+        let total: f64 = g.iter().sum();
+        g.iter().map(|v| v / total).collect()
+    }
+
+    /// Draws `n` vectors into a DataFrame with one column per name.
+    pub fn sample_frame(
+        &self,
+        n: usize,
+        names: &[&str],
+        rng: &mut dyn Rng,
+    ) -> Result<DataFrame, StatustError> {
+        check_names(names, self.dim())?;
+        frame_from_draws(names, (0..n).map(|_| self.sample(rng)).collect())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wishart {
+    df: f64,
+    scale: Matrix,
+    chol: Matrix,
+}
+
+impl Wishart {
+    /// `df` must exceed `p - 1` for a `p x p` positive definite scale matrix.
+    pub fn new(df: f64, scale: Matrix) -> Result<Wishart, StatustError> {
+        let chol = scale.cholesky()?;
+        check(
+            df > scale.nrows() as f64 - 1.0,
+            "Wishart degrees of freedom must exceed the dimension minus one",
+        )?;
+        Ok(Wishart { df, scale, chol })
+    }
+
+    pub fn dim(&self) -> usize {
+        self.scale.nrows()
+    }
+
+    pub fn mean(&self) -> Matrix {
+        let p = self.dim();
+        let data = self.scale.as_slice().iter().map(|v| v * self.df).collect();
+        Matrix::new(p, p, data).unwrap()
+    }
+
+    /// Draws a matrix using the Bartlett decomposition.
+    pub fn sample(&self, rng: &mut dyn Rng) -> Matrix {
+        let p = self.dim();
+        let mut a = Matrix::zeros(p, p);
+        for i in 0..p {
+            let chi2 = 2.0 * standard_gamma(0.5 * (self.df - i as f64), rng);
+            a[(i, i)] = chi2.sqrt();
+            for j in 0..i {
+                a[(i, j)] = standard_normal(rng);
+            }
+        }
+        let la = self.chol.matmul(&a).unwrap();
+        la.matmul(&la.transpose()).unwrap()
+    }
+
+    /// Draws `n` matrices into a DataFrame with one row per draw. Since the
+    /// draws are symmetric only the upper triangle is kept, in columns named
+    /// `"{a}_{b}"` after the variable names.
+    pub fn sample_frame(
+        &self,
+        n: usize,
+        names: &[&str],
+        rng: &mut dyn Rng,
+    ) -> Result<DataFrame, StatustError> {
+        let p = self.dim();
+        check_names(names, p)?;
+        let mut header = Vec::new();
+        for i in 0..p {
+            for j in i..p {
+                header.push(format!("{}_{}", names[i], names[j]));
+            }
+        }
+        let draws = (0..n)
+            .map(|_| {
+                let w = self.sample(rng);
+                (0..p)
+                    .flat_map(|i| (i..p).map(move |j| (i, j)))
+                    .map(|(i, j)| w[(i, j)])
+                    .collect()
+            })
+            .collect();
+        let header: Vec<&str> = header.iter().map(|s| s.as_str()).collect();
+        frame_from_draws(&header, draws)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod tests {
+    use super::*;
+
+    fn cov3() -> Matrix {
+        Matrix::from_rows(&[
+            vec![2.0, 0.5, 0.3],
+            vec![0.5, 1.0, -0.2],
+            vec![0.3, -0.2, 1.5],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_multivariate_normal() {
+        let mvn = MultivariateNormal::new(vec![1.0, -1.0, 0.5], cov3()).unwrap();
+        let lp = mvn.log_pdf(&[0.3, 0.2, 1.1]);
+        assert!((lp - -4.868789052206209182).abs() < 1e-13);
+        assert!(mvn.log_pdf(&[0.0]).is_nan());
+
+        let mut rng = Xoshiro256::seed_from_u64(1);
+        let df = mvn
+            .sample_frame(50_000, &["x", "y", "z"], &mut rng)
+            .unwrap();
+        assert_eq!(df.header(), &["x", "y", "z"]);
+        assert_eq!(df.nrows(), 50_000);
+        let cols: Vec<Vec<f64>> = ["x", "y", "z"]
+            .iter()
+            .map(|c| {
+                df.get_col_by_name(c)
+                    .unwrap()
+                    .iter()
+                    .map(|v| v.as_f64().unwrap())
+                    .collect()
+            })
+            .collect();
+        for i in 0..3 {
+            assert!((stats::mean(&cols[i]) - mvn.mean()[i]).abs() < 0.03);
+            for j in 0..3 {
+                let (mi, mj) = (stats::mean(&cols[i]), stats::mean(&cols[j]));
+                let c = cols[i]
+                    .iter()
+                    .zip(&cols[j])
+                    .map(|(a, b)| (a - mi) * (b - mj))
+                    .sum::<f64>()
+                    / (cols[i].len() - 1) as f64;
+                assert!((c - cov3()[(i, j)]).abs() < 0.05);
+            }
+        }
+
+        assert!(mvn.sample_frame(1, &["x"], &mut rng).is_err());
+        assert!(MultivariateNormal::new(vec![0.0, 0.0], cov3()).is_err());
+    }
+
+    #[test]
+    fn test_dirichlet() {
+        let d = Dirichlet::new(vec![2.0, 3.0, 4.5]).unwrap();
+        assert!((d.log_pdf(&[0.2, 0.3, 0.5]) - 2.0990510163490999913).abs() < 1e-13);
+        assert_eq!(d.log_pdf(&[0.5, 0.6, -0.1]), f64::NEG_INFINITY);
+
+        let mut rng = Xoshiro256::seed_from_u64(2);
+        let df = d.sample_frame(20_000, &["a", "b", "c"], &mut rng).unwrap();
+        for (name, m) in ["a", "b", "c"].iter().zip(d.mean()) {
+            let x: Vec<f64> = df
+                .get_col_by_name(name)
+                .unwrap()
+                .iter()
+                .map(|v| v.as_f64().unwrap())
+                .collect();
+            assert!((stats::mean(&x) - m).abs() < 0.005);
+        }
+        assert!(Dirichlet::new(vec![1.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn test_wishart() {
+        let w = Wishart::new(7.0, cov3()).unwrap();
+        let mut rng = Xoshiro256::seed_from_u64(3);
+        let n = 20_000;
+        let mut total = Matrix::zeros(3, 3);
+        for _ in 0..n {
+            let s = w.sample(&mut rng);
+            assert_eq!(s, s.transpose());
+            for i in 0..3 {
+                for j in 0..3 {
+                    total[(i, j)] += s[(i, j)] / n as f64;
+                }
+            }
+        }
+        let mean = w.mean();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((total[(i, j)] - mean[(i, j)]).abs() < 0.15);
+            }
+        }
+
+        let df = w.sample_frame(5, &["x", "y", "z"], &mut rng).unwrap();
+        assert_eq!(df.header(), &["x_x", "x_y", "x_z", "y_y", "y_z", "z_z"]);
+        assert!(Wishart::new(1.5, cov3()).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::*;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Keep {
+    /// Keep the first occurrence, mark the later ones as duplicates.
+    First,
+    /// Keep the last occurrence, mark the earlier ones as duplicates.
+    Last,
+    /// Mark every occurrence of a repeated row as a duplicate.
+    None,
+}
+
+impl DataFrame {
+    /// Flags rows whose values in the `subset` columns (all columns if `None`)
+    /// repeat another row. Nulls compare equal to each other.
+    pub fn duplicated(
+        &self,
+        subset: Option<&[&str]>,
+        keep: Keep,
+    ) -> Result<Vec<bool>, StatustError> {
+        let indices = self.subset_indices(subset)?;
+        let keys: Vec<Vec<Option<&DataType>>> = self
+            .data
+            .iter()
+            .map(|row| {
+                indices
+                    .iter()
+                    .map(|&i| Some(&row[i]).filter(|v| !v.is_null()))
+                    .collect()
+            })
+            .collect();
+
+        let mut counts: HashMap<&[Option<&DataType>], usize> = HashMap::new();
+        for key in &keys {
+            *counts.entry(key).or_insert(0) += 1;
+        }
+
+        let mut seen: HashSet<&[Option<&DataType>]> = HashSet::new();
+        let mut flags = vec![false; keys.len()];
+        match keep {
+            Keep::First => {
+                for (flag, key) in flags.iter_mut().zip(&keys) {
+                    *flag = !seen.insert(key);
+                }
+            }
+            Keep::Last => {
+                for (flag, key) in flags.iter_mut().zip(&keys).rev() {
+                    *flag = !seen.insert(key);
+                }
+            }
+            Keep::None => {
+                for (flag, key) in flags.iter_mut().zip(&keys) {
+                    *flag = counts[key.as_slice()] > 1;
+                }
+            }
+        }
+        Ok(flags)
+    }
+
+    pub fn drop_duplicates(
+        &self,
+        subset: Option<&[&str]>,
+        keep: Keep,
+    ) -> Result<DataFrame, StatustError> {
+        let flags = self.duplicated(subset, keep)?;
+        let data = self
+            .data
+            .iter()
+            .zip(flags)
+            .filter(|(_, dup)| !dup)
+            .map(|(row, _)| row.clone())
+            .collect();
+        Ok(DataFrame {
+            header: self.header.clone(),
+            data,
+        })
+    }
+
+    /// Number of distinct non-null values in each column.
+    pub fn n_unique(&self) -> HashMap<String, usize> {
+        self.header
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let values: HashSet<&DataType> = self
+                    .data
+                    .iter()
+                    .map(|row| &row[i])
+                    .filter(|v| !v.is_null())
+                    .collect();
+                (name.clone(), values.len())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iris() -> DataFrame {
+        DataFrame::read("./examples/iris.csv").unwrap()
+    }
+
+    #[test]
+    fn test_duplicated() {
+        let df = iris();
+        let first = df.duplicated(None, Keep::First).unwrap();
+        assert_eq!(positions(&first), vec![142]);
+        let last = df.duplicated(None, Keep::Last).unwrap();
+        assert_eq!(positions(&last), vec![101]);
+        let none = df.duplicated(None, Keep::None).unwrap();
+        assert_eq!(positions(&none), vec![101, 142]);
+
+        let by_variety = df.duplicated(Some(&["variety"]), Keep::First).unwrap();
+        assert_eq!(by_variety.iter().filter(|d| !**d).count(), 3);
+    }
+
+    #[test]
+    fn test_drop_duplicates() {
+        let df = iris();
+        assert_eq!(df.drop_duplicates(None, Keep::First).unwrap().nrows(), 149);
+        assert_eq!(df.drop_duplicates(None, Keep::None).unwrap().nrows(), 148);
+
+        let mock = DataFrame::read("./examples/MOCK_DATA.csv").unwrap();
+        let dedup = mock
+            .drop_duplicates(Some(&["boolean_test", "string_test"]), Keep::First)
+            .unwrap();
+        // Ten distinct (boolean, string) pairs occur, counting null as a value.
+        assert_eq!(dedup.nrows(), 10);
+    }
+
+    #[test]
+    fn test_n_unique() {
+        let n = iris().n_unique();
+        assert_eq!(n["sepal.length"], 35);
+        assert_eq!(n["variety"], 3);
+        let n = DataFrame::read("./examples/MOCK_DATA.csv")
+            .unwrap()
+            .n_unique();
+        assert_eq!(n["boolean_test"], 2);
+    }
+
+    fn positions(flags: &[bool]) -> Vec<usize> {
+        (0..flags.len()).filter(|&i| flags[i]).collect()
+    }
+}
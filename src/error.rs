@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatustError {
+    ColumnNotFound(String),
+    DuplicateColumn(String),
+    LengthMismatch { expected: usize, found: usize },
+    InvalidArgument(String),
+}
+
+impl StatustError {
+    /// Shorthand for `StatustError::InvalidArgument`.
+    pub(crate) fn invalid(msg: String) -> StatustError {
+        StatustError::InvalidArgument(msg)
+    }
+}
+
+impl Display for StatustError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            StatustError::ColumnNotFound(c) => write!(f, "Column not found: {}", c),
+            StatustError::DuplicateColumn(c) => write!(f, "Duplicate column: {}", c),
+            StatustError::LengthMismatch { expected, found } => write!(
+                f,
+                "Length mismatch: expected {} rows, found {}",
+                expected, found
+            ),
+            StatustError::InvalidArgument(s) => write!(f, "Invalid argument: {}", s),
+        }
+    }
+}
+
+impl Error for StatustError {}
+
+/// Checks that a confidence level lies strictly between 0 and 1.
+pub(crate) fn check_confidence(confidence: f64) -> Result<(), StatustError> {
+    if confidence > 0.0 && confidence < 1.0 {
+        Ok(())
+    } else {
+        Err(StatustError::invalid(format!(
+            "confidence level must be in (0, 1), got {}",
+            confidence
+        )))
+    }
+}
@@ -0,0 +1,366 @@
+use crate::*;
+use std::cmp::Ordering;
+use std::ops::{Add, Div, Mul, Neg, Not, Sub};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    And,
+    Or,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StrFn {
+    Lower,
+    Upper,
+    Trim,
+    Len,
+    Contains(String),
+    StartsWith(String),
+    EndsWith(String),
+    Replace(String, String),
+}
+
+/// A column expression evaluated row by row against a `DataFrame`.
+///
+/// Nulls propagate through every operation; a null `when` condition selects
+/// the `otherwise` branch.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Col(String),
+    Lit(DataType),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Str(Box<Expr>, StrFn),
+    Cast(Box<Expr>, DType),
+    When {
+        cond: Box<Expr>,
+        then: Box<Expr>,
+        otherwise: Box<Expr>,
+    },
+}
+
+pub fn col(name: &str) -> Expr {
+    Expr::Col(name.to_string())
+}
+
+pub fn lit<T: Into<DataType>>(value: T) -> Expr {
+    Expr::Lit(value.into())
+}
+
+pub fn when(cond: Expr) -> When {
+    When { cond }
+}
+
+pub struct When {
+    cond: Expr,
+}
+
+pub struct Then {
+    cond: Expr,
+    then: Expr,
+}
+
+impl When {
+    pub fn then(self, expr: Expr) -> Then {
+        Then {
+            cond: self.cond,
+            then: expr,
+        }
+    }
+}
+
+impl Then {
+    pub fn otherwise(self, expr: Expr) -> Expr {
+        Expr::When {
+            cond: Box::new(self.cond),
+            then: Box::new(self.then),
+            otherwise: Box::new(expr),
+        }
+    }
+}
+
+impl Expr {
+    fn binary(self, op: BinaryOp, rhs: Expr) -> Expr {
+        Expr::Binary(Box::new(self), op, Box::new(rhs))
+    }
+
+    pub fn equal(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Eq, rhs)
+    }
+
+    pub fn not_equal(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::NotEq, rhs)
+    }
+
+    pub fn lt(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Lt, rhs)
+    }
+
+    pub fn lt_eq(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::LtEq, rhs)
+    }
+
+    pub fn gt(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Gt, rhs)
+    }
+
+    pub fn gt_eq(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::GtEq, rhs)
+    }
+
+    pub fn and(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::And, rhs)
+    }
+
+    pub fn or(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Or, rhs)
+    }
+
+    pub fn cast(self, to: DType) -> Expr {
+        Expr::Cast(Box::new(self), to)
+    }
+
+    pub fn str(self, f: StrFn) -> Expr {
+        Expr::Str(Box::new(self), f)
+    }
+
+    pub fn evaluate(&self, df: &DataFrame) -> Result<Vec<DataType>, StatustError> {
+        let n = df.nrows();
+        match self {
+            Expr::Col(name) => df
+                .get_col_by_name(name)
+                .ok_or_else(|| StatustError::ColumnNotFound(name.clone())),
+            Expr::Lit(v) => Ok(vec![v.clone(); n]),
+            Expr::Binary(lhs, op, rhs) => {
+                let lhs = lhs.evaluate(df)?;
+                let rhs = rhs.evaluate(df)?;
+                Ok(lhs
+                    .into_iter()
+                    .zip(rhs)
+                    .map(|(a, b)| apply_binary(a, *op, b))
+                    .collect())
+            }
+            Expr::Neg(e) => Ok(e.evaluate(df)?.into_iter().map(|v| -v).collect()),
+            Expr::Not(e) => Ok(e
+                .evaluate(df)?
+                .into_iter()
+                .map(|v| match v {
+                    DataType::Bool(b) => DataType::Bool(!b),
+                    _ => DataType::None,
+                })
+                .collect()),
+            Expr::Str(e, f) => Ok(e
+                .evaluate(df)?
+                .into_iter()
+                .map(|v| apply_str(v, f))
+                .collect()),
+            Expr::Cast(e, to) => Ok(e.evaluate(df)?.iter().map(|v| v.cast(*to)).collect()),
+            Expr::When {
+                cond,
+                then,
+                otherwise,
+            } => {
+                let cond = cond.evaluate(df)?;
+                let then = then.evaluate(df)?;
+                let otherwise = otherwise.evaluate(df)?;
+                Ok(cond
+                    .into_iter()
+                    .zip(then.into_iter().zip(otherwise))
+                    .map(|(c, (t, o))| if c == DataType::Bool(true) { t } else { o })
+                    .collect())
+            }
+        }
+    }
+}
+
+fn apply_binary(a: DataType, op: BinaryOp, b: DataType) -> DataType {
+    let cmp = |f: fn(Ordering) -> bool, a: &DataType, b: &DataType| {
+        a.compare(b)
+            .map_or(DataType::None, |o| DataType::Bool(f(o)))
+    };
+    match op {
+        BinaryOp::Add => a + b,
+        BinaryOp::Sub => a - b,
+        BinaryOp::Mul => a * b,
+        BinaryOp::Div => a / b,
+        BinaryOp::Eq => cmp(|o| o == Ordering::Equal, &a, &b),
+        BinaryOp::NotEq => cmp(|o| o != Ordering::Equal, &a, &b),
+        BinaryOp::Lt => cmp(|o| o == Ordering::Less, &a, &b),
+        BinaryOp::LtEq => cmp(|o| o != Ordering::Greater, &a, &b),
+        BinaryOp::Gt => cmp(|o| o == Ordering::Greater, &a, &b),
+        BinaryOp::GtEq => cmp(|o| o != Ordering::Less, &a, &b),
+        BinaryOp::And | BinaryOp::Or => match (a, b) {
+            (DataType::Bool(a), DataType::Bool(b)) => {
+                DataType::Bool(if op == BinaryOp::And { a && b } else { a || b })
+            }
+            _ => DataType::None,
+        },
+    }
+}
+
+fn apply_str(v: DataType, f: &StrFn) -> DataType {
+    let s = match v {
+        DataType::String(s) if !s.is_empty() => s,
+        _ => return DataType::None,
+    };
+    match f {
+        StrFn::Lower => DataType::String(s.to_lowercase()),
+        StrFn::Upper => DataType::String(s.to_uppercase()),
+        StrFn::Trim => DataType::String(s.trim().to_string()),
+        StrFn::Len => DataType::Int(s.chars().count() as i32),
+        StrFn::Contains(p) => DataType::Bool(s.contains(p.as_str())),
+        StrFn::StartsWith(p) => DataType::Bool(s.starts_with(p.as_str())),
+        StrFn::EndsWith(p) => DataType::Bool(s.ends_with(p.as_str())),
+        StrFn::Replace(from, to) => DataType::String(s.replace(from.as_str(), to)),
+    }
+}
+
+impl Add for Expr {
+    type Output = Expr;
+
+    fn add(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Add, rhs)
+    }
+}
+
+impl Sub for Expr {
+    type Output = Expr;
+
+    fn sub(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Sub, rhs)
+    }
+}
+
+impl Mul for Expr {
+    type Output = Expr;
+
+    fn mul(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Mul, rhs)
+    }
+}
+
+impl Div for Expr {
+    type Output = Expr;
+
+    fn div(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Div, rhs)
+    }
+}
+
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        Expr::Neg(Box::new(self))
+    }
+}
+
+impl Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Expr {
+        Expr::Not(Box::new(self))
+    }
+}
+
+impl DataFrame {
+    /// Adds a column computed from `expr`, replacing any column with the same name.
+    pub fn with_column(&mut self, name: &str, expr: Expr) -> Result<(), StatustError> {
+        let values = expr.evaluate(self)?;
+        self.set_col(name, values)
+    }
+
+    /// Adds a column computed by calling `f` on every row. Values in the row are
+    /// ordered as in `DataFrame::header`.
+    pub fn with_column_fn<F>(&mut self, name: &str, f: F) -> Result<(), StatustError>
+    where
+        F: Fn(&[DataType]) -> DataType,
+    {
+        let values = (0..self.nrows())
+            .map(|r| f(&self.get_row(r).unwrap()))
+            .collect();
+        self.set_col(name, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_column_arithmetic() {
+        let mut df = DataFrame::read("./examples/iris.csv").unwrap();
+        df.with_column("petal.area", col("petal.length") * col("petal.width"))
+            .unwrap();
+        assert_eq!(df.ncols(), 6);
+        let area = df.get_col_by_name("petal.area").unwrap();
+        assert_eq!(area[0], DataType::Float(1.4 * 0.2));
+
+        df.with_column("half", col("sepal.width") / lit(2)).unwrap();
+        assert_eq!(df.get_col_by_name("half").unwrap()[1], DataType::Float(1.5));
+
+        assert_eq!(
+            df.with_column("x", col("missing") + lit(1)).unwrap_err(),
+            StatustError::ColumnNotFound("missing".to_string())
+        );
+    }
+
+    #[test]
+    fn test_with_column_when_and_strings() {
+        let mut df = DataFrame::read("./examples/iris.csv").unwrap();
+        df.with_column(
+            "size",
+            when(col("sepal.length").gt(lit(7.0)))
+                .then(lit("large"))
+                .otherwise(lit("small")),
+        )
+        .unwrap();
+        let size = df.get_col_by_name("size").unwrap();
+        assert_eq!(size.iter().filter(|v| **v == lit_str("large")).count(), 12);
+
+        df.with_column("variety", col("variety").str(StrFn::Lower))
+            .unwrap();
+        assert_eq!(df.ncols(), 6);
+        assert_eq!(df.get_col_by_name("variety").unwrap()[0], lit_str("setosa"));
+
+        df.with_column("sw", col("sepal.width").cast(DType::String))
+            .unwrap();
+        assert_eq!(df.get_col_by_name("sw").unwrap()[1], lit_str("3"));
+    }
+
+    #[test]
+    fn test_null_propagation() {
+        let mut df = DataFrame::read("./examples/MOCK_DATA.csv").unwrap();
+        df.with_column("sum", col("int_test") + col("float_test"))
+            .unwrap();
+        let sum = df.get_col_by_name("sum").unwrap();
+        assert_eq!(sum[6], DataType::None);
+        assert_eq!(sum.iter().filter(|v| v.is_null()).count(), 13);
+
+        df.with_column("flag", !col("boolean_test")).unwrap();
+        assert_eq!(df.get_col_by_name("flag").unwrap()[0], DataType::Bool(true));
+    }
+
+    #[test]
+    fn test_with_column_fn() {
+        let mut df = DataFrame::read("./examples/iris.csv").unwrap();
+        df.with_column_fn("n", |row| DataType::Int(row.len() as i32))
+            .unwrap();
+        assert_eq!(df.get_col_by_name("n").unwrap()[0], DataType::Int(5));
+    }
+
+    fn lit_str(s: &str) -> DataType {
+        DataType::String(s.to_string())
+    }
+}
@@ -6,6 +6,21 @@ use std::io::Write;
 pub enum ResultType {
     DescribeOne(DescribeResult),
     DescribeMany(HashMap<String, DescribeResult>),
+    NullReport(NullReport),
+    Test(TestResult),
+    Tests(Vec<TestResult>),
+    Correlation(CorrelationMatrix),
+    Matrix(LabelledMatrix),
+    Anova(AnovaTable),
+    PostHoc(PostHocResult),
+    CountTest(CountTestResult),
+    Adjusted(AdjustedPValues),
+    Ols(OlsResult),
+    Glm(GlmResult),
+    Penalized(PenalizedPath),
+    PenalizedCv(PenalizedCv),
+    Diagnostics(RegressionDiagnostics),
+    Prediction(Prediction),
 }
 
 pub struct FileWriter {
@@ -26,6 +41,80 @@ impl FileWriter {
                     writeln!(file, "{}", v).unwrap();
                 }
             }
+            ResultType::NullReport(r) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", r).unwrap();
+            }
+            ResultType::Test(t) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", t).unwrap();
+            }
+            ResultType::Tests(tests) => {
+                let mut file = File::create(&self.fname).unwrap();
+                for t in tests {
+                    writeln!(file, "{}", t).unwrap();
+                }
+            }
+            ResultType::Correlation(c) => {
+                let mut file = File::create(&self.fname).unwrap();
+                if self.fname.ends_with(".csv") {
+                    write!(file, "{}", c.to_csv()).unwrap();
+                } else {
+                    writeln!(file, "{}", c).unwrap();
+                }
+            }
+            ResultType::Matrix(m) => {
+                let mut file = File::create(&self.fname).unwrap();
+                if self.fname.ends_with(".csv") {
+                    write!(file, "{}", m.to_csv()).unwrap();
+                } else {
+                    writeln!(file, "{}", m).unwrap();
+                }
+            }
+            ResultType::Anova(a) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", a).unwrap();
+            }
+            ResultType::PostHoc(p) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", p).unwrap();
+            }
+            ResultType::CountTest(c) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", c).unwrap();
+            }
+            ResultType::Adjusted(a) => {
+                let mut file = File::create(&self.fname).unwrap();
+                if self.fname.ends_with(".csv") {
+                    write!(file, "{}", a.to_csv()).unwrap();
+                } else {
+                    writeln!(file, "{}", a).unwrap();
+                }
+            }
+            ResultType::Ols(o) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", o).unwrap();
+            }
+            ResultType::Glm(g) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", g).unwrap();
+            }
+            ResultType::Penalized(p) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", p).unwrap();
+            }
+            ResultType::PenalizedCv(c) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", c).unwrap();
+            }
+            ResultType::Diagnostics(d) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", d).unwrap();
+            }
+            ResultType::Prediction(p) => {
+                let mut file = File::create(&self.fname).unwrap();
+                writeln!(file, "{}", p).unwrap();
+            }
         }
     }
 }
@@ -60,4 +149,36 @@ mod tests {
         assert!(res_read.contains("Max: 7.9"));
         assert!(res_read.contains("Mean: 3.7580001"));
     }
+
+    #[test]
+    fn test_write_tests() {
+        let df = super::DataFrame::read("./examples/iris.csv").unwrap();
+        let results = df.normality_test(super::NormalityTest::JarqueBera).unwrap();
+        let res_fname = std::env::temp_dir().join("statust_iris_normality.txt");
+        let file_writer = super::FileWriter {
+            fname: res_fname.to_str().unwrap().to_string(),
+            data: super::ResultType::Tests(results),
+        };
+        file_writer.write_file();
+        let res_read = fs::read_to_string(&res_fname).unwrap();
+        assert_eq!(res_read.matches("Jarque-Bera normality test").count(), 4);
+        assert!(res_read.contains("Data: petal.width"));
+        assert!(res_read.contains("df: 2"));
+        assert!(res_read.contains("Alternative: two-sided"));
+    }
+
+    #[test]
+    fn test_write_correlation_csv() {
+        let df = super::DataFrame::read("./examples/iris.csv").unwrap();
+        let corr = df.corr(super::CorrMethod::Spearman).unwrap();
+        let res_fname = std::env::temp_dir().join("statust_iris_corr.csv");
+        let file_writer = super::FileWriter {
+            fname: res_fname.to_str().unwrap().to_string(),
+            data: super::ResultType::Correlation(corr),
+        };
+        file_writer.write_file();
+        let res_read = fs::read_to_string(&res_fname).unwrap();
+        assert_eq!(res_read.lines().count(), 7);
+        assert!(res_read.contains("sepal.length,petal.length,150,0.88189"));
+    }
 }
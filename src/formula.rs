@@ -0,0 +1,485 @@
+use crate::contingency::levels;
+use crate::linalg::Matrix;
+use crate::*;
+use std::fmt;
+
+/// A column used in a formula. `C(name)` forces categorical coding of a
+/// numeric column; String and Bool columns are always categorical.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    pub name: String,
+    pub categorical: bool,
+}
+
+impl fmt::Display for Variable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.categorical {
+            write!(f, "C({})", self.name)
+        } else {
+            write!(f, "{}", self.name)
+        }
+    }
+}
+
+/// A main effect, or an interaction of several variables joined by `:`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Term {
+    pub variables: Vec<Variable>,
+}
+
+impl Term {
+    fn key(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.variables.iter().map(|v| v.name.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self.variables.iter().map(|v| v.to_string()).collect();
+        write!(f, "{}", names.join(":"))
+    }
+}
+
+/// A model formula such as `y ~ x1 + x2 + C(group) + x1:x2`. `a * b`
+/// expands to `a + b + a:b`, and `- 1` or `+ 0` removes the intercept.
+/// Terms are ordered by the number of variables they involve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Formula {
+    pub response: String,
+    pub terms: Vec<Term>,
+    pub intercept: bool,
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rhs: Vec<String> = self.terms.iter().map(|t| t.to_string()).collect();
+        if rhs.is_empty() {
+            rhs.push("1".to_string());
+        }
+        write!(f, "{} ~ {}", self.response, rhs.join(" + "))?;
+        if !self.intercept {
+            write!(f, " - 1")?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_variable(spec: &str) -> Result<Variable, StatustError> {
+    let spec = spec.trim();
+    let (name, categorical) = match spec.strip_prefix("C(").and_then(|s| s.strip_suffix(')')) {
+        Some(inner) => (inner.trim(), true),
+        None => (spec, false),
+    };
+    if name.is_empty() || name.contains(['(', ')']) {
+        return Err(StatustError::invalid(format!(
+            "invalid variable '{}' in formula",
+            spec
+        )));
+    }
+    Ok(Variable {
+        name: name.to_string(),
+        categorical,
+    })
+}
+
+/// Expands `a*b:c*d` into all products of the `*`-separated groups.
+fn expand(spec: &str) -> Result<Vec<Term>, StatustError> {
+    let groups = spec
+        .split('*')
+        .map(|g| g.split(':').map(parse_variable).collect())
+        .collect::<Result<Vec<Vec<Variable>>, StatustError>>()?;
+    let mut masks: Vec<usize> = (1..1usize << groups.len()).collect();
+    masks.sort_by_key(|m| m.count_ones());
+    Ok(masks
+        .into_iter()
+        .map(|mask| {
+            let mut variables: Vec<Variable> = Vec::new();
+            for (i, group) in groups.iter().enumerate() {
+                if mask & (1 << i) != 0 {
+                    for v in group {
+                        if !variables.contains(v) {
+                            variables.push(v.clone());
+                        }
+                    }
+                }
+            }
+            Term { variables }
+        })
+        .collect())
+}
+
+impl Formula {
+    pub fn parse(spec: &str) -> Result<Formula, StatustError> {
+        let (lhs, rhs) = spec.split_once('~').ok_or_else(|| {
+            StatustError::invalid(format!("formula must contain '~', got '{}'", spec))
+        })?;
+        let response = lhs.trim();
+        if response.is_empty() {
+            return Err(StatustError::invalid(format!(
+                "formula needs a response before '~', got '{}'",
+                spec
+            )));
+        }
+        let mut intercept = true;
+        let mut terms: Vec<Term> = Vec::new();
+        for piece in rhs.split('+') {
+            let mut parts = piece.split('-');
+            let first = parts.next().unwrap_or("").trim();
+            let mut removed = false;
+            for part in parts {
+                if part.trim() != "1" {
+                    return Err(StatustError::invalid(format!(
+                        "only '- 1' can be subtracted in a formula, got '-{}'",
+                        part
+                    )));
+                }
+                intercept = false;
+                removed = true;
+            }
+            match first {
+                "" if removed => {}
+                "" => {
+                    return Err(StatustError::invalid(format!(
+                        "empty term in formula '{}'",
+                        spec
+                    )))
+                }
+                "1" => intercept = true,
+                "0" => intercept = false,
+                _ => {
+                    for term in expand(first)? {
+                        if !terms.iter().any(|t| t.key() == term.key()) {
+                            terms.push(term);
+                        }
+                    }
+                }
+            }
+        }
+        terms.sort_by_key(|t| t.variables.len());
+        if terms.is_empty() && !intercept {
+            return Err(StatustError::invalid(format!(
+                "formula '{}' has no terms",
+                spec
+            )));
+        }
+        Ok(Formula {
+            response: response.to_string(),
+            terms,
+            intercept,
+        })
+    }
+
+    /// Builds the design matrix and response from `df`, dropping rows with a
+    /// null in any variable of the formula and rejecting NaN or infinite
+    /// values. Categorical variables are coded
+    /// with indicator columns for every level but the first, except where
+    /// the term without that variable is absent from the model, as in R.
+    pub fn design(&self, df: &DataFrame) -> Result<Design, StatustError> {
+        self.build(df, None)
+    }
+
+    /// Design matrix for new data, coded with the levels of a design built
+    /// earlier. The response column is not needed.
+    pub fn design_like(&self, df: &DataFrame, fitted: &Design) -> Result<Design, StatustError> {
+        self.build(df, Some(&fitted.levels))
+    }
+
+    fn build(
+        &self,
+        df: &DataFrame,
+        known_levels: Option<&[(String, Vec<DataType>)]>,
+    ) -> Result<Design, StatustError> {
+        let mut names: Vec<&str> = Vec::new();
+        if known_levels.is_none() {
+            names.push(&self.response);
+        }
+        for v in self.terms.iter().flat_map(|t| &t.variables) {
+            if !names.contains(&v.name.as_str()) {
+                names.push(&v.name);
+            }
+        }
+        let columns = names
+            .iter()
+            .map(|name| {
+                df.get_col_by_name(name)
+                    .ok_or_else(|| StatustError::ColumnNotFound(name.to_string()))
+            })
+            .collect::<Result<Vec<Vec<DataType>>, StatustError>>()?;
+        let rows: Vec<usize> = (0..df.nrows())
+            .filter(|&r| columns.iter().all(|c| !c[r].is_null()))
+            .collect();
+        if rows.is_empty() {
+            return Err(StatustError::invalid(format!(
+                "no rows without nulls for formula '{}'",
+                self
+            )));
+        }
+        for (name, c) in names.iter().zip(&columns) {
+            if let Some(r) = rows
+                .iter()
+                .find(|&&r| c[r].as_f64().is_some_and(|v| !v.is_finite()))
+            {
+                return Err(StatustError::invalid(format!(
+                    "column {} has a non-finite value in row {}",
+                    name, r
+                )));
+            }
+        }
+        let column = |name: &str| -> Vec<DataType> {
+            let c = &columns[names.iter().position(|n| *n == name).unwrap()];
+            rows.iter().map(|&r| c[r].clone()).collect()
+        };
+
+        let mut response_level = None;
+        let y = if known_levels.is_some() {
+            Vec::new()
+        } else {
+            let values = column(&self.response);
+            if values.iter().any(|v| v.as_f64().is_none()) {
+                // A two-level response is coded 1 for its second level, as
+                // R does for factors.
+                let levels = levels(&values);
+                if levels.len() != 2 {
+                    return Err(StatustError::invalid(format!(
+                        "response {} must be numeric or have two levels, found {}",
+                        self.response,
+                        levels.len()
+                    )));
+                }
+                response_level = Some(levels[1].to_string());
+                values
+                    .iter()
+                    .map(|v| f64::from(u8::from(*v == levels[1])))
+                    .collect()
+            } else {
+                values.iter().map(|v| v.as_f64().unwrap()).collect()
+            }
+        };
+
+        let mut levels_out: Vec<(String, Vec<DataType>)> = Vec::new();
+        let mut design_cols: Vec<(String, Vec<f64>)> = Vec::new();
+        if self.intercept {
+            design_cols.push(("(Intercept)".to_string(), vec![1.0; rows.len()]));
+        }
+        for term in &self.terms {
+            let mut term_cols: Vec<(String, Vec<f64>)> =
+                vec![(String::new(), vec![1.0; rows.len()])];
+            for v in &term.variables {
+                let values = column(&v.name);
+                let categorical = v.categorical || values.iter().any(|x| x.as_f64().is_none());
+                let var_cols: Vec<(String, Vec<f64>)> = if categorical {
+                    let levels = match known_levels {
+                        Some(known) => {
+                            let levels = known
+                                .iter()
+                                .find(|(n, _)| *n == v.name)
+                                .map(|(_, l)| l.clone())
+                                .ok_or_else(|| {
+                                    StatustError::invalid(format!(
+                                        "{} was not categorical when fitted",
+                                        v
+                                    ))
+                                })?;
+                            if let Some(unknown) = values.iter().find(|x| !levels.contains(x)) {
+                                return Err(StatustError::invalid(format!(
+                                    "unknown level '{}' of {}",
+                                    unknown, v.name
+                                )));
+                            }
+                            levels
+                        }
+                        None => levels(&values),
+                    };
+                    if levels.len() < 2 {
+                        return Err(StatustError::invalid(format!(
+                            "{} needs at least two levels, found {}",
+                            v,
+                            levels.len()
+                        )));
+                    }
+                    if !levels_out.iter().any(|(n, _)| *n == v.name) {
+                        levels_out.push((v.name.clone(), levels.clone()));
+                    }
+                    let skip = usize::from(self.has_margin(term, v));
+                    levels[skip..]
+                        .iter()
+                        .map(|level| {
+                            (
+                                format!("{}[{}]", v, level),
+                                values
+                                    .iter()
+                                    .map(|x| f64::from(u8::from(x == level)))
+                                    .collect(),
+                            )
+                        })
+                        .collect()
+                } else {
+                    vec![(
+                        v.to_string(),
+                        values.iter().map(|x| x.as_f64().unwrap()).collect(),
+                    )]
+                };
+                term_cols = term_cols
+                    .iter()
+                    .flat_map(|(name, col)| {
+                        var_cols.iter().map(move |(vname, vcol)| {
+                            let joined = if name.is_empty() {
+                                vname.clone()
+                            } else {
+                                format!("{}:{}", name, vname)
+                            };
+                            (joined, col.iter().zip(vcol).map(|(a, b)| a * b).collect())
+                        })
+                    })
+                    .collect();
+            }
+            design_cols.extend(term_cols);
+        }
+
+        let mut x = Matrix::zeros(rows.len(), design_cols.len());
+        for (j, (_, col)) in design_cols.iter().enumerate() {
+            for (i, v) in col.iter().enumerate() {
+                x[(i, j)] = *v;
+            }
+        }
+        Ok(Design {
+            columns: design_cols.into_iter().map(|(name, _)| name).collect(),
+            x,
+            y,
+            response_level,
+            rows,
+            levels: levels_out,
+        })
+    }
+
+    /// Whether the model contains `term` without `variable` (the intercept
+    /// standing in for the empty term), so that `variable` can be coded
+    /// with contrasts rather than one indicator per level.
+    fn has_margin(&self, term: &Term, variable: &Variable) -> bool {
+        let mut key = term.key();
+        key.retain(|n| *n != variable.name);
+        if key.is_empty() {
+            self.intercept
+        } else {
+            self.terms.iter().any(|t| t.key() == key)
+        }
+    }
+}
+
+/// Design matrix and response built from a formula and a data frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Design {
+    /// Names of the design columns, e.g. `(Intercept)`, `x1`,
+    /// `group[b]` or `x1:group[b]`.
+    pub columns: Vec<String>,
+    pub x: Matrix,
+    /// Response values; empty for designs built for prediction.
+    pub y: Vec<f64>,
+    /// The level coded as 1 when the response is categorical.
+    pub response_level: Option<String>,
+    /// Indices of the data frame rows used, after dropping rows with nulls.
+    pub rows: Vec<usize>,
+    /// Sorted levels of each categorical variable.
+    pub levels: Vec<(String, Vec<DataType>)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let f = Formula::parse("y ~ a*b + C(g) + a:b - 1").unwrap();
+        assert_eq!(f.response, "y");
+        assert!(!f.intercept);
+        assert_eq!(f.to_string(), "y ~ a + b + C(g) + a:b - 1");
+        let f = Formula::parse(" y~x+0+1 ").unwrap();
+        assert!(f.intercept);
+        assert_eq!(f.to_string(), "y ~ x");
+        assert_eq!(Formula::parse("y ~ 1").unwrap().to_string(), "y ~ 1");
+        assert_eq!(
+            Formula::parse("y ~ a*b*c").unwrap().to_string(),
+            "y ~ a + b + c + a:b + a:c + b:c + a:b:c"
+        );
+        for bad in ["y", "~ x", "y ~ x +", "y ~ x - z", "y ~ 0", "y ~ C()"] {
+            assert!(Formula::parse(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_design() {
+        let df = DataFrame::from_columns(
+            vec!["y".to_string(), "x".to_string(), "g".to_string()],
+            vec![
+                vec![
+                    DataType::Float(1.0),
+                    DataType::Float(2.0),
+                    DataType::None,
+                    DataType::Float(4.0),
+                ],
+                vec![
+                    DataType::Int(1),
+                    DataType::Int(2),
+                    DataType::Int(3),
+                    DataType::Int(2),
+                ],
+                vec![
+                    DataType::String("b".to_string()),
+                    DataType::String("a".to_string()),
+                    DataType::String("a".to_string()),
+                    DataType::String("c".to_string()),
+                ],
+            ],
+        )
+        .unwrap();
+        let d = Formula::parse("y ~ x*g").unwrap().design(&df).unwrap();
+        assert_eq!(
+            d.columns,
+            ["(Intercept)", "x", "g[b]", "g[c]", "x:g[b]", "x:g[c]"]
+        );
+        assert_eq!(d.rows, vec![0, 1, 3]);
+        assert_eq!(d.y, vec![1.0, 2.0, 4.0]);
+        assert_eq!(d.x.row(2), &[1.0, 2.0, 0.0, 1.0, 0.0, 2.0]);
+
+        let d = Formula::parse("y ~ C(x) - 1").unwrap().design(&df).unwrap();
+        assert_eq!(d.columns, ["C(x)[1]", "C(x)[2]"]);
+        let d = Formula::parse("y ~ x:g").unwrap().design(&df).unwrap();
+        assert_eq!(d.columns, ["(Intercept)", "x:g[a]", "x:g[b]", "x:g[c]"]);
+
+        let fitted = Formula::parse("y ~ g").unwrap().design(&df).unwrap();
+        let new = DataFrame::from_columns(
+            vec!["g".to_string()],
+            vec![vec![DataType::String("c".to_string())]],
+        )
+        .unwrap();
+        let f = Formula::parse("y ~ g").unwrap();
+        let d = f.design_like(&new, &fitted).unwrap();
+        assert_eq!(d.x.row(0), &[1.0, 0.0, 1.0]);
+        assert!(d.y.is_empty());
+        let unknown = DataFrame::from_columns(
+            vec!["g".to_string()],
+            vec![vec![DataType::String("z".to_string())]],
+        )
+        .unwrap();
+        assert!(f.design_like(&unknown, &fitted).is_err());
+        assert!(Formula::parse("g ~ x").unwrap().design(&df).is_err());
+
+        let binary = DataFrame::from_columns(
+            vec!["y".to_string(), "x".to_string()],
+            vec![
+                ["yes", "no", "yes"]
+                    .iter()
+                    .map(|s| DataType::String(s.to_string()))
+                    .collect(),
+                vec![DataType::Int(1), DataType::Int(2), DataType::Int(3)],
+            ],
+        )
+        .unwrap();
+        let d = Formula::parse("y ~ x").unwrap().design(&binary).unwrap();
+        assert_eq!(d.y, vec![1.0, 0.0, 1.0]);
+        assert_eq!(d.response_level.as_deref(), Some("yes"));
+        assert!(Formula::parse("y ~ z").unwrap().design(&df).is_err());
+    }
+}
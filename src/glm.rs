@@ -0,0 +1,923 @@
+use crate::distributions::{ChiSquared, ContinuousDistribution, Normal, StudentT};
+use crate::error::check_confidence;
+use crate::linalg::Matrix;
+use crate::special::{digamma, erfc, ln_gamma, ndtri, trigamma};
+use crate::*;
+use std::f64::consts::{PI, SQRT_2};
+use std::fmt;
+
+const MAX_ITER: usize = 50;
+const TOLERANCE: f64 = 1e-10;
+
+/// Link function `g` relating the mean to the linear predictor,
+/// `g(mu) = eta`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Link {
+    Identity,
+    Log,
+    Logit,
+    Probit,
+    /// Complementary log-log, `ln(-ln(1 - mu))`.
+    Cloglog,
+    Inverse,
+    Sqrt,
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Link::Identity => "identity",
+            Link::Log => "log",
+            Link::Logit => "logit",
+            Link::Probit => "probit",
+            Link::Cloglog => "cloglog",
+            Link::Inverse => "inverse",
+            Link::Sqrt => "sqrt",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Means are kept away from the boundaries by `f64::EPSILON`, as in R's
+// `make.link`.
+impl Link {
+    fn link(&self, mu: f64) -> f64 {
+        match self {
+            Link::Identity => mu,
+            Link::Log => mu.ln(),
+            Link::Logit => (mu / (1.0 - mu)).ln(),
+            Link::Probit => ndtri(mu),
+            Link::Cloglog => (-(-mu).ln_1p()).ln(),
+            Link::Inverse => 1.0 / mu,
+            Link::Sqrt => mu.sqrt(),
+        }
+    }
+
+    fn inverse(&self, eta: f64) -> f64 {
+        match self {
+            Link::Identity => eta,
+            Link::Log => eta.exp().max(f64::EPSILON),
+            Link::Logit => {
+                if eta < -30.0 {
+                    f64::EPSILON
+                } else if eta > 30.0 {
+                    1.0 - f64::EPSILON
+                } else {
+                    1.0 / (1.0 + (-eta).exp())
+                }
+            }
+            Link::Probit => {
+                let thresh = -ndtri(f64::EPSILON);
+                0.5 * erfc(-eta.clamp(-thresh, thresh) / SQRT_2)
+            }
+            Link::Cloglog => (-(-eta.exp()).exp_m1()).clamp(f64::EPSILON, 1.0 - f64::EPSILON),
+            Link::Inverse => 1.0 / eta,
+            Link::Sqrt => eta * eta,
+        }
+    }
+
+    /// Derivative `d mu / d eta`.
+    fn mu_eta(&self, eta: f64) -> f64 {
+        match self {
+            Link::Identity => 1.0,
+            Link::Log => eta.exp().max(f64::EPSILON),
+            Link::Logit => {
+                let e = (-eta.abs()).exp();
+                (e / (1.0 + e).powi(2)).max(f64::EPSILON)
+            }
+            Link::Probit => ((-eta * eta / 2.0).exp() / (2.0 * PI).sqrt()).max(f64::EPSILON),
+            Link::Cloglog => {
+                let eta = eta.min(700.0);
+                (eta - eta.exp()).exp().max(f64::EPSILON)
+            }
+            Link::Inverse => -1.0 / (eta * eta),
+            Link::Sqrt => 2.0 * eta,
+        }
+    }
+}
+
+/// Error distribution of a generalized linear model.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Family {
+    Gaussian,
+    /// Binary response coded 0/1, or a two-level column.
+    Binomial,
+    Poisson,
+    Gamma,
+    /// Negative binomial with variance `mu + mu^2 / theta`. When `theta` is
+    /// `None` it is estimated by maximum likelihood, as in R's `MASS::glm.nb`.
+    NegativeBinomial(Option<f64>),
+}
+
+impl fmt::Display for Family {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Family::Gaussian => write!(f, "gaussian"),
+            Family::Binomial => write!(f, "binomial"),
+            Family::Poisson => write!(f, "poisson"),
+            Family::Gamma => write!(f, "Gamma"),
+            Family::NegativeBinomial(Some(theta)) => {
+                write!(f, "negative binomial (theta = {:.4})", theta)
+            }
+            Family::NegativeBinomial(None) => write!(f, "negative binomial"),
+        }
+    }
+}
+
+impl Family {
+    /// The canonical link, or log for the negative binomial.
+    pub fn default_link(&self) -> Link {
+        match self {
+            Family::Gaussian => Link::Identity,
+            Family::Binomial => Link::Logit,
+            Family::Poisson | Family::NegativeBinomial(_) => Link::Log,
+            Family::Gamma => Link::Inverse,
+        }
+    }
+
+    fn allows(&self, link: Link) -> bool {
+        use Link::*;
+        match self {
+            Family::Gaussian => matches!(link, Identity | Log | Inverse),
+            Family::Binomial => matches!(link, Logit | Probit | Cloglog | Log),
+            Family::Poisson | Family::NegativeBinomial(_) => matches!(link, Log | Identity | Sqrt),
+            Family::Gamma => matches!(link, Inverse | Identity | Log),
+        }
+    }
+
+    /// Whether the dispersion is estimated rather than fixed at 1.
+    fn estimates_dispersion(&self) -> bool {
+        matches!(self, Family::Gaussian | Family::Gamma)
+    }
+
+    fn theta(&self) -> f64 {
+        match self {
+            Family::NegativeBinomial(Some(theta)) => *theta,
+            _ => f64::NAN,
+        }
+    }
+
+    fn check_response(&self, y: &[f64]) -> Result<(), StatustError> {
+        let ok = |v: &f64| match self {
+            Family::Gaussian => v.is_finite(),
+            Family::Binomial => *v == 0.0 || *v == 1.0,
+            Family::Poisson | Family::NegativeBinomial(_) => *v >= 0.0 && v.is_finite(),
+            Family::Gamma => *v > 0.0 && v.is_finite(),
+        };
+        match y.iter().find(|v| !ok(v)) {
+            Some(v) => Err(StatustError::invalid(format!(
+                "response value {} is not valid for the {} family",
+                v, self
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn valid_mu(&self, mu: f64) -> bool {
+        match self {
+            Family::Gaussian => mu.is_finite(),
+            Family::Binomial => mu > 0.0 && mu < 1.0,
+            _ => mu > 0.0 && mu.is_finite(),
+        }
+    }
+
+    fn initial_mu(&self, y: f64) -> f64 {
+        match self {
+            Family::Binomial => (y + 0.5) / 2.0,
+            Family::Poisson | Family::NegativeBinomial(_) => y + 0.1,
+            _ => y,
+        }
+    }
+
+    fn variance(&self, mu: f64) -> f64 {
+        match self {
+            Family::Gaussian => 1.0,
+            Family::Binomial => mu * (1.0 - mu),
+            Family::Poisson => mu,
+            Family::Gamma => mu * mu,
+            Family::NegativeBinomial(_) => mu + mu * mu / self.theta(),
+        }
+    }
+
+    fn unit_deviance(&self, y: f64, mu: f64) -> f64 {
+        // y ln(y / mu), which is 0 for y = 0.
+        let ylog = |y: f64, mu: f64| if y == 0.0 { 0.0 } else { y * (y / mu).ln() };
+        match self {
+            Family::Gaussian => (y - mu).powi(2),
+            Family::Binomial => 2.0 * (ylog(y, mu) + ylog(1.0 - y, 1.0 - mu)),
+            Family::Poisson => 2.0 * (ylog(y, mu) - (y - mu)),
+            Family::Gamma => -2.0 * ((y / mu).ln() - (y - mu) / mu),
+            Family::NegativeBinomial(_) => {
+                let theta = self.theta();
+                2.0 * (ylog(y, mu) - (y + theta) * ((y + theta) / (mu + theta)).ln())
+            }
+        }
+    }
+
+    fn deviance(&self, y: &[f64], mu: &[f64]) -> f64 {
+        y.iter()
+            .zip(mu)
+            .map(|(y, m)| self.unit_deviance(*y, *m))
+            .sum()
+    }
+
+    /// Log-likelihood at the fitted means. The Gaussian and Gamma
+    /// dispersions are the ML estimates, as in R's `logLik`.
+    fn log_likelihood(&self, y: &[f64], mu: &[f64]) -> f64 {
+        let n = y.len() as f64;
+        match self {
+            Family::Gaussian => -n / 2.0 * ((2.0 * PI * self.deviance(y, mu) / n).ln() + 1.0),
+            Family::Gamma => {
+                let shape = n / self.deviance(y, mu);
+                y.iter()
+                    .zip(mu)
+                    .map(|(y, m)| {
+                        let scale = m / shape;
+                        (shape - 1.0) * y.ln() - y / scale - ln_gamma(shape) - shape * scale.ln()
+                    })
+                    .sum()
+            }
+            Family::Binomial => y
+                .iter()
+                .zip(mu)
+                .map(|(y, m)| if *y == 1.0 { m.ln() } else { (-m).ln_1p() })
+                .sum(),
+            Family::Poisson => y
+                .iter()
+                .zip(mu)
+                .map(|(y, m)| {
+                    let ylogm = if *y == 0.0 { 0.0 } else { y * m.ln() };
+                    ylogm - m - ln_gamma(y + 1.0)
+                })
+                .sum(),
+            Family::NegativeBinomial(_) => {
+                let theta = self.theta();
+                y.iter()
+                    .zip(mu)
+                    .map(|(y, m)| {
+                        let ylogm = if *y == 0.0 {
+                            0.0
+                        } else {
+                            y * (m / (m + theta)).ln()
+                        };
+                        ln_gamma(theta + y) - ln_gamma(theta) - ln_gamma(y + 1.0)
+                            + theta * (theta / (m + theta)).ln()
+                            + ylogm
+                    })
+                    .sum()
+            }
+        }
+    }
+}
+
+/// Weighted least squares through QR of `sqrt(w) X`.
+fn weighted_qr(x: &Matrix, w: &[f64]) -> Result<linalg::Qr, StatustError> {
+    let mut xw = x.clone();
+    for (i, wi) in w.iter().enumerate() {
+        let s = wi.sqrt();
+        for j in 0..x.ncols() {
+            xw[(i, j)] *= s;
+        }
+    }
+    xw.qr()
+}
+
+struct IrlsFit {
+    beta: Vec<f64>,
+    mu: Vec<f64>,
+    deviance: f64,
+    iterations: usize,
+    converged: bool,
+}
+
+/// Iteratively reweighted least squares, halving the step whenever it
+/// leaves the valid range of the mean. If the coefficients have not settled
+/// after `MAX_ITER` iterations, as when they diverge under separation, the
+/// last iterate is returned with `converged` unset.
+fn irls(
+    design: &Design,
+    family: Family,
+    link: Link,
+    start: Option<&[f64]>,
+) -> Result<IrlsFit, StatustError> {
+    let (x, y) = (&design.x, &design.y);
+    let (n, p) = (x.nrows(), x.ncols());
+    let mut mu: Vec<f64> = match start {
+        Some(mu) => mu.to_vec(),
+        None => y.iter().map(|v| family.initial_mu(*v)).collect(),
+    };
+    let mut eta: Vec<f64> = mu.iter().map(|m| link.link(*m)).collect();
+    let mut deviance = family.deviance(y, &mu);
+    let mut beta_old: Option<Vec<f64>> = None;
+    for iteration in 1..=MAX_ITER {
+        let mut z = vec![0.0; n];
+        let mut w = vec![0.0; n];
+        for i in 0..n {
+            let d = link.mu_eta(eta[i]);
+            z[i] = eta[i] + (y[i] - mu[i]) / d;
+            w[i] = d * d / family.variance(mu[i]);
+        }
+        let qr = weighted_qr(x, &w)?;
+        if iteration == 1 {
+            for j in 0..p {
+                let norm = x
+                    .col(j)
+                    .iter()
+                    .zip(&w)
+                    .map(|(v, w)| v * v * w)
+                    .sum::<f64>()
+                    .sqrt();
+                if norm == 0.0 || qr.r[(j, j)].abs() <= 1e-7 * norm {
+                    return Err(StatustError::invalid(format!(
+                        "design column {} is collinear with earlier columns",
+                        design.columns[j]
+                    )));
+                }
+            }
+        }
+        let zw: Vec<f64> = z.iter().zip(&w).map(|(z, w)| z * w.sqrt()).collect();
+        let mut beta = qr.solve(&zw)?;
+        let mut halvings = 0;
+        loop {
+            eta = x.mul_vec(&beta)?;
+            mu = eta.iter().map(|e| link.inverse(*e)).collect();
+            let dev = family.deviance(y, &mu);
+            if dev.is_finite() && mu.iter().all(|m| family.valid_mu(*m)) {
+                break;
+            }
+            match &beta_old {
+                Some(old) if halvings < 30 => {
+                    beta = beta.iter().zip(old).map(|(b, o)| (b + o) / 2.0).collect();
+                    halvings += 1;
+                }
+                _ => {
+                    return Err(StatustError::invalid(format!(
+                        "no valid fitted means for the {} family with {} link",
+                        family, link
+                    )))
+                }
+            }
+        }
+        // Stop once both the deviance and the coefficients have settled; the
+        // deviance alone is flat near the optimum and stops too early.
+        let dev = family.deviance(y, &mu);
+        let settled = beta_old.as_ref().is_some_and(|old| {
+            beta.iter()
+                .zip(old)
+                .all(|(b, o)| (b - o).abs() <= 1e-8 * (b.abs() + 1e-8))
+        });
+        let converged = settled && (dev - deviance).abs() / (dev.abs() + 0.1) < TOLERANCE;
+        deviance = dev;
+        beta_old = Some(beta);
+        if converged {
+            return Ok(IrlsFit {
+                beta: beta_old.unwrap(),
+                mu,
+                deviance,
+                iterations: iteration,
+                converged: true,
+            });
+        }
+    }
+    Ok(IrlsFit {
+        beta: beta_old.unwrap(),
+        mu,
+        deviance,
+        iterations: MAX_ITER,
+        converged: false,
+    })
+}
+
+/// Maximum likelihood estimate of the negative binomial `theta` for fixed
+/// means, by Newton's method as in `MASS::theta.ml`.
+fn theta_ml(y: &[f64], mu: &[f64]) -> Result<f64, StatustError> {
+    let n = y.len() as f64;
+    let mut theta = n / y
+        .iter()
+        .zip(mu)
+        .map(|(y, m)| (y / m - 1.0).powi(2))
+        .sum::<f64>();
+    for _ in 0..MAX_ITER {
+        let (mut score, mut info) = (0.0, 0.0);
+        for (y, m) in y.iter().zip(mu) {
+            score += digamma(theta + y) - digamma(theta) + theta.ln() + 1.0
+                - (theta + m).ln()
+                - (y + theta) / (m + theta);
+            info += -trigamma(theta + y) + trigamma(theta) - 1.0 / theta + 2.0 / (m + theta)
+                - (y + theta) / (m + theta).powi(2);
+        }
+        let step = score / info;
+        theta += step;
+        if !theta.is_finite() || theta <= 0.0 {
+            break;
+        }
+        if step.abs() <= TOLERANCE * theta {
+            return Ok(theta);
+        }
+    }
+    Err(StatustError::invalid(
+        "negative binomial theta did not converge; the data may not be overdispersed".to_string(),
+    ))
+}
+
+impl DataFrame {
+    /// Fits a generalized linear model, e.g.
+    /// `df.glm("score ~ age + housing", Family::Binomial, None, 0.95)`.
+    /// The link defaults to `family.default_link()`. Rows with a null in
+    /// any variable of the formula are dropped.
+    pub fn glm(
+        &self,
+        formula: &str,
+        family: Family,
+        link: Option<Link>,
+        confidence: f64,
+    ) -> Result<GlmResult, StatustError> {
+        let formula = Formula::parse(formula)?;
+        let design = formula.design(self)?;
+        glm(formula, design, family, link, confidence)
+    }
+}
+
+/// Fits a generalized linear model by iteratively reweighted least squares.
+pub fn glm(
+    formula: Formula,
+    design: Design,
+    family: Family,
+    link: Option<Link>,
+    confidence: f64,
+) -> Result<GlmResult, StatustError> {
+    check_confidence(confidence)?;
+    let link = link.unwrap_or_else(|| family.default_link());
+    if !family.allows(link) {
+        return Err(StatustError::invalid(format!(
+            "the {} link is not supported for the {} family",
+            link, family
+        )));
+    }
+    if let Family::NegativeBinomial(Some(theta)) = family {
+        if theta.is_nan() || theta <= 0.0 {
+            return Err(StatustError::invalid(format!(
+                "theta must be positive, got {}",
+                theta
+            )));
+        }
+    }
+    if design.response_level.is_some() && family != Family::Binomial {
+        return Err(StatustError::invalid(format!(
+            "the {} family needs a numeric response, not a two-level column",
+            family
+        )));
+    }
+    family.check_response(&design.y)?;
+    let (n, p) = (design.x.nrows(), design.x.ncols());
+    if n <= p {
+        return Err(StatustError::invalid(format!(
+            "GLM needs more observations ({}) than coefficients ({})",
+            n, p
+        )));
+    }
+
+    // With theta unknown, alternate between IRLS for fixed theta and the ML
+    // estimate of theta, starting from a Poisson fit.
+    let (family, fit, theta_estimated) = match family {
+        Family::NegativeBinomial(None) => {
+            let mut fit = irls(&design, Family::Poisson, link, None)?;
+            let mut theta = theta_ml(&design.y, &fit.mu)?;
+            let mut iterations = fit.iterations;
+            for _ in 0..MAX_ITER {
+                let nb = Family::NegativeBinomial(Some(theta));
+                fit = irls(&design, nb, link, Some(&fit.mu))?;
+                iterations += fit.iterations;
+                let next = theta_ml(&design.y, &fit.mu)?;
+                let done = (next - theta).abs() <= 1e-8 * theta;
+                theta = next;
+                if done {
+                    break;
+                }
+            }
+            let family = Family::NegativeBinomial(Some(theta));
+            fit = irls(&design, family, link, Some(&fit.mu))?;
+            fit.iterations += iterations;
+            (family, fit, true)
+        }
+        _ => (family, irls(&design, family, link, None)?, false),
+    };
+
+    let y = &design.y;
+    let df_residual = (n - p) as f64;
+    let dispersion = if family.estimates_dispersion() {
+        y.iter()
+            .zip(&fit.mu)
+            .map(|(y, m)| (y - m).powi(2) / family.variance(*m))
+            .sum::<f64>()
+            / df_residual
+    } else {
+        1.0
+    };
+    let eta = design.x.mul_vec(&fit.beta)?;
+    let w: Vec<f64> = eta
+        .iter()
+        .zip(&fit.mu)
+        .map(|(e, m)| link.mu_eta(*e).powi(2) / family.variance(*m))
+        .collect();
+    let r_inv = weighted_qr(&design.x, &w)?.r.inverse()?;
+    let cov_unscaled = r_inv.matmul(&r_inv.transpose())?;
+
+    // Wald tests use t with an estimated dispersion, as R's summary.glm.
+    let (dist, t_dist): (Box<dyn ContinuousDistribution>, bool) = if family.estimates_dispersion() {
+        (Box::new(StudentT::new(df_residual)?), true)
+    } else {
+        (Box::new(Normal::standard()), false)
+    };
+    let q = dist.quantile(0.5 + confidence / 2.0);
+    let coefficients = fit
+        .beta
+        .iter()
+        .enumerate()
+        .map(|(j, &estimate)| {
+            let std_error = (dispersion * cov_unscaled[(j, j)]).sqrt();
+            let statistic = estimate / std_error;
+            Coefficient {
+                name: design.columns[j].clone(),
+                estimate,
+                std_error,
+                statistic,
+                p_value: Alternative::TwoSided.p_value(dist.as_ref(), statistic),
+                lower: estimate - q * std_error,
+                upper: estimate + q * std_error,
+            }
+        })
+        .collect();
+
+    // The null model has a common mean: the sample mean with an intercept,
+    // otherwise the inverse link of zero.
+    let null_mu = if formula.intercept {
+        stats::mean(y)
+    } else {
+        link.inverse(0.0)
+    };
+    let null_deviance = family.deviance(y, &vec![null_mu; n]);
+    let df_null = (n - usize::from(formula.intercept)) as f64;
+    let lr_test = if df_null > df_residual {
+        let statistic = (null_deviance - fit.deviance) / dispersion;
+        let df = df_null - df_residual;
+        let mut test = TestResult::new(
+            "Likelihood ratio test against the null model",
+            statistic,
+            ChiSquared::new(df)?.sf(statistic),
+            Alternative::Greater,
+        );
+        test.data = formula.to_string();
+        test.parameters.push(("df".to_string(), df));
+        Some(test)
+    } else {
+        None
+    };
+
+    let log_likelihood = family.log_likelihood(y, &fit.mu);
+    let k = (p + usize::from(family.estimates_dispersion()) + usize::from(theta_estimated)) as f64;
+    Ok(GlmResult {
+        formula,
+        family,
+        link,
+        nobs: n,
+        confidence,
+        t_statistics: t_dist,
+        coefficients,
+        dispersion,
+        deviance: fit.deviance,
+        null_deviance,
+        df_residual,
+        df_null,
+        log_likelihood,
+        aic: -2.0 * log_likelihood + 2.0 * k,
+        bic: -2.0 * log_likelihood + (n as f64).ln() * k,
+        iterations: fit.iterations,
+        converged: fit.converged,
+        lr_test,
+        fitted: fit.mu,
+        cov_unscaled,
+        design,
+    })
+}
+
+impl GlmResult {
+    /// Whether a binomial fit has fitted probabilities numerically 0 or 1,
+    /// the sign of (quasi-)complete separation, where the maximum likelihood
+    /// estimates do not exist.
+    pub fn separated(&self) -> bool {
+        let eps = 10.0 * f64::EPSILON;
+        self.family == Family::Binomial && self.fitted.iter().any(|m| *m < eps || *m > 1.0 - eps)
+    }
+
+    /// Exponentiated coefficients and intervals: odds ratios under the
+    /// logit link, rate ratios under the log link.
+    pub fn odds_ratios(&self) -> Vec<OddsRatio> {
+        self.coefficients
+            .iter()
+            .map(|c| OddsRatio {
+                name: c.name.clone(),
+                odds_ratio: c.estimate.exp(),
+                lower: c.lower.exp(),
+                upper: c.upper.exp(),
+            })
+            .collect()
+    }
+
+    /// Joint Wald test that the named coefficients are all zero, e.g. all
+    /// indicator columns of one categorical variable.
+    pub fn wald_test(&self, names: &[&str]) -> Result<TestResult, StatustError> {
+        let idx = names
+            .iter()
+            .map(|name| {
+                self.coefficients
+                    .iter()
+                    .position(|c| c.name == *name)
+                    .ok_or_else(|| StatustError::invalid(format!("no coefficient named {}", name)))
+            })
+            .collect::<Result<Vec<usize>, StatustError>>()?;
+        if idx.is_empty() {
+            return Err(StatustError::invalid(
+                "Wald test needs at least one coefficient".to_string(),
+            ));
+        }
+        let beta: Vec<f64> = idx.iter().map(|&j| self.coefficients[j].estimate).collect();
+        let mut cov = self.cov_unscaled.select(&idx, &idx);
+        for i in 0..idx.len() {
+            for j in 0..idx.len() {
+                cov[(i, j)] *= self.dispersion;
+            }
+        }
+        let solved = cov.inverse()?.mul_vec(&beta)?;
+        let statistic: f64 = beta.iter().zip(&solved).map(|(a, b)| a * b).sum();
+        let df = idx.len() as f64;
+        let mut result = TestResult::new(
+            "Wald test",
+            statistic,
+            ChiSquared::new(df)?.sf(statistic),
+            Alternative::Greater,
+        );
+        result.data = names.join(", ");
+        result.parameters.push(("df".to_string(), df));
+        Ok(result)
+    }
+
+    /// Likelihood-ratio test of this model against a `reduced` model nested
+    /// in it, fitted to the same rows with the same family. With an
+    /// estimated dispersion the deviance difference is scaled by this
+    /// model's dispersion, as in R's `anova(reduced, full, test = "Chisq")`.
+    pub fn lr_test(&self, reduced: &GlmResult) -> Result<TestResult, StatustError> {
+        let same_family = std::mem::discriminant(&self.family)
+            == std::mem::discriminant(&reduced.family)
+            && self.link == reduced.link;
+        if !same_family || self.design.rows != reduced.design.rows {
+            return Err(StatustError::invalid(
+                "models must share family, link and rows".to_string(),
+            ));
+        }
+        let df = reduced.df_residual - self.df_residual;
+        if df <= 0.0 {
+            return Err(StatustError::invalid(
+                "the reduced model must have fewer coefficients".to_string(),
+            ));
+        }
+        let statistic = if self.family.estimates_dispersion() {
+            (reduced.deviance - self.deviance) / self.dispersion
+        } else {
+            2.0 * (self.log_likelihood - reduced.log_likelihood)
+        };
+        let mut result = TestResult::new(
+            "Likelihood ratio test",
+            statistic,
+            ChiSquared::new(df)?.sf(statistic),
+            Alternative::Greater,
+        );
+        result.data = format!("{} vs {}", self.formula, reduced.formula);
+        result.parameters.push(("df".to_string(), df));
+        Ok(result)
+    }
+
+    /// Coefficient of the design column `name`.
+    pub fn coef(&self, name: &str) -> Option<&Coefficient> {
+        self.coefficients.iter().find(|c| c.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    // Reference values from an independent IRLS in 30-digit arithmetic with
+    // mpmath, checked by the score equations vanishing at the estimates;
+    // negative binomial theta from the joint ML fixed point.
+
+    #[test]
+    fn test_logistic_credit() {
+        let df = DataFrame::read("./examples/credit_scoring.csv").unwrap();
+        let formula = "score ~ duration in months + credit amount + age in years + housing";
+        let fit = df.glm(formula, Family::Binomial, None, 0.95).unwrap();
+        assert_eq!(fit.link, Link::Logit);
+        assert_eq!(fit.design.response_level.as_deref(), Some("good"));
+        assert_eq!(fit.nobs, 1000);
+        assert!(fit.converged && !fit.separated());
+        assert_close(
+            fit.coef("(Intercept)").unwrap().estimate,
+            0.597358233025,
+            1e-7,
+        );
+        let c = fit.coef("duration in months").unwrap();
+        assert_close(c.estimate, -0.0335898024535, 1e-9);
+        assert_close(c.std_error, 0.00746994673751, 1e-9);
+        let c = fit.coef("credit amount").unwrap();
+        assert_close(c.estimate, -1.887125531659e-5, 1e-11);
+        assert_close(c.std_error, 3.128132635791e-5, 1e-11);
+        let c = fit.coef("housing[own]").unwrap();
+        assert_close(c.estimate, 0.569022012992, 1e-7);
+        assert_close(c.std_error, 0.235104605148, 1e-7);
+        assert_close(c.statistic, 2.4202929272, 1e-6);
+        assert_close(c.p_value, 0.01550800897993, 1e-8);
+        assert_close(fit.deviance, 1154.468257608, 1e-7);
+        assert_close(fit.null_deviance, 1221.72860411, 1e-7);
+        assert_eq!(fit.dispersion, 1.0);
+        assert_close(fit.aic, 1166.468257608, 1e-7);
+        assert_close(fit.bic, 1195.914789282, 1e-7);
+        assert_close(
+            fit.lr_test.as_ref().unwrap().statistic,
+            67.26034650132,
+            1e-7,
+        );
+
+        let own = &fit.odds_ratios()[4];
+        assert_eq!(own.name, "housing[own]");
+        assert_close(own.odds_ratio, 1.766538554592, 1e-7);
+        assert_close(own.lower, 1.114298940559, 1e-7);
+        assert_close(own.upper, 2.800557688131, 1e-7);
+
+        let wald = fit.wald_test(&["housing[own]", "housing[rent]"]).unwrap();
+        assert_close(wald.statistic, 14.03638407441, 1e-6);
+        assert_close(wald.p_value, 0.0008954429578031, 1e-10);
+        assert!(fit.wald_test(&["housing[none]"]).is_err());
+
+        let reduced = df
+            .glm(
+                "score ~ duration in months + credit amount + age in years",
+                Family::Binomial,
+                None,
+                0.95,
+            )
+            .unwrap();
+        assert_close(reduced.deviance, 1168.317333908, 1e-7);
+        let lr = fit.lr_test(&reduced).unwrap();
+        assert_close(lr.statistic, 13.84907629975, 1e-6);
+        assert_eq!(lr.parameters[0].1, 2.0);
+        assert!(reduced.lr_test(&fit).is_err());
+
+        let text = fit.to_string();
+        assert!(text.contains("score = good") && text.contains("Odds ratio"));
+    }
+
+    #[test]
+    fn test_probit_and_counts() {
+        let df = DataFrame::read("./examples/credit_scoring.csv").unwrap();
+        let fit = df
+            .glm(
+                "score ~ duration in months + age in years",
+                Family::Binomial,
+                Some(Link::Probit),
+                0.95,
+            )
+            .unwrap();
+        assert_close(
+            fit.coef("(Intercept)").unwrap().estimate,
+            0.645078511823,
+            1e-7,
+        );
+        let c = fit.coef("age in years").unwrap();
+        assert_close(c.estimate, 0.0106849923912, 1e-9);
+        assert_close(c.std_error, 0.00388788311957, 1e-9);
+        assert_close(fit.deviance, 1168.991911901, 1e-7);
+
+        let fit = df
+            .glm(
+                "number of existing credits at this bank ~ age in years + housing",
+                Family::Poisson,
+                None,
+                0.95,
+            )
+            .unwrap();
+        assert!(!fit.t_statistics);
+        let c = fit.coef("age in years").unwrap();
+        assert_close(c.estimate, 0.00526914543894, 1e-9);
+        assert_close(c.std_error, 0.00238968862895, 1e-9);
+        assert_close(fit.deviance, 207.9353035634, 1e-7);
+        assert_close(fit.null_deviance, 213.5132226717, 1e-7);
+        assert_close(fit.log_likelihood, -1223.832731861, 1e-7);
+        assert_close(fit.aic, 2455.665463721, 1e-7);
+
+        let fit = df
+            .glm(
+                "duration in months ~ age in years + housing",
+                Family::NegativeBinomial(None),
+                None,
+                0.95,
+            )
+            .unwrap();
+        match fit.family {
+            Family::NegativeBinomial(Some(theta)) => assert_close(theta, 4.041513727876, 1e-6),
+            other => panic!("unexpected family {}", other),
+        }
+        let c = fit.coef("housing[rent]").unwrap();
+        assert_close(c.estimate, -0.420809704598, 1e-7);
+        assert_close(c.std_error, 0.0690067223064, 1e-8);
+        assert_close(fit.deviance, 1025.722155553, 1e-6);
+        assert_close(fit.log_likelihood, -3748.047452131, 1e-6);
+        assert_close(fit.aic, 7506.094904263, 1e-6);
+    }
+
+    #[test]
+    fn test_gamma_and_gaussian() {
+        let df = DataFrame::read("./examples/credit_scoring.csv").unwrap();
+        let fit = df
+            .glm(
+                "credit amount ~ duration in months + age in years",
+                Family::Gamma,
+                Some(Link::Log),
+                0.95,
+            )
+            .unwrap();
+        assert!(fit.t_statistics);
+        let c = fit.coef("duration in months").unwrap();
+        assert_close(c.estimate, 0.0387746700325, 1e-9);
+        assert_close(c.std_error, 0.00189887377536, 1e-9);
+        assert_close(
+            fit.coef("(Intercept)").unwrap().estimate,
+            6.990446135798,
+            1e-7,
+        );
+        assert_close(fit.dispersion, 0.5231177043135, 1e-8);
+        assert_close(fit.deviance, 371.6012796796, 1e-7);
+        assert_close(fit.null_deviance, 608.4773093333, 1e-7);
+        assert_close(fit.aic, 17494.98445848, 1e-6);
+        assert_close(
+            fit.lr_test.as_ref().unwrap().statistic,
+            452.8159297619,
+            1e-5,
+        );
+
+        // The Gaussian family with identity link reproduces OLS.
+        let iris = DataFrame::read("./examples/iris.csv").unwrap();
+        let formula = "sepal.length ~ sepal.width + variety";
+        let glm_fit = iris.glm(formula, Family::Gaussian, None, 0.95).unwrap();
+        let ols_fit = iris.ols(formula, 0.95).unwrap();
+        for (a, b) in glm_fit.coefficients.iter().zip(&ols_fit.coefficients) {
+            assert_close(a.estimate, b.estimate, 1e-9);
+            assert_close(a.std_error, b.std_error, 1e-9);
+            assert_close(a.p_value, b.p_value, 1e-12);
+        }
+        assert_close(glm_fit.aic, ols_fit.aic, 1e-8);
+        assert_close(glm_fit.dispersion.sqrt(), ols_fit.sigma, 1e-10);
+    }
+
+    #[test]
+    fn test_separation() {
+        // y is 1 exactly when x >= 20, so the slope diverges.
+        let x: Vec<DataType> = (0..40).map(DataType::Int).collect();
+        let y: Vec<DataType> = (0..40).map(|i| DataType::Int(i32::from(i >= 20))).collect();
+        let df =
+            DataFrame::from_columns(vec!["y".to_string(), "x".to_string()], vec![y, x]).unwrap();
+        let fit = df.glm("y ~ x", Family::Binomial, None, 0.95).unwrap();
+        assert!(!fit.converged);
+        assert!(fit.separated());
+        assert!(fit.coefficients[1].estimate > 1.0);
+        assert!(fit.deviance < 1e-6);
+        let text = fit.to_string();
+        assert!(text.contains("did not converge") && text.contains("separated"));
+    }
+
+    #[test]
+    fn test_invalid_models() {
+        let df = DataFrame::read("./examples/credit_scoring.csv").unwrap();
+        let binomial = |formula, link| df.glm(formula, Family::Binomial, link, 0.95);
+        assert!(binomial("score ~ age in years", Some(Link::Inverse)).is_err());
+        assert!(binomial("housing ~ age in years", None).is_err());
+        assert!(binomial("duration in months ~ age in years", None).is_err());
+        assert!(df
+            .glm("score ~ age in years", Family::Poisson, None, 0.95)
+            .is_err());
+        assert!(df
+            .glm(
+                "duration in months ~ age in years",
+                Family::NegativeBinomial(Some(-1.0)),
+                None,
+                0.95
+            )
+            .is_err());
+        assert!(binomial("score ~ age in years", None)
+            .map(|fit| fit.confidence)
+            .is_ok());
+        assert!(df
+            .glm("score ~ age in years", Family::Binomial, None, 1.5)
+            .is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::distributions::{ChiSquared, ContinuousDistribution};
+use crate::special::ndtri;
+use crate::*;
+
+/// Tests of the null hypothesis that all groups have the same variance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VarianceTest {
+    /// Levene's test on absolute deviations from the group means.
+    Levene,
+    /// Brown-Forsythe variant of Levene's test, using group medians.
+    BrownForsythe,
+    Bartlett,
+    FlignerKilleen,
+}
+
+impl VarianceTest {
+    pub fn run(&self, groups: &[Vec<f64>]) -> Result<TestResult, StatustError> {
+        match self {
+            VarianceTest::Levene => levene(groups, false),
+            VarianceTest::BrownForsythe => levene(groups, true),
+            VarianceTest::Bartlett => bartlett(groups),
+            VarianceTest::FlignerKilleen => fligner_killeen(groups),
+        }
+    }
+}
+
+impl DataFrame {
+    /// Runs `test` on the numeric column `value` split by the levels of
+    /// `group`. Rows with a null in either column are skipped.
+    pub fn variance_test(
+        &self,
+        value: &str,
+        group: &str,
+        test: VarianceTest,
+    ) -> Result<TestResult, StatustError> {
+        let (_, groups) = self.grouped_f64(value, group)?;
+        let mut result = test.run(&groups)?;
+        result.data = format!("{} by {}", value, group);
+        Ok(result)
+    }
+}
+
+fn check_groups(groups: &[Vec<f64>], min_len: usize, method: &str) -> Result<(), StatustError> {
+    if groups.len() < 2 {
+        return Err(StatustError::invalid(format!(
+            "{} needs at least two groups, got {}",
+            method,
+            groups.len()
+        )));
+    }
+    if groups.iter().any(|g| g.len() < min_len) {
+        return Err(StatustError::invalid(format!(
+            "{} needs at least {} observations per group",
+            method, min_len
+        )));
+    }
+    if groups.iter().flatten().any(|v| !v.is_finite()) {
+        return Err(StatustError::invalid(format!(
+            "{} needs finite values",
+            method
+        )));
+    }
+    Ok(())
+}
+
+/// Levene's test: a one-way ANOVA on the absolute deviations from each
+/// group's mean, or from its median when `median` is set (Brown-Forsythe).
+pub fn levene(groups: &[Vec<f64>], median: bool) -> Result<TestResult, StatustError> {
+    let method = if median {
+        "Brown-Forsythe test for homogeneity of variance"
+    } else {
+        "Levene's test for homogeneity of variance"
+    };
+    check_groups(groups, 2, method)?;
+    let deviations: Vec<Vec<f64>> = groups
+        .iter()
+        .map(|g| {
+            let center = if median {
+                stats::median(g)
+            } else {
+                stats::mean(g)
+            };
+            g.iter().map(|v| (v - center).abs()).collect()
+        })
+        .collect();
+    let table = one_way_anova(&deviations)?;
+    let (effect, residual) = (&table.rows[0], &table.rows[1]);
+    if effect.f.is_nan() {
+        return Err(StatustError::invalid("all deviations are zero".to_string()));
+    }
+    let mut result = TestResult::new(method, effect.f, effect.p_value, Alternative::Greater);
+    result.parameters = vec![
+        ("num df".to_string(), effect.df),
+        ("denom df".to_string(), residual.df),
+    ];
+    Ok(result)
+}
+
+/// Bartlett's test, which is sensitive to departures from normality.
+pub fn bartlett(groups: &[Vec<f64>]) -> Result<TestResult, StatustError> {
+    const METHOD: &str = "Bartlett test of homogeneity of variances";
+    check_groups(groups, 2, METHOD)?;
+    let k = groups.len() as f64;
+    let dfs: Vec<f64> = groups.iter().map(|g| g.len() as f64 - 1.0).collect();
+    let vars: Vec<f64> = groups.iter().map(|g| stats::variance(g)).collect();
+    if vars.iter().any(|v| *v <= 0.0) {
+        return Err(StatustError::invalid(
+            "a group has zero variance".to_string(),
+        ));
+    }
+    let df_total: f64 = dfs.iter().sum();
+    let pooled = dfs.iter().zip(&vars).map(|(d, v)| d * v).sum::<f64>() / df_total;
+    let statistic = (df_total * pooled.ln()
+        - dfs.iter().zip(&vars).map(|(d, v)| d * v.ln()).sum::<f64>())
+        / (1.0 + (dfs.iter().map(|d| 1.0 / d).sum::<f64>() - 1.0 / df_total) / (3.0 * (k - 1.0)));
+    let mut result = TestResult::new(
+        METHOD,
+        statistic,
+        ChiSquared::new(k - 1.0)?.sf(statistic),
+        Alternative::Greater,
+    );
+    result.parameters.push(("df".to_string(), k - 1.0));
+    Ok(result)
+}
+
+/// Fligner-Killeen median test, a rank-based test robust to
+/// non-normality, as in R's `fligner.test`.
+pub fn fligner_killeen(groups: &[Vec<f64>]) -> Result<TestResult, StatustError> {
+    const METHOD: &str = "Fligner-Killeen test of homogeneity of variances";
+    check_groups(groups, 1, METHOD)?;
+    let centered: Vec<f64> = groups
+        .iter()
+        .flat_map(|g| {
+            let m = stats::median(g);
+            g.iter().map(move |v| (v - m).abs())
+        })
+        .collect();
+    let n = centered.len() as f64;
+    let scores: Vec<f64> = stats::rank(&snap_ties(&centered, groups))
+        .iter()
+        .map(|r| ndtri((1.0 + r / (n + 1.0)) / 2.0))
+        .collect();
+    let var = stats::variance(&scores);
+    if var.is_nan() || var <= 0.0 {
+        return Err(StatustError::invalid(
+            "all scores are identical".to_string(),
+        ));
+    }
+    let mut start = 0;
+    let mut statistic = 0.0;
+    for g in groups {
+        let sum: f64 = scores[start..start + g.len()].iter().sum();
+        statistic += sum * sum / g.len() as f64;
+        start += g.len();
+    }
+    statistic = (statistic - n * stats::mean(&scores).powi(2)) / var;
+    let df = (groups.len() - 1) as f64;
+    let mut result = TestResult::new(
+        METHOD,
+        statistic,
+        ChiSquared::new(df)?.sf(statistic),
+        Alternative::Greater,
+    );
+    result.parameters.push(("df".to_string(), df));
+    Ok(result)
+}
+
+/// Snaps absolute deviations that differ only by rounding noise onto a common
+/// value so they rank as ties. Data read from `Float` columns is f32, and
+/// deviations such as |5.1 - 5.0| and |4.9 - 5.0| are then unequal in f64.
+fn snap_ties(deviations: &[f64], groups: &[Vec<f64>]) -> Vec<f64> {
+    let scale = groups
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |acc, v| acc.max(v.abs()));
+    let tol = 4.0 * f32::EPSILON as f64 * scale;
+    let mut order: Vec<usize> = (0..deviations.len()).collect();
+    order.sort_by(|&a, &b| deviations[a].total_cmp(&deviations[b]));
+    let mut snapped = deviations.to_vec();
+    let mut anchor = f64::NEG_INFINITY;
+    for &i in &order {
+        if deviations[i] - anchor <= tol {
+            snapped[i] = anchor;
+        } else {
+            anchor = deviations[i];
+        }
+    }
+    snapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    // Reference values from R's car::leveneTest and bartlett.test on the iris
+    // data. The Fligner-Killeen values follow R's fligner.test formula with
+    // tied deviations ranked as ties; fligner.test itself reports 11.617981
+    // because rounding in |x - median| splits some of them.
+
+    #[test]
+    fn test_iris() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let run = |test| df.variance_test("sepal.length", "variety", test).unwrap();
+
+        let levene = run(VarianceTest::Levene);
+        assert_close(levene.statistic, 7.3810917, 1e-5);
+        assert_close(levene.p_value, 0.00088178878, 1e-8);
+        assert_eq!(levene.parameters[1].1, 147.0);
+
+        let bf = run(VarianceTest::BrownForsythe);
+        assert_close(bf.statistic, 6.3527200, 1e-5);
+        assert_close(bf.p_value, 0.0022585278, 1e-8);
+
+        let bartlett = run(VarianceTest::Bartlett);
+        assert_close(bartlett.statistic, 16.005702, 1e-5);
+        assert_close(bartlett.p_value, 0.00033450761, 1e-8);
+
+        let fligner = run(VarianceTest::FlignerKilleen);
+        assert_close(fligner.statistic, 11.672505447448, 1e-8);
+        assert_close(fligner.p_value, 0.0029197633114, 1e-11);
+        assert_eq!(fligner.data, "sepal.length by variety");
+    }
+
+    #[test]
+    fn test_invalid_groups() {
+        assert!(levene(&[vec![1.0, 2.0]], false).is_err());
+        assert!(bartlett(&[vec![1.0, 1.0], vec![1.0, 2.0]]).is_err());
+        assert!(fligner_killeen(&[vec![1.0], vec![f64::NAN]]).is_err());
+    }
+}
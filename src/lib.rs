@@ -1,6 +1,37 @@
+mod adjust;
+mod anova;
+mod contingency;
+mod correlation;
 mod dataframe;
 mod datatype;
+mod diagnostics;
+pub mod distributions;
+mod duplicates;
+mod error;
+mod expr;
 mod file_writer;
+mod formula;
+mod glm;
+mod homogeneity;
+pub mod linalg;
+mod missing;
+mod nonparametric;
+mod normality;
+mod ols;
+mod penalized;
 mod results;
+mod rng;
+mod sampling;
+mod simulate;
+pub mod special;
+pub mod stats;
+#[cfg(test)]
+mod test_util;
+mod ttest;
 
-pub use {dataframe::*, datatype::*, file_writer::*, results::*};
+pub use {
+    adjust::*, anova::*, contingency::*, correlation::*, dataframe::*, datatype::*, diagnostics::*,
+    duplicates::*, error::*, expr::*, file_writer::*, formula::*, glm::*, homogeneity::*,
+    missing::*, nonparametric::*, normality::*, ols::*, penalized::*, results::*, rng::*,
+    sampling::*, simulate::*, ttest::*,
+};
@@ -0,0 +1,1016 @@
+//! Dense linear algebra on row-major `f64` matrices.
+
+use crate::*;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix {
+    nrows: usize,
+    ncols: usize,
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    /// Creates a matrix from row-major data.
+    pub fn new(nrows: usize, ncols: usize, data: Vec<f64>) -> Result<Matrix, StatustError> {
+        if data.len() != nrows * ncols {
+            return Err(StatustError::LengthMismatch {
+                expected: nrows * ncols,
+                found: data.len(),
+            });
+        }
+        Ok(Matrix { nrows, ncols, data })
+    }
+
+    pub fn zeros(nrows: usize, ncols: usize) -> Matrix {
+        Matrix {
+            nrows,
+            ncols,
+            data: vec![0.0; nrows * ncols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Matrix {
+        let mut m = Matrix::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = 1.0;
+        }
+        m
+    }
+
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Matrix, StatustError> {
+        let ncols = rows.first().map_or(0, |r| r.len());
+        let mut data = Vec::with_capacity(rows.len() * ncols);
+        for row in rows {
+            if row.len() != ncols {
+                return Err(StatustError::LengthMismatch {
+                    expected: ncols,
+                    found: row.len(),
+                });
+            }
+            data.extend_from_slice(row);
+        }
+        Ok(Matrix {
+            nrows: rows.len(),
+            ncols,
+            data,
+        })
+    }
+
+    pub fn from_diag(diag: &[f64]) -> Matrix {
+        let mut m = Matrix::zeros(diag.len(), diag.len());
+        for (i, d) in diag.iter().enumerate() {
+            m[(i, i)] = *d;
+        }
+        m
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.nrows == self.ncols
+    }
+
+    /// Row-major view of the underlying data.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.data
+    }
+
+    pub fn row(&self, i: usize) -> &[f64] {
+        &self.data[i * self.ncols..(i + 1) * self.ncols]
+    }
+
+    pub fn col(&self, j: usize) -> Vec<f64> {
+        (0..self.nrows).map(|i| self[(i, j)]).collect()
+    }
+
+    pub fn diag(&self) -> Vec<f64> {
+        (0..self.nrows.min(self.ncols))
+            .map(|i| self[(i, i)])
+            .collect()
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let mut t = Matrix::zeros(self.ncols, self.nrows);
+        for i in 0..self.nrows {
+            for j in 0..self.ncols {
+                t[(j, i)] = self[(i, j)];
+            }
+        }
+        t
+    }
+
+    pub fn matmul(&self, other: &Matrix) -> Result<Matrix, StatustError> {
+        if self.ncols != other.nrows {
+            return Err(StatustError::LengthMismatch {
+                expected: self.ncols,
+                found: other.nrows,
+            });
+        }
+        let mut out = Matrix::zeros(self.nrows, other.ncols);
+        for i in 0..self.nrows {
+            for k in 0..self.ncols {
+                let a = self[(i, k)];
+                if a == 0.0 {
+                    continue;
+                }
+                for j in 0..other.ncols {
+                    out[(i, j)] += a * other[(k, j)];
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn mul_vec(&self, v: &[f64]) -> Result<Vec<f64>, StatustError> {
+        if self.ncols != v.len() {
+            return Err(StatustError::LengthMismatch {
+                expected: self.ncols,
+                found: v.len(),
+            });
+        }
+        Ok((0..self.nrows)
+            .map(|i| self.row(i).iter().zip(v).map(|(a, b)| a * b).sum())
+            .collect())
+    }
+
+    /// Cholesky factor `L` (lower triangular) with `self = L * L^T`.
+    ///
+    /// Fails if the matrix is not square, not symmetric or not positive
+    /// definite.
+    pub fn cholesky(&self) -> Result<Matrix, StatustError> {
+        if !self.is_square() {
+            return Err(StatustError::invalid(
+                "Cholesky decomposition requires a square matrix".to_string(),
+            ));
+        }
+        let n = self.nrows;
+        let scale = self.data.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+        for i in 0..n {
+            for j in 0..i {
+                if (self[(i, j)] - self[(j, i)]).abs() > 1e-10 * scale.max(1.0) {
+                    return Err(StatustError::invalid(
+                        "Cholesky decomposition requires a symmetric matrix".to_string(),
+                    ));
+                }
+            }
+        }
+        let mut l = Matrix::zeros(n, n);
+        for j in 0..n {
+            let mut d = self[(j, j)];
+            for k in 0..j {
+                d -= l[(j, k)] * l[(j, k)];
+            }
+            if d <= 0.0 || !d.is_finite() {
+                return Err(StatustError::invalid(
+                    "matrix is not positive definite".to_string(),
+                ));
+            }
+            let d = d.sqrt();
+            l[(j, j)] = d;
+            for i in j + 1..n {
+                let mut s = self[(i, j)];
+                for k in 0..j {
+                    s -= l[(i, k)] * l[(j, k)];
+                }
+                l[(i, j)] = s / d;
+            }
+        }
+        Ok(l)
+    }
+
+    /// Inverse by Gauss-Jordan elimination with partial pivoting. Fails if
+    /// the matrix is not square, has non-finite entries or is numerically
+    /// singular.
+    pub fn inverse(&self) -> Result<Matrix, StatustError> {
+        if !self.is_square() {
+            return Err(StatustError::invalid(
+                "inverse requires a square matrix".to_string(),
+            ));
+        }
+        if self.data.iter().any(|v| !v.is_finite()) {
+            return Err(StatustError::invalid(
+                "inverse requires finite entries".to_string(),
+            ));
+        }
+        let n = self.nrows;
+        let scale = self.data.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+        let mut a = self.clone();
+        let mut inv = Matrix::identity(n);
+        for col in 0..n {
+            let pivot = (col..n)
+                .max_by(|&i, &j| a[(i, col)].abs().total_cmp(&a[(j, col)].abs()))
+                .unwrap();
+            if a[(pivot, col)].abs() <= n as f64 * f64::EPSILON * scale {
+                return Err(StatustError::invalid("matrix is singular".to_string()));
+            }
+            a.swap_rows(col, pivot);
+            inv.swap_rows(col, pivot);
+            let p = a[(col, col)];
+            for j in 0..n {
+                a[(col, j)] /= p;
+                inv[(col, j)] /= p;
+            }
+            for i in 0..n {
+                let factor = a[(i, col)];
+                if i == col || factor == 0.0 {
+                    continue;
+                }
+                for j in 0..n {
+                    a[(i, j)] -= factor * a[(col, j)];
+                    inv[(i, j)] -= factor * inv[(col, j)];
+                }
+            }
+        }
+        Ok(inv)
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for j in 0..self.ncols {
+                self.data.swap(a * self.ncols + j, b * self.ncols + j);
+            }
+        }
+    }
+
+    /// Submatrix with the given rows and columns, in that order.
+    pub fn select(&self, rows: &[usize], cols: &[usize]) -> Matrix {
+        let mut out = Matrix::zeros(rows.len(), cols.len());
+        for (i, &r) in rows.iter().enumerate() {
+            for (j, &c) in cols.iter().enumerate() {
+                out[(i, j)] = self[(r, c)];
+            }
+        }
+        out
+    }
+
+    /// Solves `self * x = b` for lower triangular `self` by forward
+    /// substitution.
+    pub fn solve_lower(&self, b: &[f64]) -> Result<Vec<f64>, StatustError> {
+        self.check_triangular_system(b)?;
+        let mut x = vec![0.0; b.len()];
+        for i in 0..self.nrows {
+            let s: f64 = (0..i).map(|k| self[(i, k)] * x[k]).sum();
+            x[i] = (b[i] - s) / self[(i, i)];
+        }
+        Ok(x)
+    }
+
+    /// Solves `self * x = b` for upper triangular `self` by back
+    /// substitution.
+    pub fn solve_upper(&self, b: &[f64]) -> Result<Vec<f64>, StatustError> {
+        self.check_triangular_system(b)?;
+        let n = self.nrows;
+        let mut x = vec![0.0; n];
+        for i in (0..n).rev() {
+            let s: f64 = (i + 1..n).map(|k| self[(i, k)] * x[k]).sum();
+            x[i] = (b[i] - s) / self[(i, i)];
+        }
+        Ok(x)
+    }
+
+    fn check_triangular_system(&self, b: &[f64]) -> Result<(), StatustError> {
+        if !self.is_square() {
+            return Err(StatustError::invalid(
+                "triangular solve requires a square matrix".to_string(),
+            ));
+        }
+        if b.len() != self.nrows {
+            return Err(StatustError::LengthMismatch {
+                expected: self.nrows,
+                found: b.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Thin QR decomposition `A = Q * R` of an `m x n` matrix with `m >= n`:
+/// `Q` is `m x n` with orthonormal columns and `R` is `n x n` upper
+/// triangular.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Qr {
+    pub q: Matrix,
+    pub r: Matrix,
+}
+
+impl Matrix {
+    /// QR decomposition by Householder reflections. The signs are chosen
+    /// for stability, so the diagonal of `R` may be negative.
+    pub fn qr(&self) -> Result<Qr, StatustError> {
+        let (m, n) = (self.nrows, self.ncols);
+        if m < n {
+            return Err(StatustError::invalid(format!(
+                "QR decomposition needs at least as many rows as columns, got {}x{}",
+                m, n
+            )));
+        }
+        let mut a = self.clone();
+        let mut reflectors: Vec<Vec<f64>> = Vec::with_capacity(n);
+        for j in 0..n {
+            let norm = (j..m).map(|i| a[(i, j)].powi(2)).sum::<f64>().sqrt();
+            let mut v: Vec<f64> = (j..m).map(|i| a[(i, j)]).collect();
+            v[0] += if v[0] >= 0.0 { norm } else { -norm };
+            let v_norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if v_norm > 0.0 {
+                v.iter_mut().for_each(|x| *x /= v_norm);
+                reflect(&mut a, &v, j);
+            }
+            reflectors.push(v);
+        }
+        let mut q = Matrix::zeros(m, n);
+        for j in 0..n {
+            q[(j, j)] = 1.0;
+        }
+        for (j, v) in reflectors.iter().enumerate().rev() {
+            reflect(&mut q, v, j);
+        }
+        let mut r = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in i..n {
+                r[(i, j)] = a[(i, j)];
+            }
+        }
+        Ok(Qr { q, r })
+    }
+}
+
+/// Applies the reflection `I - 2 v v^T` to the rows `offset..` of `a`.
+fn reflect(a: &mut Matrix, v: &[f64], offset: usize) {
+    for k in 0..a.ncols {
+        let dot: f64 = v
+            .iter()
+            .enumerate()
+            .map(|(i, vi)| vi * a[(offset + i, k)])
+            .sum();
+        for (i, vi) in v.iter().enumerate() {
+            a[(offset + i, k)] -= 2.0 * vi * dot;
+        }
+    }
+}
+
+impl Qr {
+    /// Least squares solution of `A * x = b`. Fails if `R` has a zero on
+    /// its diagonal, i.e. `A` does not have full column rank.
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, StatustError> {
+        if b.len() != self.q.nrows {
+            return Err(StatustError::LengthMismatch {
+                expected: self.q.nrows,
+                found: b.len(),
+            });
+        }
+        if self.r.diag().contains(&0.0) {
+            return Err(StatustError::invalid(
+                "matrix does not have full column rank".to_string(),
+            ));
+        }
+        let qtb = self.q.transpose().mul_vec(b)?;
+        self.r.solve_upper(&qtb)
+    }
+}
+
+/// LU decomposition with partial pivoting, `P * A = L * U`: `L` is unit
+/// lower triangular, `U` upper triangular and row `i` of `P * A` is row
+/// `perm[i]` of `A`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lu {
+    pub l: Matrix,
+    pub u: Matrix,
+    pub perm: Vec<usize>,
+}
+
+impl Matrix {
+    /// LU decomposition of a square matrix by Gaussian elimination with
+    /// partial pivoting. Singular matrices decompose with a zero on the
+    /// diagonal of `U`.
+    pub fn lu(&self) -> Result<Lu, StatustError> {
+        if !self.is_square() {
+            return Err(StatustError::invalid(
+                "LU decomposition requires a square matrix".to_string(),
+            ));
+        }
+        let n = self.nrows;
+        let mut u = self.clone();
+        let mut l = Matrix::identity(n);
+        let mut perm: Vec<usize> = (0..n).collect();
+        for col in 0..n {
+            let pivot = (col..n)
+                .max_by(|&i, &j| u[(i, col)].abs().total_cmp(&u[(j, col)].abs()))
+                .unwrap();
+            if pivot != col {
+                u.swap_rows(col, pivot);
+                perm.swap(col, pivot);
+                for k in 0..col {
+                    let t = l[(col, k)];
+                    l[(col, k)] = l[(pivot, k)];
+                    l[(pivot, k)] = t;
+                }
+            }
+            let p = u[(col, col)];
+            if p == 0.0 {
+                continue;
+            }
+            for i in col + 1..n {
+                let factor = u[(i, col)] / p;
+                l[(i, col)] = factor;
+                u[(i, col)] = 0.0;
+                for j in col + 1..n {
+                    u[(i, j)] -= factor * u[(col, j)];
+                }
+            }
+        }
+        Ok(Lu { l, u, perm })
+    }
+}
+
+impl Lu {
+    /// Solves `A * x = b`. Fails if `A` is numerically singular.
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, StatustError> {
+        let n = self.perm.len();
+        if b.len() != n {
+            return Err(StatustError::LengthMismatch {
+                expected: n,
+                found: b.len(),
+            });
+        }
+        let scale = self.u.data.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+        if self
+            .u
+            .diag()
+            .iter()
+            .any(|d| d.abs() <= n as f64 * f64::EPSILON * scale)
+        {
+            return Err(StatustError::invalid("matrix is singular".to_string()));
+        }
+        let pb: Vec<f64> = self.perm.iter().map(|&i| b[i]).collect();
+        let y = self.l.solve_lower(&pb)?;
+        self.u.solve_upper(&y)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        // The sign of the permutation from the parity of its cycles.
+        let mut visited = vec![false; self.perm.len()];
+        let mut sign = 1.0;
+        for start in 0..self.perm.len() {
+            let mut len = 0;
+            let mut i = start;
+            while !visited[i] {
+                visited[i] = true;
+                i = self.perm[i];
+                len += 1;
+            }
+            if len % 2 == 0 && len > 0 {
+                sign = -sign;
+            }
+        }
+        sign * self.u.diag().iter().product::<f64>()
+    }
+}
+
+/// Eigendecomposition `A = V * diag(values) * V^T` of a symmetric matrix,
+/// with eigenvalues in decreasing order and orthonormal eigenvectors in the
+/// columns of `vectors`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymmetricEigen {
+    pub values: Vec<f64>,
+    pub vectors: Matrix,
+}
+
+const MAX_JACOBI_SWEEPS: usize = 100;
+
+impl Matrix {
+    /// Eigendecomposition of a symmetric matrix by cyclic Jacobi rotations,
+    /// accurate to working precision for small dense matrices. Each
+    /// eigenvector is signed so that its largest component is positive.
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen, StatustError> {
+        if !self.is_square() {
+            return Err(StatustError::invalid(
+                "eigendecomposition requires a square matrix".to_string(),
+            ));
+        }
+        let n = self.nrows;
+        let scale = self.data.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+        for i in 0..n {
+            for j in 0..i {
+                if (self[(i, j)] - self[(j, i)]).abs() > 1e-10 * scale.max(1.0) {
+                    return Err(StatustError::invalid(
+                        "eigendecomposition requires a symmetric matrix".to_string(),
+                    ));
+                }
+            }
+        }
+        let norm = self.data.iter().map(|v| v * v).sum::<f64>().sqrt();
+        let mut a = self.clone();
+        let mut v = Matrix::identity(n);
+        let mut converged = false;
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            // Rounding leaves off-diagonal noise of order `eps * norm` in
+            // every entry, so the off-diagonal mass is only expected to fall
+            // to about `n * eps * norm`; below that the eigenvalues are
+            // accurate to working precision.
+            let off: f64 = (0..n)
+                .flat_map(|i| (0..i).map(move |j| (i, j)))
+                .map(|(i, j)| a[(i, j)].powi(2))
+                .sum();
+            if off.sqrt() <= n as f64 * f64::EPSILON * norm {
+                converged = true;
+                break;
+            }
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    // Skip entries negligible next to their diagonal, as in
+                    // the one-sided Jacobi SVD below.
+                    if a[(p, q)].abs() <= f64::EPSILON * (a[(p, p)] * a[(q, q)]).abs().sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+                    // Rotation that zeroes a[p][q], in the stable form of
+                    // Golub and Van Loan (algorithm 8.4.1).
+                    let tau = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                    let t = tau.signum() / (tau.abs() + (1.0 + tau * tau).sqrt());
+                    let c = 1.0 / (1.0 + t * t).sqrt();
+                    let s = t * c;
+                    for k in 0..n {
+                        let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                        a[(k, p)] = c * akp - s * akq;
+                        a[(k, q)] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+                        a[(p, k)] = c * apk - s * aqk;
+                        a[(q, k)] = s * apk + c * aqk;
+                    }
+                    for k in 0..n {
+                        let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                        v[(k, p)] = c * vkp - s * vkq;
+                        v[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+            if !rotated {
+                converged = true;
+                break;
+            }
+        }
+        if !converged {
+            return Err(StatustError::invalid(format!(
+                "Jacobi eigenvalue iteration did not converge in {} sweeps",
+                MAX_JACOBI_SWEEPS
+            )));
+        }
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| a[(j, j)].total_cmp(&a[(i, i)]));
+        let rows: Vec<usize> = (0..n).collect();
+        let mut vectors = v.select(&rows, &order);
+        normalize_signs(&mut vectors);
+        Ok(SymmetricEigen {
+            values: order.iter().map(|&i| a[(i, i)]).collect(),
+            vectors,
+        })
+    }
+}
+
+/// Flips columns so that the component of largest magnitude is positive.
+fn normalize_signs(m: &mut Matrix) {
+    for j in 0..m.ncols {
+        let largest = (0..m.nrows).map(|i| m[(i, j)]).fold(0.0f64, |best, x| {
+            if x.abs() > best.abs() {
+                x
+            } else {
+                best
+            }
+        });
+        if largest < 0.0 {
+            for i in 0..m.nrows {
+                m[(i, j)] = -m[(i, j)];
+            }
+        }
+    }
+}
+
+/// Thin singular value decomposition `A = U * diag(s) * V^T` of an
+/// `m x n` matrix: with `k = min(m, n)`, `U` is `m x k` and `V` is `n x k`,
+/// both with orthonormal columns, and `s` is decreasing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Svd {
+    pub u: Matrix,
+    pub s: Vec<f64>,
+    pub v: Matrix,
+}
+
+impl Matrix {
+    /// Singular value decomposition by one-sided Jacobi rotations, which
+    /// computes small singular values to high relative accuracy.
+    pub fn svd(&self) -> Result<Svd, StatustError> {
+        if self.nrows < self.ncols {
+            let Svd { u, s, v } = self.transpose().svd()?;
+            return Ok(Svd { u: v, s, v: u });
+        }
+        let (m, n) = (self.nrows, self.ncols);
+        if n == 0 {
+            return Ok(Svd {
+                u: Matrix::zeros(m, 0),
+                s: Vec::new(),
+                v: Matrix::zeros(0, 0),
+            });
+        }
+        // Columns of `w` are rotated until mutually orthogonal; `w = A * V`.
+        let mut w = self.clone();
+        let mut v = Matrix::identity(n);
+        let mut converged = false;
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let (mut alpha, mut beta, mut gamma) = (0.0, 0.0, 0.0);
+                    for i in 0..m {
+                        alpha += w[(i, p)] * w[(i, p)];
+                        beta += w[(i, q)] * w[(i, q)];
+                        gamma += w[(i, p)] * w[(i, q)];
+                    }
+                    if gamma == 0.0 || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+                    let zeta = (beta - alpha) / (2.0 * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                    let c = 1.0 / (1.0 + t * t).sqrt();
+                    let s = c * t;
+                    for i in 0..m {
+                        let (wp, wq) = (w[(i, p)], w[(i, q)]);
+                        w[(i, p)] = c * wp - s * wq;
+                        w[(i, q)] = s * wp + c * wq;
+                    }
+                    for i in 0..n {
+                        let (vp, vq) = (v[(i, p)], v[(i, q)]);
+                        v[(i, p)] = c * vp - s * vq;
+                        v[(i, q)] = s * vp + c * vq;
+                    }
+                }
+            }
+            if !rotated {
+                converged = true;
+                break;
+            }
+        }
+        if !converged {
+            return Err(StatustError::invalid(format!(
+                "Jacobi SVD did not converge in {} sweeps",
+                MAX_JACOBI_SWEEPS
+            )));
+        }
+        let norms: Vec<f64> = (0..n)
+            .map(|j| (0..m).map(|i| w[(i, j)].powi(2)).sum::<f64>().sqrt())
+            .collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+        let s: Vec<f64> = order.iter().map(|&j| norms[j]).collect();
+        let rows: Vec<usize> = (0..n).collect();
+        let v = v.select(&rows, &order);
+        let mut u = Matrix::zeros(m, n);
+        let tol = s[0].max(f64::MIN_POSITIVE) * m as f64 * f64::EPSILON;
+        for (k, &j) in order.iter().enumerate() {
+            if s[k] > tol {
+                for i in 0..m {
+                    u[(i, k)] = w[(i, j)] / s[k];
+                }
+            } else {
+                complete_column(&mut u, k);
+            }
+        }
+        Ok(Svd { u, s, v })
+    }
+}
+
+/// Fills column `k` of `u` with a unit vector orthogonal to columns
+/// `0..k`, by Gram-Schmidt on the standard basis.
+fn complete_column(u: &mut Matrix, k: usize) {
+    let m = u.nrows;
+    for e in 0..m {
+        let mut x = vec![0.0; m];
+        x[e] = 1.0;
+        // Two passes of Gram-Schmidt keep the result orthogonal.
+        for _ in 0..2 {
+            for j in 0..k {
+                let dot: f64 = (0..m).map(|i| u[(i, j)] * x[i]).sum();
+                for (i, xi) in x.iter_mut().enumerate() {
+                    *xi -= dot * u[(i, j)];
+                }
+            }
+        }
+        let norm = x.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm > 0.5 {
+            for (i, xi) in x.iter().enumerate() {
+                u[(i, k)] = xi / norm;
+            }
+            return;
+        }
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        &self.data[i * self.ncols + j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        &mut self.data[i * self.ncols + j]
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..self.nrows {
+            let row: Vec<String> = self.row(i).iter().map(|v| format!("{:>12.6}", v)).collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_matrix_close(a: &Matrix, b: &Matrix, tol: f64) {
+        assert_eq!((a.nrows(), a.ncols()), (b.nrows(), b.ncols()));
+        for (x, y) in a.as_slice().iter().zip(b.as_slice()) {
+            assert!((x - y).abs() < tol, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_matmul_transpose() {
+        let a = Matrix::from_rows(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+        let b = a.transpose();
+        assert_eq!(b.row(2), &[3.0, 6.0]);
+        let c = a.matmul(&b).unwrap();
+        assert_eq!(c.as_slice(), &[14.0, 32.0, 32.0, 77.0]);
+        assert_eq!(a.mul_vec(&[1.0, 0.0, -1.0]).unwrap(), vec![-2.0, -2.0]);
+        assert!(a.matmul(&a).is_err());
+        assert!(Matrix::new(2, 2, vec![1.0; 3]).is_err());
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let a = Matrix::from_rows(&[
+            vec![4.0, 12.0, -16.0],
+            vec![12.0, 37.0, -43.0],
+            vec![-16.0, -43.0, 98.0],
+        ])
+        .unwrap();
+        let l = a.cholesky().unwrap();
+        let expected = Matrix::from_rows(&[
+            vec![2.0, 0.0, 0.0],
+            vec![6.0, 1.0, 0.0],
+            vec![-8.0, 5.0, 3.0],
+        ])
+        .unwrap();
+        assert_matrix_close(&l, &expected, 1e-12);
+        assert_matrix_close(&l.matmul(&l.transpose()).unwrap(), &a, 1e-12);
+
+        let x = l.solve_lower(&[2.0, 7.0, 12.0]).unwrap();
+        assert_eq!(l.mul_vec(&x).unwrap(), vec![2.0, 7.0, 12.0]);
+        let lt = l.transpose();
+        let y = lt.solve_upper(&[1.0, 2.0, 3.0]).unwrap();
+        let back = lt.mul_vec(&y).unwrap();
+        assert!(back
+            .iter()
+            .zip([1.0, 2.0, 3.0])
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+
+        let not_pd = Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 1.0]]).unwrap();
+        assert!(not_pd.cholesky().is_err());
+        let not_sym = Matrix::from_rows(&[vec![2.0, 1.0], vec![0.0, 2.0]]).unwrap();
+        assert!(not_sym.cholesky().is_err());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let a = Matrix::from_rows(&[
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+            vec![3.0, 0.0, 4.0],
+        ])
+        .unwrap();
+        let inv = a.inverse().unwrap();
+        assert_matrix_close(&a.matmul(&inv).unwrap(), &Matrix::identity(3), 1e-12);
+        assert_eq!(a.select(&[2, 0], &[1]).as_slice(), &[0.0, 2.0]);
+        let singular = Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+        assert!(singular.inverse().is_err());
+        let nan = Matrix::from_rows(&[vec![f64::NAN, 1.0], vec![1.0, 2.0]]).unwrap();
+        assert!(nan.inverse().is_err());
+        let inf = Matrix::from_rows(&[vec![1.0, 0.0], vec![0.0, f64::INFINITY]]).unwrap();
+        assert!(inf.inverse().is_err());
+    }
+
+    #[test]
+    fn test_qr() {
+        let a = Matrix::from_rows(&[
+            vec![12.0, -51.0, 4.0],
+            vec![6.0, 167.0, -68.0],
+            vec![-4.0, 24.0, -41.0],
+            vec![1.0, 2.0, 3.0],
+        ])
+        .unwrap();
+        let Qr { q, r } = a.qr().unwrap();
+        assert_matrix_close(&q.matmul(&r).unwrap(), &a, 1e-10);
+        assert_matrix_close(
+            &q.transpose().matmul(&q).unwrap(),
+            &Matrix::identity(3),
+            1e-12,
+        );
+        assert!((0..3).all(|i| (0..i).all(|j| r[(i, j)] == 0.0)));
+
+        // Exact fit of y = 1 + 2x, then the least squares line through
+        // (0, 0), (1, 1), (2, 1).
+        let x = Matrix::from_rows(&[vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]).unwrap();
+        let qr = x.qr().unwrap();
+        assert_matrix_close(
+            &Matrix::new(2, 1, qr.solve(&[1.0, 3.0, 5.0]).unwrap()).unwrap(),
+            &Matrix::new(2, 1, vec![1.0, 2.0]).unwrap(),
+            1e-12,
+        );
+        let beta = qr.solve(&[0.0, 1.0, 1.0]).unwrap();
+        assert!((beta[0] - 1.0 / 6.0).abs() < 1e-12 && (beta[1] - 0.5).abs() < 1e-12);
+        assert!(qr.solve(&[1.0]).is_err());
+        assert!(x.transpose().qr().is_err());
+    }
+
+    #[test]
+    fn test_lu() {
+        let a = Matrix::from_rows(&[
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+            vec![3.0, 0.0, 4.0],
+        ])
+        .unwrap();
+        let lu = a.lu().unwrap();
+        assert_eq!(lu.perm, vec![2, 0, 1]);
+        let pa = a.select(&lu.perm, &[0, 1, 2]);
+        assert_matrix_close(&lu.l.matmul(&lu.u).unwrap(), &pa, 1e-12);
+        assert!((0..3).all(|i| lu.l[(i, i)] == 1.0 && (0..i).all(|j| lu.u[(i, j)] == 0.0)));
+        // det = 0 * (4 - 0) - 2 * (4 - 0) + 1 * (0 - 3) = -11
+        assert!((lu.determinant() + 11.0).abs() < 1e-12);
+        let x = lu.solve(&[3.0, 2.0, 7.0]).unwrap();
+        assert!(x
+            .iter()
+            .zip([1.0, 1.0, 1.0])
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+        assert!(lu.solve(&[1.0]).is_err());
+
+        let singular = Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+        let lu = singular.lu().unwrap();
+        assert_eq!(lu.determinant(), 0.0);
+        assert!(lu.solve(&[1.0, 2.0]).is_err());
+        assert!(Matrix::zeros(2, 3).lu().is_err());
+        let nan = Matrix::from_rows(&[vec![1.0, f64::NAN], vec![2.0, 1.0]]).unwrap();
+        assert!(nan.lu().unwrap().determinant().is_nan());
+    }
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let a = Matrix::from_rows(&[vec![2.0, 1.0], vec![1.0, 2.0]]).unwrap();
+        let eig = a.symmetric_eigen().unwrap();
+        assert!((eig.values[0] - 3.0).abs() < 1e-14 && (eig.values[1] - 1.0).abs() < 1e-14);
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        assert_matrix_close(
+            &eig.vectors,
+            &Matrix::from_rows(&[vec![h, h], vec![h, -h]]).unwrap(),
+            1e-14,
+        );
+
+        let a = Matrix::from_rows(&[
+            vec![4.0, 1.0, -2.0, 2.0],
+            vec![1.0, 2.0, 0.0, 1.0],
+            vec![-2.0, 0.0, 3.0, -2.0],
+            vec![2.0, 1.0, -2.0, -1.0],
+        ])
+        .unwrap();
+        let SymmetricEigen { values, vectors } = a.symmetric_eigen().unwrap();
+        // Reference eigenvalues from mpmath's eigsy in 30-digit arithmetic.
+        let expected = [
+            6.844621107235,
+            2.268531406431,
+            1.084364463773,
+            -2.197516977439,
+        ];
+        for (v, e) in values.iter().zip(expected) {
+            assert!((v - e).abs() < 1e-11, "{:?}", values);
+        }
+        assert!((values.iter().sum::<f64>() - 8.0).abs() < 1e-12);
+        let rebuilt = vectors
+            .matmul(&Matrix::from_diag(&values))
+            .unwrap()
+            .matmul(&vectors.transpose())
+            .unwrap();
+        assert_matrix_close(&rebuilt, &a, 1e-12);
+        assert_matrix_close(
+            &vectors.transpose().matmul(&vectors).unwrap(),
+            &Matrix::identity(4),
+            1e-12,
+        );
+        let not_sym = Matrix::from_rows(&[vec![2.0, 1.0], vec![0.0, 2.0]]).unwrap();
+        assert!(not_sym.symmetric_eigen().is_err());
+
+        // Larger random matrices converge to working precision.
+        let n = 60;
+        let mut rng = Xoshiro256::seed_from_u64(3);
+        let mut a = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in 0..=i {
+                a[(i, j)] = 2.0 * rng.next_f64() - 1.0;
+                a[(j, i)] = a[(i, j)];
+            }
+        }
+        let SymmetricEigen { values, vectors } = a.symmetric_eigen().unwrap();
+        assert!(values.windows(2).all(|w| w[0] >= w[1]));
+        let rebuilt = vectors
+            .matmul(&Matrix::from_diag(&values))
+            .unwrap()
+            .matmul(&vectors.transpose())
+            .unwrap();
+        assert_matrix_close(&rebuilt, &a, 1e-12);
+        assert_matrix_close(
+            &vectors.transpose().matmul(&vectors).unwrap(),
+            &Matrix::identity(n),
+            1e-12,
+        );
+
+        // A rank-one matrix: the zero eigenvalues must not stall the sweeps.
+        let ones = Matrix::from_rows(&vec![vec![1.0; 30]; 30]).unwrap();
+        let eig = ones.symmetric_eigen().unwrap();
+        assert!((eig.values[0] - 30.0).abs() < 1e-12);
+        assert!(eig.values[1..].iter().all(|v| v.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_svd() {
+        let a = Matrix::from_rows(&[vec![3.0, 2.0, 2.0], vec![2.0, 3.0, -2.0]]).unwrap();
+        let Svd { u, s, v } = a.svd().unwrap();
+        assert_eq!((u.nrows(), u.ncols(), v.nrows(), v.ncols()), (2, 2, 3, 2));
+        assert!((s[0] - 5.0).abs() < 1e-14 && (s[1] - 3.0).abs() < 1e-14);
+        let rebuilt = u
+            .matmul(&Matrix::from_diag(&s))
+            .unwrap()
+            .matmul(&v.transpose())
+            .unwrap();
+        assert_matrix_close(&rebuilt, &a, 1e-12);
+        assert_matrix_close(
+            &v.transpose().matmul(&v).unwrap(),
+            &Matrix::identity(2),
+            1e-12,
+        );
+
+        // Rank one: the second left singular vector still completes an
+        // orthonormal pair.
+        let a = Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]).unwrap();
+        let Svd { u, s, v } = a.svd().unwrap();
+        assert!((s[0] - 70f64.sqrt()).abs() < 1e-12 && s[1].abs() < 1e-12);
+        assert_matrix_close(
+            &u.transpose().matmul(&u).unwrap(),
+            &Matrix::identity(2),
+            1e-12,
+        );
+        let rebuilt = u
+            .matmul(&Matrix::from_diag(&s))
+            .unwrap()
+            .matmul(&v.transpose())
+            .unwrap();
+        assert_matrix_close(&rebuilt, &a, 1e-12);
+
+        // Singular values are the square roots of the eigenvalues of A^T A.
+        let a = Matrix::from_rows(&[
+            vec![12.0, -51.0, 4.0],
+            vec![6.0, 167.0, -68.0],
+            vec![-4.0, 24.0, -41.0],
+            vec![1.0, 2.0, 3.0],
+        ])
+        .unwrap();
+        let s = a.svd().unwrap().s;
+        let eig = a.transpose().matmul(&a).unwrap().symmetric_eigen().unwrap();
+        for (s, l) in s.iter().zip(&eig.values) {
+            assert!((s - l.sqrt()).abs() < 1e-10 * s.max(1.0));
+        }
+
+        // No columns (or no rows) gives an empty decomposition.
+        let Svd { u, s, v } = Matrix::zeros(3, 0).svd().unwrap();
+        assert!(s.is_empty());
+        assert_eq!((u.nrows(), u.ncols(), v.nrows(), v.ncols()), (3, 0, 0, 0));
+        let Svd { u, s, v } = Matrix::zeros(0, 3).svd().unwrap();
+        assert!(s.is_empty());
+        assert_eq!((u.nrows(), u.ncols(), v.nrows(), v.ncols()), (0, 0, 3, 0));
+    }
+}
@@ -2,7 +2,7 @@ extern crate statust;
 
 use std::cell::RefCell;
 
-use easy_repl::{command, CommandStatus, Repl};
+use easy_repl::{anyhow, command, Command, CommandStatus, Repl};
 
 fn main() {
     // let s = "Hello, world!";
@@ -21,9 +21,10 @@ fn main() {
     //     data: statust::ResultType::DescribeMany(df.describe()),
     // };
     // writer.write_file();
-    let mut df = RefCell::new(statust::DataFrame::new());
-    let mut ref1 = &df;
+    let df = RefCell::new(statust::DataFrame::new());
+    let ref1 = &df;
     let ref2 = &df;
+    let ref3 = &df;
 
     let mut repl = Repl::builder()
         .description("Basic Statust REPL")
@@ -44,11 +45,33 @@ fn main() {
             command! {
                 "Print the dataframe",
                 () => || {
-                    ref2.borrow().print();
+                    ref2.borrow().print().ok();
                     Ok(CommandStatus::Done)
                 }
             },
         )
+        .add(
+            "simulate",
+            Command {
+                description:
+                    "Simulate columns, e.g. simulate n=1000 x~normal(0,1) y~poisson(3) seed=42"
+                        .into(),
+                args_info: vec![
+                    "[n=N] name~dist(params).. [seed=S] [mode=replace|add]:String".into(),
+                ],
+                handler: Box::new(|args: &[&str]| -> anyhow::Result<CommandStatus> {
+                    let sim = statust::Simulation::parse(&args.join(" "))?;
+                    let simulated = sim.run(&ref3.borrow())?;
+                    println!(
+                        "Simulated {} rows, dataframe now has columns: {}",
+                        simulated.nrows(),
+                        simulated.header().join(", ")
+                    );
+                    *ref3.borrow_mut() = simulated;
+                    Ok(CommandStatus::Done)
+                }),
+            },
+        )
         .build()
         .expect("Failed to create repl");
 
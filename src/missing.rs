@@ -0,0 +1,372 @@
+use crate::dataframe::is_numeric_col;
+use crate::*;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DropHow {
+    /// Drop rows with at least one null.
+    Any,
+    /// Drop rows where every value is null.
+    All,
+    /// Keep only rows with at least this many non-null values.
+    Thresh(usize),
+}
+
+/// How `DataFrame::fill_null` replaces nulls. The numeric strategies (`Mean`,
+/// `Median` and `Linear`) round their fill values on integer columns so the
+/// column keeps its type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FillStrategy {
+    /// A constant of the column's type. Numeric columns also take an `Int`,
+    /// stored as a `Float` unless the column holds only integers.
+    Value(DataType),
+    Mean,
+    Median,
+    Mode,
+    Forward,
+    Backward,
+    /// Linear interpolation between the surrounding non-null values. Leading
+    /// and trailing nulls are left untouched.
+    Linear,
+}
+
+impl FillStrategy {
+    /// Why the strategy cannot fill `col`, if it cannot.
+    fn mismatch(&self, col: &[DataType]) -> Option<&'static str> {
+        let present: Vec<&DataType> = col.iter().filter(|v| !v.is_null()).collect();
+        let numeric = present.iter().all(|v| v.as_f64().is_some());
+        let int_only = !present.is_empty() && present.iter().all(|v| matches!(v, DataType::Int(_)));
+        match self {
+            FillStrategy::Mean | FillStrategy::Median | FillStrategy::Linear
+                if !is_numeric_col(col) =>
+            {
+                Some("is not numeric")
+            }
+            FillStrategy::Value(v) if v.is_null() => None,
+            FillStrategy::Value(DataType::Int(_)) if !numeric => Some("is not numeric"),
+            FillStrategy::Value(DataType::Float(_)) if !numeric || int_only => {
+                Some("does not match the type of the fill value")
+            }
+            FillStrategy::Value(v @ (DataType::Bool(_) | DataType::String(_)))
+                if present.iter().any(|c| c.dtype() != v.dtype()) =>
+            {
+                Some("does not match the type of the fill value")
+            }
+            _ => None,
+        }
+    }
+}
+
+impl DataFrame {
+    /// Removes rows with nulls in the `subset` columns (all columns if `None`).
+    pub fn drop_nulls(
+        &self,
+        subset: Option<&[&str]>,
+        how: DropHow,
+    ) -> Result<DataFrame, StatustError> {
+        let indices = self.subset_indices(subset)?;
+        let data = self
+            .data
+            .iter()
+            .filter(|row| {
+                let present = indices.iter().filter(|&&i| !row[i].is_null()).count();
+                match how {
+                    DropHow::Any => present == indices.len(),
+                    DropHow::All => present > 0 || indices.is_empty(),
+                    DropHow::Thresh(n) => present >= n,
+                }
+            })
+            .cloned()
+            .collect();
+        Ok(DataFrame {
+            header: self.header.clone(),
+            data,
+        })
+    }
+
+    /// Replaces nulls in the `subset` columns (all columns if `None`). With no
+    /// subset, numeric strategies skip non-numeric columns and a `Value` skips
+    /// columns of another type; naming such a column explicitly is an error.
+    pub fn fill_null(
+        &mut self,
+        subset: Option<&[&str]>,
+        strategy: FillStrategy,
+    ) -> Result<(), StatustError> {
+        let indices = self.subset_indices(subset)?;
+        for i in indices {
+            let mut col = self.get_col(i).unwrap();
+            if let Some(reason) = strategy.mismatch(&col) {
+                if subset.is_some() {
+                    return Err(StatustError::invalid(format!(
+                        "column {} {}",
+                        self.header[i], reason
+                    )));
+                }
+                continue;
+            }
+            fill_col(&mut col, &strategy);
+            for (row, v) in self.data.iter_mut().zip(col) {
+                row[i] = v;
+            }
+        }
+        Ok(())
+    }
+
+    /// Summarizes missing values per column and the sets of columns that are
+    /// missing together in the same row.
+    pub fn null_report(&self) -> NullReport {
+        let nrows = self.nrows();
+        let columns = (0..self.ncols())
+            .map(|i| {
+                let null_count = self.data.iter().filter(|row| row[i].is_null()).count();
+                ColumnNullSummary {
+                    name: self.header[i].clone(),
+                    null_count,
+                    null_fraction: if nrows > 0 {
+                        null_count as f64 / nrows as f64
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect();
+
+        let mut counts: HashMap<Vec<usize>, usize> = HashMap::new();
+        for row in &self.data {
+            let missing: Vec<usize> = (0..row.len()).filter(|&i| row[i].is_null()).collect();
+            if !missing.is_empty() {
+                *counts.entry(missing).or_insert(0) += 1;
+            }
+        }
+        let mut patterns: Vec<(Vec<usize>, usize)> = counts.into_iter().collect();
+        patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let patterns = patterns
+            .into_iter()
+            .map(|(cols, count)| NullPattern {
+                columns: cols.iter().map(|&i| self.header[i].clone()).collect(),
+                count,
+            })
+            .collect();
+
+        NullReport {
+            nrows,
+            columns,
+            patterns,
+        }
+    }
+
+    pub(crate) fn subset_indices(
+        &self,
+        subset: Option<&[&str]>,
+    ) -> Result<Vec<usize>, StatustError> {
+        match subset {
+            None => Ok((0..self.ncols()).collect()),
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    self.col_index(name)
+                        .ok_or_else(|| StatustError::ColumnNotFound(name.to_string()))
+                })
+                .collect(),
+        }
+    }
+}
+
+fn fill_col(col: &mut [DataType], strategy: &FillStrategy) {
+    let values: Vec<f64> = col.iter().filter_map(|v| v.as_f64()).collect();
+    let int_col = col
+        .iter()
+        .all(|v| v.is_null() || matches!(v, DataType::Int(_)));
+    let fill = |x: f64| {
+        if int_col {
+            DataType::Int(x.round() as i32)
+        } else {
+            DataType::from(x)
+        }
+    };
+    let constant = match strategy {
+        FillStrategy::Value(DataType::Int(i)) => Some(fill(*i as f64)),
+        FillStrategy::Value(v) => Some(v.clone()),
+        FillStrategy::Mean => Some(fill(stats::mean(&values))),
+        FillStrategy::Median => Some(fill(stats::median(&values))),
+        FillStrategy::Mode => mode(col),
+        _ => None,
+    };
+    if let Some(constant) = constant {
+        for v in col.iter_mut().filter(|v| v.is_null()) {
+            *v = constant.clone();
+        }
+        return;
+    }
+
+    match strategy {
+        FillStrategy::Forward => {
+            let mut last = DataType::None;
+            for v in col.iter_mut() {
+                if v.is_null() {
+                    *v = last.clone();
+                } else {
+                    last = v.clone();
+                }
+            }
+        }
+        FillStrategy::Backward => {
+            let mut next = DataType::None;
+            for v in col.iter_mut().rev() {
+                if v.is_null() {
+                    *v = next.clone();
+                } else {
+                    next = v.clone();
+                }
+            }
+        }
+        FillStrategy::Linear => {
+            let known: Vec<(usize, f64)> = col
+                .iter()
+                .enumerate()
+                .filter_map(|(i, v)| v.as_f64().map(|x| (i, x)))
+                .collect();
+            for pair in known.windows(2) {
+                let ((i0, x0), (i1, x1)) = (pair[0], pair[1]);
+                for (i, v) in col.iter_mut().enumerate().take(i1).skip(i0 + 1) {
+                    let t = (i - i0) as f64 / (i1 - i0) as f64;
+                    *v = fill(x0 + t * (x1 - x0));
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Most frequent non-null value, ties broken by first occurrence.
+fn mode(col: &[DataType]) -> Option<DataType> {
+    let mut counts: HashMap<&DataType, usize> = HashMap::new();
+    for v in col.iter().filter(|v| !v.is_null()) {
+        *counts.entry(v).or_insert(0) += 1;
+    }
+    let max = counts.values().copied().max()?;
+    col.iter().find(|v| counts.get(v) == Some(&max)).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock() -> DataFrame {
+        DataFrame::read("./examples/MOCK_DATA.csv").unwrap()
+    }
+
+    #[test]
+    fn test_drop_nulls() {
+        let df = mock();
+        assert_eq!(df.drop_nulls(None, DropHow::Any).unwrap().nrows(), 129);
+        assert_eq!(df.drop_nulls(None, DropHow::All).unwrap().nrows(), 150);
+        assert_eq!(
+            df.drop_nulls(Some(&["float_test"]), DropHow::Any)
+                .unwrap()
+                .nrows(),
+            141
+        );
+        assert_eq!(
+            df.drop_nulls(None, DropHow::Thresh(3)).unwrap().nrows(),
+            148
+        );
+        assert_eq!(
+            df.drop_nulls(Some(&["nope"]), DropHow::Any).unwrap_err(),
+            StatustError::ColumnNotFound("nope".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fill_null() {
+        let mut df = mock();
+        df.fill_null(Some(&["float_test"]), FillStrategy::Linear)
+            .unwrap();
+        let col = df.get_col_by_name("float_test").unwrap();
+        // Row 6 lies between 2.8 and 4.28.
+        assert_eq!(
+            col[6],
+            DataType::from((2.8f32 as f64 + 4.28f32 as f64) / 2.0)
+        );
+
+        let mut df = mock();
+        df.fill_null(Some(&["string_test"]), FillStrategy::Mode)
+            .unwrap();
+        assert_eq!(
+            df.get_col_by_name("string_test").unwrap()[22],
+            DataType::from("Setosa")
+        );
+
+        let mut df = mock();
+        df.fill_null(None, FillStrategy::Median).unwrap();
+        assert_eq!(
+            df.get_col_by_name("int_test").unwrap()[69],
+            DataType::Int(5)
+        );
+        assert_eq!(df.null_report().columns[0].null_count, 6);
+        assert!(df
+            .fill_null(Some(&["boolean_test"]), FillStrategy::Mean)
+            .is_err());
+
+        let mut df = DataFrame::from_columns(
+            vec!["n".to_string()],
+            vec![vec![
+                DataType::Int(1),
+                DataType::None,
+                DataType::Int(4),
+                DataType::None,
+            ]],
+        )
+        .unwrap();
+        let mut mean = df.clone();
+        mean.fill_null(None, FillStrategy::Mean).unwrap();
+        assert_eq!(mean.get_col(0).unwrap()[1], DataType::Int(3));
+        df.fill_null(None, FillStrategy::Linear).unwrap();
+        assert_eq!(
+            df.get_col(0).unwrap(),
+            vec![
+                DataType::Int(1),
+                DataType::Int(3),
+                DataType::Int(4),
+                DataType::None
+            ]
+        );
+
+        let mut df = mock();
+        df.fill_null(None, FillStrategy::Forward).unwrap();
+        assert_eq!(
+            df.get_col_by_name("float_test").unwrap()[6],
+            DataType::Float(2.8)
+        );
+        df.fill_null(None, FillStrategy::Value(DataType::Int(0)))
+            .unwrap();
+        assert_eq!(df.drop_nulls(None, DropHow::Any).unwrap().nrows(), 150);
+
+        let mut df = mock();
+        let text = FillStrategy::Value(DataType::from("none"));
+        assert!(df.fill_null(Some(&["int_test"]), text.clone()).is_err());
+        let float = FillStrategy::Value(DataType::Float(0.5));
+        assert!(df.fill_null(Some(&["int_test"]), float).is_err());
+        df.fill_null(None, text).unwrap();
+        assert_eq!(df.null_report().columns[1].null_count, 4);
+        assert_eq!(df.null_report().columns[3].null_count, 0);
+        df.fill_null(Some(&["float_test"]), FillStrategy::Value(DataType::Int(0)))
+            .unwrap();
+        assert_eq!(
+            df.get_col_by_name("float_test").unwrap()[6],
+            DataType::Float(0.0)
+        );
+    }
+
+    #[test]
+    fn test_null_report() {
+        let report = mock().null_report();
+        assert_eq!(report.nrows, 150);
+        let counts: Vec<usize> = report.columns.iter().map(|c| c.null_count).collect();
+        assert_eq!(counts, vec![6, 4, 9, 4]);
+        assert_eq!(report.patterns[0].columns, vec!["float_test".to_string()]);
+        assert_eq!(report.patterns[0].count, 9);
+        assert_eq!(report.patterns.iter().map(|p| p.count).sum::<usize>(), 21);
+        assert!(report.to_string().contains("float_test: 9 (6.00%)"));
+    }
+}
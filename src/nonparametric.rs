@@ -0,0 +1,668 @@
+use crate::contingency::chi_square_test;
+use crate::distributions::{
+    Binomial, ChiSquared, ContinuousDistribution, DiscreteDistribution, Normal,
+};
+use crate::linalg::Matrix;
+use crate::normality::kolmogorov_sf_asymptotic;
+use crate::*;
+
+/// Samples below this size without ties get exact p-values.
+const EXACT_LIMIT: usize = 50;
+
+fn check_finite(x: &[f64], name: &str) -> Result<(), StatustError> {
+    if x.iter().any(|v| !v.is_finite()) {
+        return Err(StatustError::invalid(format!(
+            "{} contains non-finite values",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Sizes of the groups of tied values (only groups larger than one).
+fn tie_sizes(x: &[f64]) -> Vec<f64> {
+    let mut sorted = x.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut sizes = Vec::new();
+    let mut i = 0;
+    while i < sorted.len() {
+        let j = sorted[i..].iter().take_while(|v| **v == sorted[i]).count();
+        if j > 1 {
+            sizes.push(j as f64);
+        }
+        i += j;
+    }
+    sizes
+}
+
+/// `sum(t^3 - t)` over the tie groups of `x`.
+fn tie_sum(x: &[f64]) -> f64 {
+    tie_sizes(x).iter().map(|t| t * t * t - t).sum()
+}
+
+/// P-value of a normal approximation with continuity correction of 0.5
+/// towards the mean. Errors when `sigma` is zero, which happens when every
+/// value is tied.
+fn normal_p_value(z: f64, sigma: f64, alternative: Alternative) -> Result<f64, StatustError> {
+    if sigma.is_nan() || sigma <= 0.0 {
+        return Err(StatustError::invalid(
+            "the statistic has zero variance because all values are tied".to_string(),
+        ));
+    }
+    let correction = match alternative {
+        // No correction when the statistic sits exactly at its mean, as in R.
+        Alternative::TwoSided if z == 0.0 => 0.0,
+        Alternative::TwoSided => 0.5 * z.signum(),
+        Alternative::Greater => 0.5,
+        Alternative::Less => -0.5,
+    };
+    Ok(alternative.p_value(&Normal::standard(), (z - correction) / sigma))
+}
+
+/// P-value from exact counts `counts[s]` of a statistic taking values
+/// `0..counts.len()`, symmetric about its mean, as in R's `wilcox.test`.
+fn exact_p_value(counts: &[f64], statistic: f64, alternative: Alternative) -> f64 {
+    let total: f64 = counts.iter().sum();
+    let s = statistic.round() as usize;
+    let lower = |k: usize| counts[..=k.min(counts.len() - 1)].iter().sum::<f64>() / total;
+    let upper = |k: usize| counts[k.min(counts.len())..].iter().sum::<f64>() / total;
+    match alternative {
+        Alternative::Less => lower(s),
+        Alternative::Greater => upper(s),
+        Alternative::TwoSided => {
+            let mean = (counts.len() - 1) as f64 / 2.0;
+            let p = if statistic > mean { upper(s) } else { lower(s) };
+            (2.0 * p).min(1.0)
+        }
+    }
+}
+
+/// Number of ways to pick `m` of the ranks `1..=m + n` for each value of the
+/// Mann-Whitney statistic `U = rank sum - m (m + 1) / 2`.
+fn mann_whitney_counts(m: usize, n: usize) -> Vec<f64> {
+    let max_sum = (m + n) * (m + n + 1) / 2;
+    // ways[j][s]: subsets of size j with rank sum s.
+    let mut ways = vec![vec![0.0; max_sum + 1]; m + 1];
+    ways[0][0] = 1.0;
+    for r in 1..=m + n {
+        for j in (1..=m.min(r)).rev() {
+            for s in (r..=max_sum).rev() {
+                ways[j][s] += ways[j - 1][s - r];
+            }
+        }
+    }
+    let offset = m * (m + 1) / 2;
+    ways[m][offset..=offset + m * n].to_vec()
+}
+
+/// Number of subsets of the ranks `1..=n` for each value of their sum.
+fn signed_rank_counts(n: usize) -> Vec<f64> {
+    let max_sum = n * (n + 1) / 2;
+    let mut ways = vec![0.0; max_sum + 1];
+    ways[0] = 1.0;
+    for r in 1..=n {
+        for s in (r..=max_sum).rev() {
+            ways[s] += ways[s - r];
+        }
+    }
+    ways
+}
+
+/// Wilcoxon rank-sum (Mann-Whitney) test of a location shift between `x`
+/// and `y`. The statistic is `W`, the rank sum of `x` minus
+/// `nx (nx + 1) / 2`. P-values are exact for samples under 50 without
+/// ties, otherwise from the normal approximation with tie and continuity
+/// corrections.
+pub fn mann_whitney(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+) -> Result<TestResult, StatustError> {
+    check_finite(x, "x")?;
+    check_finite(y, "y")?;
+    if x.is_empty() || y.is_empty() {
+        return Err(StatustError::invalid(
+            "both samples must be non-empty".to_string(),
+        ));
+    }
+    let (m, n) = (x.len(), y.len());
+    let all = [x, y].concat();
+    let ranks = stats::rank(&all);
+    let w = ranks[..m].iter().sum::<f64>() - (m * (m + 1)) as f64 / 2.0;
+    let ties = tie_sum(&all);
+    let exact = m < EXACT_LIMIT && n < EXACT_LIMIT && ties == 0.0;
+    let (mf, nf) = (m as f64, n as f64);
+    let (method, p_value) = if exact {
+        (
+            "Wilcoxon rank sum exact test",
+            exact_p_value(&mann_whitney_counts(m, n), w, alternative),
+        )
+    } else {
+        let sigma =
+            (mf * nf / 12.0 * ((mf + nf + 1.0) - ties / ((mf + nf) * (mf + nf - 1.0)))).sqrt();
+        (
+            "Wilcoxon rank sum test with continuity correction",
+            normal_p_value(w - mf * nf / 2.0, sigma, alternative)?,
+        )
+    };
+    let mut result = TestResult::new(method, w, p_value, alternative);
+    // Rank-biserial correlation as effect size.
+    result
+        .estimates
+        .push(("rank-biserial r".to_string(), 2.0 * w / (mf * nf) - 1.0));
+    Ok(result)
+}
+
+/// Wilcoxon signed-rank test of `H0: median(x) = mu`. Zeros are dropped.
+/// The statistic `V` is the sum of the ranks of the positive differences.
+/// P-values are exact for under 50 non-zero differences without ties.
+pub fn wilcoxon_signed_rank(
+    x: &[f64],
+    mu: f64,
+    alternative: Alternative,
+) -> Result<TestResult, StatustError> {
+    check_finite(x, "x")?;
+    let d: Vec<f64> = x.iter().map(|v| v - mu).filter(|v| *v != 0.0).collect();
+    if d.is_empty() {
+        return Err(StatustError::invalid(
+            "all differences are zero".to_string(),
+        ));
+    }
+    let n = d.len();
+    let abs: Vec<f64> = d.iter().map(|v| v.abs()).collect();
+    let ranks = stats::rank(&abs);
+    let v: f64 = ranks
+        .iter()
+        .zip(&d)
+        .filter(|(_, di)| **di > 0.0)
+        .map(|(r, _)| r)
+        .sum();
+    let ties = tie_sum(&abs);
+    let exact = n < EXACT_LIMIT && ties == 0.0 && n == x.len();
+    let nf = n as f64;
+    let (method, p_value) = if exact {
+        (
+            "Wilcoxon signed rank exact test",
+            exact_p_value(&signed_rank_counts(n), v, alternative),
+        )
+    } else {
+        let sigma = (nf * (nf + 1.0) * (2.0 * nf + 1.0) / 24.0 - ties / 48.0).sqrt();
+        (
+            "Wilcoxon signed rank test with continuity correction",
+            normal_p_value(v - nf * (nf + 1.0) / 4.0, sigma, alternative)?,
+        )
+    };
+    let mut result = TestResult::new(method, v, p_value, alternative);
+    result.parameters.push(("mu".to_string(), mu));
+    Ok(result)
+}
+
+/// Wilcoxon signed-rank test on the paired differences `x - y`.
+pub fn wilcoxon_paired(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+) -> Result<TestResult, StatustError> {
+    if x.len() != y.len() {
+        return Err(StatustError::LengthMismatch {
+            expected: x.len(),
+            found: y.len(),
+        });
+    }
+    let d: Vec<f64> = x.iter().zip(y).map(|(a, b)| a - b).collect();
+    wilcoxon_signed_rank(&d, 0.0, alternative)
+}
+
+/// Exact sign test of `H0: median(x) = mu`. The statistic is the number of
+/// values above `mu`; values equal to `mu` are dropped.
+pub fn sign_test(x: &[f64], mu: f64, alternative: Alternative) -> Result<TestResult, StatustError> {
+    check_finite(x, "x")?;
+    let above = x.iter().filter(|v| **v > mu).count();
+    let n = above + x.iter().filter(|v| **v < mu).count();
+    if n == 0 {
+        return Err(StatustError::invalid("all values equal mu".to_string()));
+    }
+    let dist = Binomial::new(n as u64, 0.5)?;
+    let k = above as i64;
+    let lower = dist.cdf(k);
+    let upper = 1.0 - dist.cdf(k - 1);
+    let p_value = match alternative {
+        Alternative::Less => lower,
+        Alternative::Greater => upper,
+        Alternative::TwoSided => (2.0 * lower.min(upper)).min(1.0),
+    };
+    let mut result = TestResult::new("Exact sign test", above as f64, p_value, alternative);
+    result.parameters = vec![("n".to_string(), n as f64), ("mu".to_string(), mu)];
+    Ok(result)
+}
+
+/// Kruskal-Wallis rank sum test that all groups come from the same
+/// distribution, with the tie correction.
+pub fn kruskal_wallis(groups: &[Vec<f64>]) -> Result<TestResult, StatustError> {
+    if groups.len() < 2 || groups.iter().any(|g| g.is_empty()) {
+        return Err(StatustError::invalid(
+            "Kruskal-Wallis test needs at least two non-empty groups".to_string(),
+        ));
+    }
+    let all = groups.concat();
+    check_finite(&all, "groups")?;
+    let n = all.len() as f64;
+    let ranks = stats::rank(&all);
+    let mut start = 0;
+    let mut h = 0.0;
+    for g in groups {
+        let r: f64 = ranks[start..start + g.len()].iter().sum();
+        h += r * r / g.len() as f64;
+        start += g.len();
+    }
+    h = 12.0 / (n * (n + 1.0)) * h - 3.0 * (n + 1.0);
+    let correction = 1.0 - tie_sum(&all) / (n * n * n - n);
+    if correction <= 0.0 {
+        return Err(StatustError::invalid(
+            "all values are identical".to_string(),
+        ));
+    }
+    h /= correction;
+    let df = (groups.len() - 1) as f64;
+    let mut result = TestResult::new(
+        "Kruskal-Wallis rank sum test",
+        h,
+        ChiSquared::new(df)?.sf(h),
+        Alternative::Greater,
+    );
+    result.parameters.push(("df".to_string(), df));
+    Ok(result)
+}
+
+/// Friedman rank sum test for an unreplicated complete block design. Each
+/// element of `blocks` holds one observation per treatment.
+pub fn friedman(blocks: &[Vec<f64>]) -> Result<TestResult, StatustError> {
+    let k = blocks.first().map_or(0, |b| b.len());
+    if blocks.len() < 2 || k < 2 {
+        return Err(StatustError::invalid(
+            "Friedman test needs at least two blocks and two treatments".to_string(),
+        ));
+    }
+    if let Some(b) = blocks.iter().find(|b| b.len() != k) {
+        return Err(StatustError::LengthMismatch {
+            expected: k,
+            found: b.len(),
+        });
+    }
+    check_finite(&blocks.concat(), "blocks")?;
+    let (n, kf) = (blocks.len() as f64, k as f64);
+    let mut rank_sums = vec![0.0; k];
+    let mut ties = 0.0;
+    for b in blocks {
+        for (s, r) in rank_sums.iter_mut().zip(stats::rank(b)) {
+            *s += r;
+        }
+        ties += tie_sum(b);
+    }
+    let mean = n * (kf + 1.0) / 2.0;
+    let ss: f64 = rank_sums.iter().map(|r| (r - mean).powi(2)).sum();
+    let denom = n * kf * (kf + 1.0) - ties / (kf - 1.0);
+    if denom <= 0.0 {
+        return Err(StatustError::invalid(
+            "all values within every block are tied".to_string(),
+        ));
+    }
+    let statistic = 12.0 * ss / denom;
+    let df = kf - 1.0;
+    let mut result = TestResult::new(
+        "Friedman rank sum test",
+        statistic,
+        ChiSquared::new(df)?.sf(statistic),
+        Alternative::Greater,
+    );
+    result.parameters.push(("df".to_string(), df));
+    Ok(result)
+}
+
+/// Mood's median test: Pearson's chi-squared test (without continuity
+/// correction) on the counts above and not above the grand median in each
+/// group.
+pub fn mood_median(groups: &[Vec<f64>]) -> Result<TestResult, StatustError> {
+    if groups.len() < 2 || groups.iter().any(|g| g.is_empty()) {
+        return Err(StatustError::invalid(
+            "Mood's median test needs at least two non-empty groups".to_string(),
+        ));
+    }
+    let all = groups.concat();
+    check_finite(&all, "groups")?;
+    let median = stats::median(&all);
+    let mut counts = Matrix::zeros(2, groups.len());
+    for (j, g) in groups.iter().enumerate() {
+        let above = g.iter().filter(|v| **v > median).count() as f64;
+        counts[(0, j)] = above;
+        counts[(1, j)] = g.len() as f64 - above;
+    }
+    let table = ContingencyTable {
+        row_name: "median".to_string(),
+        col_name: "group".to_string(),
+        row_labels: vec!["above".to_string(), "not above".to_string()],
+        col_labels: (1..=groups.len()).map(|i| i.to_string()).collect(),
+        counts,
+    };
+    let chi = chi_square_test(&table, false)?.test;
+    let mut result = TestResult::new(
+        "Mood's median test",
+        chi.statistic,
+        chi.p_value,
+        Alternative::Greater,
+    );
+    result.parameters = chi.parameters;
+    result.estimates.push(("grand median".to_string(), median));
+    Ok(result)
+}
+
+/// `P(D < d)` for the exact two-sample Smirnov statistic with sample sizes
+/// `m` and `n`, counting lattice paths that stay within the band. `excess`
+/// gives the distance of a lattice point `(i / m, j / n)` from the diagonal
+/// in the direction being tested.
+fn smirnov_cdf_exact(m: usize, n: usize, d: f64, excess: impl Fn(f64, f64) -> f64) -> f64 {
+    let (mf, nf) = (m as f64, n as f64);
+    let q = (0.5 + (d * mf * nf - 1e-7).floor()) / (mf * nf);
+    let mut u: Vec<f64> = (0..=n)
+        .map(|j| {
+            if excess(0.0, j as f64 / nf) > q {
+                0.0
+            } else {
+                1.0
+            }
+        })
+        .collect();
+    for j in 1..=n {
+        if u[j - 1] == 0.0 {
+            u[j] = 0.0;
+        }
+    }
+    for i in 1..=m {
+        let w = i as f64 / (i + n) as f64;
+        let fi = i as f64 / mf;
+        u[0] = if excess(fi, 0.0) > q { 0.0 } else { w * u[0] };
+        for j in 1..=n {
+            u[j] = if excess(fi, j as f64 / nf) > q {
+                0.0
+            } else {
+                w * u[j] + u[j - 1]
+            };
+        }
+    }
+    u[n]
+}
+
+/// Two-sample Kolmogorov-Smirnov test. For `Greater` the statistic is
+/// `max(F_x - F_y)`, for `Less` `max(F_y - F_x)`. P-values are exact when
+/// `nx * ny < 10000` and there are no ties, otherwise asymptotic.
+pub fn ks_two_sample(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+) -> Result<TestResult, StatustError> {
+    check_finite(x, "x")?;
+    check_finite(y, "y")?;
+    if x.is_empty() || y.is_empty() {
+        return Err(StatustError::invalid(
+            "both samples must be non-empty".to_string(),
+        ));
+    }
+    let (m, n) = (x.len(), y.len());
+    let (mf, nf) = (m as f64, n as f64);
+    let mut all = [x, y].concat();
+    all.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    all.dedup();
+    let ecdf = |s: &[f64], v: f64| s.iter().filter(|w| **w <= v).count() as f64 / s.len() as f64;
+    let diffs: Vec<f64> = all.iter().map(|v| ecdf(x, *v) - ecdf(y, *v)).collect();
+    let d = match alternative {
+        Alternative::TwoSided => diffs.iter().fold(0.0_f64, |a, b| a.max(b.abs())),
+        Alternative::Greater => diffs.iter().fold(0.0_f64, |a, b| a.max(*b)),
+        Alternative::Less => diffs.iter().fold(0.0_f64, |a, b| a.max(-b)),
+    };
+    let exact = m * n < 10000 && all.len() == m + n;
+    let p_value = if exact {
+        1.0 - match alternative {
+            Alternative::TwoSided => smirnov_cdf_exact(m, n, d, |a, b| (a - b).abs()),
+            Alternative::Greater => smirnov_cdf_exact(m, n, d, |a, b| a - b),
+            Alternative::Less => smirnov_cdf_exact(m, n, d, |a, b| b - a),
+        }
+    } else {
+        let z = (mf * nf / (mf + nf)).sqrt() * d;
+        match alternative {
+            Alternative::TwoSided => kolmogorov_sf_asymptotic(z),
+            _ => (-2.0 * z * z).exp(),
+        }
+    };
+    let method = if exact {
+        "Exact two-sample Kolmogorov-Smirnov test"
+    } else {
+        "Asymptotic two-sample Kolmogorov-Smirnov test"
+    };
+    Ok(TestResult::new(
+        method,
+        d,
+        p_value.clamp(0.0, 1.0),
+        alternative,
+    ))
+}
+
+impl DataFrame {
+    /// Splits `value` by `group` and checks that there are exactly two groups.
+    fn two_groups(&self, value: &str, group: &str) -> Result<[Vec<f64>; 2], StatustError> {
+        let (labels, mut samples) = self.grouped_f64(value, group)?;
+        if samples.len() != 2 {
+            return Err(StatustError::invalid(format!(
+                "expected two groups in {}, found {}",
+                group,
+                labels.len()
+            )));
+        }
+        let y = samples.pop().unwrap();
+        let x = samples.pop().unwrap();
+        Ok([x, y])
+    }
+
+    /// Mann-Whitney test of `value` between the two levels of `group`.
+    pub fn mann_whitney(
+        &self,
+        value: &str,
+        group: &str,
+        alternative: Alternative,
+    ) -> Result<TestResult, StatustError> {
+        let [x, y] = self.two_groups(value, group)?;
+        let mut result = mann_whitney(&x, &y, alternative)?;
+        result.data = format!("{} by {}", value, group);
+        Ok(result)
+    }
+
+    /// Two-sample Kolmogorov-Smirnov test of `value` between the two
+    /// levels of `group`.
+    pub fn ks_two_sample(
+        &self,
+        value: &str,
+        group: &str,
+        alternative: Alternative,
+    ) -> Result<TestResult, StatustError> {
+        let [x, y] = self.two_groups(value, group)?;
+        let mut result = ks_two_sample(&x, &y, alternative)?;
+        result.data = format!("{} by {}", value, group);
+        Ok(result)
+    }
+
+    /// Kruskal-Wallis test of `value` across the levels of `group`.
+    pub fn kruskal_wallis(&self, value: &str, group: &str) -> Result<TestResult, StatustError> {
+        let (_, groups) = self.grouped_f64(value, group)?;
+        let mut result = kruskal_wallis(&groups)?;
+        result.data = format!("{} by {}", value, group);
+        Ok(result)
+    }
+
+    /// Mood's median test of `value` across the levels of `group`.
+    pub fn mood_median(&self, value: &str, group: &str) -> Result<TestResult, StatustError> {
+        let (_, groups) = self.grouped_f64(value, group)?;
+        let mut result = mood_median(&groups)?;
+        result.data = format!("{} by {}", value, group);
+        Ok(result)
+    }
+
+    /// Friedman test with `treatments` as columns and rows as blocks. Rows
+    /// with a null in any of the columns are skipped.
+    pub fn friedman(&self, treatments: &[&str]) -> Result<TestResult, StatustError> {
+        let values = treatments
+            .iter()
+            .map(|c| self.get_f64_col(c))
+            .collect::<Result<Vec<_>, _>>()?;
+        let blocks: Vec<Vec<f64>> = (0..self.nrows())
+            .filter_map(|r| values.iter().map(|v| v[r]).collect())
+            .collect();
+        let mut result = friedman(&blocks)?;
+        result.data = treatments.join(", ");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    // Examples from R's documentation; exact p-values checked by brute-force
+    // enumeration in Python.
+
+    #[test]
+    fn test_mann_whitney() {
+        let x = [0.80, 0.83, 1.89, 1.04, 1.45, 1.38, 1.91, 1.64, 0.73, 1.46];
+        let y = [1.15, 0.88, 0.90, 0.74, 1.21];
+        let greater = mann_whitney(&x, &y, Alternative::Greater).unwrap();
+        assert_eq!(greater.statistic, 35.0);
+        assert_close(greater.p_value, 0.1272061272, 1e-10);
+        assert!(greater.method.contains("exact"));
+        let two = mann_whitney(&x, &y, Alternative::TwoSided).unwrap();
+        assert_close(two.p_value, 0.2544122544, 1e-10);
+        let less = mann_whitney(&x, &y, Alternative::Less).unwrap();
+        assert_close(less.p_value, 0.8967698968, 1e-10);
+
+        let x = [1.0, 2.0, 2.0, 3.0, 4.0, 5.0, 5.0, 5.0, 6.0];
+        let y = [3.0, 4.0, 4.0, 6.0, 7.0, 7.0, 8.0, 9.0];
+        let ties = mann_whitney(&x, &y, Alternative::TwoSided).unwrap();
+        assert_eq!(ties.statistic, 15.0);
+        assert_close(ties.p_value, 0.0468913887, 1e-9);
+        assert!(ties.method.contains("continuity"));
+        assert!(mann_whitney(&x, &[], Alternative::TwoSided).is_err());
+
+        // A statistic at its mean gets no continuity correction, as in R.
+        let centered =
+            mann_whitney(&[1.0, 1.0, 2.0], &[1.0, 2.0, 1.0], Alternative::TwoSided).unwrap();
+        assert_eq!(centered.statistic, 4.5);
+        assert_eq!(centered.p_value, 1.0);
+        let x: Vec<f64> = (0..60).map(f64::from).collect();
+        assert_eq!(
+            mann_whitney(&x, &x, Alternative::TwoSided).unwrap().p_value,
+            1.0
+        );
+        assert!(mann_whitney(&[3.0; 60], &[3.0; 60], Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn test_signed_rank_and_sign() {
+        let x = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+        let y = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+        let paired = wilcoxon_paired(&x, &y, Alternative::Greater).unwrap();
+        assert_eq!(paired.statistic, 40.0);
+        assert_close(paired.p_value, 0.01953125, 1e-12);
+
+        let x = [4.2, 5.0, 6.1, 6.1, 3.3, 7.0, 5.8, 6.1, 4.2, 8.0];
+        let ties = wilcoxon_signed_rank(&x, 5.0, Alternative::TwoSided).unwrap();
+        assert_eq!(ties.statistic, 34.0);
+        assert_close(ties.p_value, 0.1893818448, 1e-9);
+        let centered =
+            wilcoxon_signed_rank(&[1.0, -1.0, 1.0, -1.0], 0.0, Alternative::TwoSided).unwrap();
+        assert_eq!(centered.statistic, 5.0);
+        assert_eq!(centered.p_value, 1.0);
+
+        let sign = sign_test(&x, 5.0, Alternative::TwoSided).unwrap();
+        assert_eq!(sign.statistic, 6.0);
+        assert_eq!(sign.parameters[0].1, 9.0);
+        assert_close(sign.p_value, 0.5078125, 1e-12);
+        let greater = sign_test(&x, 5.0, Alternative::Greater).unwrap();
+        assert_close(greater.p_value, 0.25390625, 1e-12);
+        assert!(sign_test(&[5.0, 5.0], 5.0, Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_k_sample() {
+        let groups = vec![
+            vec![2.9, 3.0, 2.5, 2.6, 3.2],
+            vec![3.8, 2.7, 4.0, 2.4],
+            vec![2.8, 3.4, 3.7, 2.2, 2.0],
+        ];
+        let kw = kruskal_wallis(&groups).unwrap();
+        assert_close(kw.statistic, 0.7714285714, 1e-9);
+        assert_close(kw.p_value, 0.6799647736, 1e-9);
+
+        let mood = mood_median(&groups).unwrap();
+        assert_close(mood.statistic, 0.4, 1e-12);
+        assert_close(mood.p_value, (-0.2_f64).exp(), 1e-12);
+        assert_close(mood.estimates[0].1, 2.85, 1e-12);
+
+        // R's RoundingTimes data.
+        let times = [
+            5.40, 5.50, 5.55, 5.85, 5.70, 5.75, 5.20, 5.60, 5.50, 5.55, 5.50, 5.40, 5.90, 5.85,
+            5.70, 5.45, 5.55, 5.60, 5.40, 5.40, 5.35, 5.45, 5.50, 5.35, 5.25, 5.15, 5.00, 5.85,
+            5.80, 5.70, 5.25, 5.20, 5.10, 5.65, 5.55, 5.45, 5.60, 5.35, 5.45, 5.05, 5.00, 4.95,
+            5.50, 5.50, 5.40, 5.45, 5.55, 5.50, 5.55, 5.55, 5.35, 5.45, 5.50, 5.55, 5.50, 5.45,
+            5.25, 5.65, 5.60, 5.40, 5.70, 5.65, 5.55, 6.30, 6.30, 6.25,
+        ];
+        let blocks: Vec<Vec<f64>> = times.chunks(3).map(|c| c.to_vec()).collect();
+        let fr = friedman(&blocks).unwrap();
+        assert_close(fr.statistic, 11.142857143, 1e-8);
+        assert_close(fr.p_value, 0.0038050408, 1e-9);
+        assert!(friedman(&[vec![1.0, 2.0], vec![1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_ks_two_sample() {
+        let x = [
+            0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64,
+            -0.82, 0.37, 1.77, 1.09, -1.28, 2.36, 1.31, 1.05, -0.32, -0.4, 1.06, -2.47,
+        ];
+        let y = [
+            2.2, 1.66, 1.38, 0.2, 0.36, 0.0, 0.96, 1.56, 0.44, 1.5, -0.3, 0.66, 2.31, 3.29, -0.27,
+            -0.37, 0.38, 0.7, 0.52, -0.71,
+        ];
+        let two = ks_two_sample(&x, &y, Alternative::TwoSided).unwrap();
+        assert_close(two.statistic, 0.23, 1e-12);
+        assert_close(two.p_value, 0.5286364448, 1e-9);
+        let greater = ks_two_sample(&x, &y, Alternative::Greater).unwrap();
+        assert_close(greater.p_value, 0.2687791534, 1e-9);
+        let less = ks_two_sample(&x, &y, Alternative::Less).unwrap();
+        assert_eq!(less.statistic, 0.0);
+        assert_close(less.p_value, 1.0, 1e-12);
+
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let mut df = df;
+        let setosa: Vec<DataType> = df
+            .get_col_by_name("variety")
+            .unwrap()
+            .iter()
+            .map(|v| DataType::Bool(v.to_string() == "Setosa"))
+            .collect();
+        df.set_col("setosa", setosa).unwrap();
+        let ks = df
+            .ks_two_sample("sepal.length", "setosa", Alternative::TwoSided)
+            .unwrap();
+        assert!(ks.method.starts_with("Asymptotic"));
+        assert!(ks.p_value < 1e-10);
+        let mw = df
+            .mann_whitney("sepal.length", "setosa", Alternative::Greater)
+            .unwrap();
+        assert!(mw.p_value < 1e-10);
+        assert!(
+            df.kruskal_wallis("sepal.length", "variety")
+                .unwrap()
+                .p_value
+                < 1e-10
+        );
+    }
+}
@@ -0,0 +1,571 @@
+use crate::distributions::{ContinuousDistribution, Normal};
+use crate::special::ndtri;
+use crate::*;
+
+/// Tests of the null hypothesis that a sample comes from a normal
+/// distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NormalityTest {
+    ShapiroWilk,
+    AndersonDarling,
+    Lilliefors,
+    DAgostinoPearson,
+    JarqueBera,
+}
+
+impl NormalityTest {
+    pub fn run(&self, x: &[f64]) -> Result<TestResult, StatustError> {
+        match self {
+            NormalityTest::ShapiroWilk => shapiro_wilk(x),
+            NormalityTest::AndersonDarling => anderson_darling(x),
+            NormalityTest::Lilliefors => lilliefors(x),
+            NormalityTest::DAgostinoPearson => dagostino_pearson(x),
+            NormalityTest::JarqueBera => jarque_bera(x),
+        }
+    }
+}
+
+impl DataFrame {
+    /// Runs `test` on every numeric column, ignoring nulls. Each result is
+    /// labelled with its column name. Columns the test cannot be run on,
+    /// such as constant or too short ones, are skipped.
+    pub fn normality_test(&self, test: NormalityTest) -> Result<Vec<TestResult>, StatustError> {
+        let mut results = Vec::new();
+        for name in self.numeric_col_names() {
+            let x: Vec<f64> = self.get_f64_col(&name)?.into_iter().flatten().collect();
+            if let Ok(mut result) = test.run(&x) {
+                result.data = name;
+                results.push(result);
+            }
+        }
+        Ok(results)
+    }
+}
+
+fn sorted_sample(x: &[f64], min_len: usize, method: &str) -> Result<Vec<f64>, StatustError> {
+    if x.iter().any(|v| !v.is_finite()) {
+        return Err(StatustError::invalid(format!(
+            "{} requires finite values",
+            method
+        )));
+    }
+    if x.len() < min_len {
+        return Err(StatustError::invalid(format!(
+            "{} requires at least {} values, got {}",
+            method,
+            min_len,
+            x.len()
+        )));
+    }
+    let mut sorted = x.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    if sorted[0] == sorted[sorted.len() - 1] {
+        return Err(StatustError::invalid(format!(
+            "{} requires values that are not all identical",
+            method
+        )));
+    }
+    Ok(sorted)
+}
+
+fn poly(coefs: &[f64], x: f64) -> f64 {
+    coefs.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+/// Shapiro-Wilk W test using Royston's (1995) approximation for the
+/// coefficients and p-value. Valid for 3 to 5000 values.
+pub fn shapiro_wilk(x: &[f64]) -> Result<TestResult, StatustError> {
+    const METHOD: &str = "Shapiro-Wilk normality test";
+    let x = sorted_sample(x, 3, METHOD)?;
+    let n = x.len();
+    if n > 5000 {
+        return Err(StatustError::invalid(format!(
+            "{} supports at most 5000 values, got {}",
+            METHOD, n
+        )));
+    }
+    let an = n as f64;
+    let nn2 = n / 2;
+
+    // Coefficients for the lower half of the sample, all positive.
+    let mut a = vec![0.0; nn2];
+    if n == 3 {
+        a[0] = 0.5f64.sqrt();
+    } else {
+        const C1: [f64; 6] = [0.0, 0.221157, -0.147981, -2.07119, 4.434685, -2.706056];
+        const C2: [f64; 6] = [0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633];
+        let m: Vec<f64> = (1..=nn2)
+            .map(|i| ndtri((i as f64 - 0.375) / (an + 0.25)))
+            .collect();
+        let summ2 = 2.0 * m.iter().map(|v| v * v).sum::<f64>();
+        let ssumm2 = summ2.sqrt();
+        let rsn = 1.0 / an.sqrt();
+        let a1 = poly(&C1, rsn) - m[0] / ssumm2;
+        let (first, fac) = if n > 5 {
+            let a2 = -m[1] / ssumm2 + poly(&C2, rsn);
+            a[1] = a2;
+            let fac = ((summ2 - 2.0 * m[0].powi(2) - 2.0 * m[1].powi(2))
+                / (1.0 - 2.0 * a1.powi(2) - 2.0 * a2.powi(2)))
+            .sqrt();
+            (2, fac)
+        } else {
+            (
+                1,
+                ((summ2 - 2.0 * m[0].powi(2)) / (1.0 - 2.0 * a1.powi(2))).sqrt(),
+            )
+        };
+        a[0] = a1;
+        for i in first..nn2 {
+            a[i] = -m[i] / fac;
+        }
+    }
+
+    // Scale by the range for numerical stability.
+    let range = x[n - 1] - x[0];
+    let scaled: Vec<f64> = x.iter().map(|v| v / range).collect();
+    let mean = stats::mean(&scaled);
+    let ssq: f64 = scaled.iter().map(|v| (v - mean).powi(2)).sum();
+    let num: f64 = (0..nn2)
+        .map(|i| a[i] * (scaled[n - 1 - i] - scaled[i]))
+        .sum();
+    let w = (num * num / ssq).min(1.0);
+
+    let p_value = if n == 3 {
+        const PI6: f64 = 6.0 / std::f64::consts::PI;
+        const STQR: f64 = std::f64::consts::PI / 3.0;
+        (PI6 * (w.sqrt().asin() - STQR)).max(0.0)
+    } else {
+        let y = (1.0 - w).ln();
+        let (y, m, s) = if n <= 11 {
+            let gamma = poly(&[-2.273, 0.459], an);
+            if y >= gamma {
+                return Ok(TestResult::new(METHOD, w, 1e-99, Alternative::TwoSided));
+            }
+            (
+                -(gamma - y).ln(),
+                poly(&[0.544, -0.39978, 0.025054, -6.714e-4], an),
+                poly(&[1.3822, -0.77857, 0.062767, -0.0020322], an).exp(),
+            )
+        } else {
+            let ln_n = an.ln();
+            (
+                y,
+                poly(&[-1.5861, -0.31082, -0.083751, 0.0038915], ln_n),
+                poly(&[-0.4803, -0.082676, 0.0030302], ln_n).exp(),
+            )
+        };
+        Normal::standard().sf((y - m) / s)
+    };
+    Ok(TestResult::new(METHOD, w, p_value, Alternative::TwoSided))
+}
+
+/// Standardizes sorted values and returns their normal cdf values.
+fn normal_cdf_values(x: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let mean = stats::mean(x);
+    let sd = stats::std_dev(x);
+    let normal = Normal::standard();
+    x.iter()
+        .map(|v| {
+            let z = (v - mean) / sd;
+            (normal.cdf(z), normal.sf(z))
+        })
+        .unzip()
+}
+
+/// Anderson-Darling test for normality with estimated mean and variance.
+/// The p-value uses Stephens' (1986) formulas for the adjusted statistic.
+pub fn anderson_darling(x: &[f64]) -> Result<TestResult, StatustError> {
+    const METHOD: &str = "Anderson-Darling normality test";
+    let x = sorted_sample(x, 8, METHOD)?;
+    let n = x.len();
+    let an = n as f64;
+    let (cdf, sf) = normal_cdf_values(&x);
+    let h: f64 = (0..n)
+        .map(|i| (2.0 * i as f64 + 1.0) * (cdf[i].ln() + sf[n - 1 - i].ln()))
+        .sum();
+    let a = -an - h / an;
+    let aa = (1.0 + 0.75 / an + 2.25 / (an * an)) * a;
+    let p_value = if aa < 0.2 {
+        1.0 - (-13.436 + 101.14 * aa - 223.73 * aa * aa).exp()
+    } else if aa < 0.34 {
+        1.0 - (-8.318 + 42.796 * aa - 59.938 * aa * aa).exp()
+    } else if aa < 0.6 {
+        (0.9177 - 4.279 * aa - 1.38 * aa * aa).exp()
+    } else if aa < 10.0 {
+        (1.2937 - 5.709 * aa + 0.0186 * aa * aa).exp()
+    } else {
+        3.7e-24
+    };
+    Ok(TestResult::new(METHOD, a, p_value, Alternative::TwoSided))
+}
+
+/// Kolmogorov-Smirnov statistic `D = sup |F_n(x) - F(x)|` for sorted `x`
+/// given the values of `F` at each point.
+fn ks_statistic(cdf: &[f64]) -> f64 {
+    let n = cdf.len() as f64;
+    cdf.iter()
+        .enumerate()
+        .map(|(i, p)| ((i as f64 + 1.0) / n - p).max(p - i as f64 / n))
+        .fold(0.0, f64::max)
+}
+
+/// Lilliefors (Kolmogorov-Smirnov) test for normality with estimated mean
+/// and variance, using the Dallal-Wilkinson (1986) p-value approximation.
+pub fn lilliefors(x: &[f64]) -> Result<TestResult, StatustError> {
+    const METHOD: &str = "Lilliefors (Kolmogorov-Smirnov) normality test";
+    let x = sorted_sample(x, 5, METHOD)?;
+    let an = x.len() as f64;
+    let (cdf, _) = normal_cdf_values(&x);
+    let d = ks_statistic(&cdf);
+    let (kd, nd) = if an <= 100.0 {
+        (d, an)
+    } else {
+        (d * (an / 100.0).powf(0.49), 100.0)
+    };
+    let mut p_value = (-7.01256 * kd * kd * (nd + 2.78019) + 2.99587 * kd * (nd + 2.78019).sqrt()
+        - 0.122119
+        + 0.974598 / nd.sqrt()
+        + 1.67997 / nd)
+        .exp();
+    if p_value > 0.1 {
+        let kk = (an.sqrt() - 0.01 + 0.85 / an.sqrt()) * d;
+        p_value = if kk <= 0.302 {
+            1.0
+        } else if kk <= 0.5 {
+            poly(&[2.76773, -19.828315, 80.709644, -138.55152, 81.218052], kk)
+        } else if kk <= 0.9 {
+            poly(
+                &[-4.901232, 40.662806, -97.490286, 94.029866, -32.355711],
+                kk,
+            )
+        } else if kk <= 1.31 {
+            poly(&[6.198765, -19.558097, 23.186922, -12.234627, 2.423045], kk)
+        } else {
+            0.0
+        };
+    }
+    Ok(TestResult::new(METHOD, d, p_value, Alternative::TwoSided))
+}
+
+/// One-sample Kolmogorov-Smirnov test against a fully specified
+/// distribution. The p-value is exact (Marsaglia, Tsang and Wang, 2003) for
+/// fewer than 100 values and asymptotic otherwise.
+pub fn ks_test(x: &[f64], dist: &dyn ContinuousDistribution) -> Result<TestResult, StatustError> {
+    const METHOD: &str = "One-sample Kolmogorov-Smirnov test";
+    let x = sorted_sample(x, 1, METHOD)?;
+    let cdf: Vec<f64> = x.iter().map(|v| dist.cdf(*v)).collect();
+    let d = ks_statistic(&cdf);
+    let n = x.len();
+    let p_value = if n < 100 {
+        1.0 - kolmogorov_cdf_exact(n, d)
+    } else {
+        kolmogorov_sf_asymptotic((n as f64).sqrt() * d)
+    };
+    Ok(TestResult::new(
+        METHOD,
+        d,
+        p_value.clamp(0.0, 1.0),
+        Alternative::TwoSided,
+    ))
+}
+
+/// `P(D_n < d)` for the two-sided one-sample statistic.
+pub(crate) fn kolmogorov_cdf_exact(n: usize, d: f64) -> f64 {
+    let nd = n as f64 * d;
+    if d <= 0.0 {
+        return 0.0;
+    }
+    if d >= 1.0 {
+        return 1.0;
+    }
+    let k = nd as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - nd;
+    let mut hm = vec![0.0; m * m];
+    for i in 0..m {
+        for j in 0..m {
+            if i + 1 >= j {
+                hm[i * m + j] = 1.0;
+            }
+        }
+    }
+    for i in 0..m {
+        hm[i * m] -= h.powi(i as i32 + 1);
+        hm[(m - 1) * m + i] -= h.powi((m - i) as i32);
+    }
+    if 2.0 * h - 1.0 > 0.0 {
+        hm[(m - 1) * m] += (2.0 * h - 1.0).powi(m as i32);
+    }
+    for i in 0..m {
+        for j in 0..m {
+            if i + 1 > j {
+                for g in 1..=(i + 1 - j) {
+                    hm[i * m + j] /= g as f64;
+                }
+            }
+        }
+    }
+    let (q, mut exponent) = matrix_power(&hm, m, n);
+    let mut s = q[(k - 1) * m + k - 1];
+    for i in 1..=n {
+        s = s * i as f64 / n as f64;
+        if s < 1e-140 {
+            s *= 1e140;
+            exponent -= 140;
+        }
+    }
+    s * 10f64.powi(exponent)
+}
+
+/// Raises an `m x m` matrix to the power `n`, returning the result scaled
+/// by `10^-exponent` to avoid overflow.
+fn matrix_power(a: &[f64], m: usize, n: usize) -> (Vec<f64>, i32) {
+    let multiply = |x: &[f64], y: &[f64]| {
+        let mut out = vec![0.0; m * m];
+        for i in 0..m {
+            for k in 0..m {
+                let v = x[i * m + k];
+                if v != 0.0 {
+                    for j in 0..m {
+                        out[i * m + j] += v * y[k * m + j];
+                    }
+                }
+            }
+        }
+        out
+    };
+    if n == 1 {
+        return (a.to_vec(), 0);
+    }
+    let (half, e) = matrix_power(a, m, n / 2);
+    let mut result = multiply(&half, &half);
+    let mut exponent = 2 * e;
+    if n % 2 == 1 {
+        result = multiply(a, &result);
+    }
+    if result[(m / 2) * m + m / 2] > 1e140 {
+        for v in result.iter_mut() {
+            *v *= 1e-140;
+        }
+        exponent += 140;
+    }
+    (result, exponent)
+}
+
+/// Survival function of the limiting Kolmogorov distribution.
+pub(crate) fn kolmogorov_sf_asymptotic(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < 1.0 {
+        let pi2 = std::f64::consts::PI.powi(2);
+        let w = (2.0 * std::f64::consts::PI).sqrt() / x;
+        let s: f64 = (1..=20)
+            .map(|k| (-((2 * k - 1) as f64).powi(2) * pi2 / (8.0 * x * x)).exp())
+            .sum();
+        1.0 - w * s
+    } else {
+        let s: f64 = (1..=100)
+            .map(|k| {
+                let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+                sign * (-2.0 * (k as f64).powi(2) * x * x).exp()
+            })
+            .sum();
+        (2.0 * s).clamp(0.0, 1.0)
+    }
+}
+
+fn skew_z(x: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let b2 = stats::skewness(x);
+    let y = b2 * ((n + 1.0) * (n + 3.0) / (6.0 * (n - 2.0))).sqrt();
+    let beta2 = 3.0 * (n * n + 27.0 * n - 70.0) * (n + 1.0) * (n + 3.0)
+        / ((n - 2.0) * (n + 5.0) * (n + 7.0) * (n + 9.0));
+    let w2 = -1.0 + (2.0 * (beta2 - 1.0)).sqrt();
+    let delta = 1.0 / (0.5 * w2.ln()).sqrt();
+    let alpha = (2.0 / (w2 - 1.0)).sqrt();
+    let y = if y == 0.0 { 1.0 } else { y };
+    delta * (y / alpha + ((y / alpha).powi(2) + 1.0).sqrt()).ln()
+}
+
+fn kurtosis_z(x: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let b2 = stats::kurtosis(x);
+    let e = 3.0 * (n - 1.0) / (n + 1.0);
+    let var_b2 = 24.0 * n * (n - 2.0) * (n - 3.0) / ((n + 1.0).powi(2) * (n + 3.0) * (n + 5.0));
+    let z = (b2 - e) / var_b2.sqrt();
+    let sqrt_beta1 = 6.0 * (n * n - 5.0 * n + 2.0) / ((n + 7.0) * (n + 9.0))
+        * (6.0 * (n + 3.0) * (n + 5.0) / (n * (n - 2.0) * (n - 3.0))).sqrt();
+    let a = 6.0 + 8.0 / sqrt_beta1 * (2.0 / sqrt_beta1 + (1.0 + 4.0 / sqrt_beta1.powi(2)).sqrt());
+    let term1 = 1.0 - 2.0 / (9.0 * a);
+    let denom = 1.0 + z * (2.0 / (a - 4.0)).sqrt();
+    let term2 = denom.signum() * ((1.0 - 2.0 / a) / denom.abs()).cbrt();
+    (term1 - term2) / (2.0 / (9.0 * a)).sqrt()
+}
+
+/// D'Agostino-Pearson omnibus K² test combining the skewness and kurtosis
+/// z-scores (D'Agostino, Belanger and D'Agostino, 1990). The kurtosis
+/// approximation is poor below 20 values.
+pub fn dagostino_pearson(x: &[f64]) -> Result<TestResult, StatustError> {
+    const METHOD: &str = "D'Agostino-Pearson K² normality test";
+    let x = sorted_sample(x, 8, METHOD)?;
+    let k2 = skew_z(&x).powi(2) + kurtosis_z(&x).powi(2);
+    let mut result = TestResult::new(METHOD, k2, (-k2 / 2.0).exp(), Alternative::TwoSided);
+    result.parameters.push(("df".to_string(), 2.0));
+    Ok(result)
+}
+
+/// Jarque-Bera test based on the sample skewness and kurtosis, with the
+/// asymptotic chi-squared(2) p-value.
+pub fn jarque_bera(x: &[f64]) -> Result<TestResult, StatustError> {
+    const METHOD: &str = "Jarque-Bera normality test";
+    let x = sorted_sample(x, 2, METHOD)?;
+    let n = x.len() as f64;
+    let s = stats::skewness(&x);
+    let k = stats::kurtosis(&x);
+    let jb = n / 6.0 * (s * s + (k - 3.0).powi(2) / 4.0);
+    let mut result = TestResult::new(METHOD, jb, (-jb / 2.0).exp(), Alternative::TwoSided);
+    result.parameters.push(("df".to_string(), 2.0));
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_rel_close;
+
+    fn iris_col(name: &str) -> Vec<f64> {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        df.get_f64_col(name)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    // Reference values from R's shapiro.test and the nortest package.
+
+    #[test]
+    fn test_shapiro_wilk() {
+        let r = shapiro_wilk(&iris_col("sepal.length")).unwrap();
+        assert_rel_close(r.statistic, 0.97609, 1e-4);
+        assert_rel_close(r.p_value, 0.01018, 1e-3);
+        let r = shapiro_wilk(&iris_col("sepal.width")).unwrap();
+        assert_rel_close(r.statistic, 0.98492, 1e-4);
+        assert_rel_close(r.p_value, 0.1012, 1e-3);
+        let r = shapiro_wilk(&iris_col("petal.length")).unwrap();
+        assert_rel_close(r.statistic, 0.87627, 1e-4);
+        assert_rel_close(r.p_value, 7.412e-10, 1e-3);
+
+        let r = shapiro_wilk(&[1.0, 2.0, 4.0]).unwrap();
+        assert_rel_close(r.statistic, 0.9642857, 1e-6);
+        assert_rel_close(r.p_value, 0.6368868, 1e-6);
+        assert!(shapiro_wilk(&[1.0, 2.0]).is_err());
+        assert!(shapiro_wilk(&[3.0; 10]).is_err());
+    }
+
+    #[test]
+    fn test_anderson_darling_lilliefors() {
+        let x = iris_col("sepal.length");
+        let r = anderson_darling(&x).unwrap();
+        assert_rel_close(r.statistic, 0.8892, 1e-3);
+        assert_rel_close(r.p_value, 0.02251, 1e-3);
+        let r = lilliefors(&x).unwrap();
+        assert_rel_close(r.statistic, 0.088654, 1e-4);
+        assert_rel_close(r.p_value, 0.005788, 1e-3);
+    }
+
+    #[test]
+    fn test_moment_tests() {
+        let x = iris_col("sepal.length");
+        let n = x.len() as f64;
+        let (s, k) = (stats::skewness(&x), stats::kurtosis(&x));
+        let r = jarque_bera(&x).unwrap();
+        assert_rel_close(
+            r.statistic,
+            n / 6.0 * (s * s + (k - 3.0).powi(2) / 4.0),
+            1e-12,
+        );
+        assert_rel_close(r.p_value, (-r.statistic / 2.0).exp(), 1e-12);
+
+        // A large normal sample should not be rejected, a skewed one should.
+        let mut rng = Xoshiro256::seed_from_u64(7);
+        let normal = Normal::new(5.0, 2.0).unwrap().sample_n(2000, &mut rng);
+        let skewed: Vec<f64> = normal.iter().map(|v| v.exp()).collect();
+        for test in [
+            NormalityTest::ShapiroWilk,
+            NormalityTest::AndersonDarling,
+            NormalityTest::Lilliefors,
+            NormalityTest::DAgostinoPearson,
+            NormalityTest::JarqueBera,
+        ] {
+            assert!(test.run(&normal).unwrap().p_value > 0.01, "{:?}", test);
+            assert!(test.run(&skewed).unwrap().p_value < 1e-6, "{:?}", test);
+        }
+    }
+
+    // Reference values computed in 50-digit arithmetic with mpmath from the
+    // formulas of R's fBasics::dagoTest.
+
+    #[test]
+    fn test_dagostino_pearson() {
+        let r = dagostino_pearson(&iris_col("sepal.length")).unwrap();
+        assert_rel_close(r.statistic, 5.73558691188, 1e-10);
+        assert_rel_close(r.p_value, 0.05682417339, 1e-9);
+        assert_eq!(r.parameters, [("df".to_string(), 2.0)]);
+        let r = dagostino_pearson(&iris_col("petal.length")).unwrap();
+        assert_rel_close(r.statistic, 221.6872968246, 1e-10);
+        assert_rel_close(r.p_value, 7.264657444303e-49, 1e-8);
+
+        let squares: Vec<f64> = (1..=20).map(|i| (i * i) as f64).collect();
+        let x = sorted_sample(&squares, 8, "").unwrap();
+        assert_rel_close(skew_z(&x), 1.319732572582, 1e-10);
+        assert_rel_close(kurtosis_z(&x), -0.8792061017083, 1e-10);
+        let r = dagostino_pearson(&squares).unwrap();
+        assert_rel_close(r.statistic, 2.514697432414, 1e-10);
+        assert_rel_close(r.p_value, 0.2844070716501, 1e-10);
+        assert!(dagostino_pearson(&squares[..7]).is_err());
+    }
+
+    #[test]
+    fn test_ks() {
+        // For a single value D = max(U, 1 - U), so P(D < d) = 2d - 1.
+        assert_rel_close(kolmogorov_cdf_exact(1, 0.75), 0.5, 1e-12);
+        let exact = 1.0 - kolmogorov_cdf_exact(90, 0.12);
+        let asymptotic = kolmogorov_sf_asymptotic(90f64.sqrt() * 0.12);
+        assert!((exact - asymptotic).abs() < 0.02);
+        assert_rel_close(kolmogorov_sf_asymptotic(1.3580986), 0.05, 1e-5);
+
+        let x = [0.1, 0.4, 0.7];
+        let uniform = distributions::Uniform::new(0.0, 1.0).unwrap();
+        let r = ks_test(&x, &uniform).unwrap();
+        assert_rel_close(r.statistic, 0.3, 1e-12);
+    }
+
+    #[test]
+    fn test_dataframe_normality() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let results = df.normality_test(NormalityTest::ShapiroWilk).unwrap();
+        let names: Vec<&str> = results.iter().map(|r| r.data.as_str()).collect();
+        assert_eq!(
+            names,
+            ["sepal.length", "sepal.width", "petal.length", "petal.width"]
+        );
+        let printed = results[0].to_string();
+        assert!(printed.contains("Shapiro-Wilk normality test"));
+        assert!(printed.contains("Data: sepal.length"));
+        assert!(printed.contains("P-value: 0.0102"));
+
+        let mut df = df;
+        df.set_col("constant", vec![DataType::Int(1); 150]).unwrap();
+        let mut short = vec![DataType::None; 150];
+        short[0] = DataType::Float(1.0);
+        short[1] = DataType::Float(2.0);
+        df.set_col("short", short).unwrap();
+        let results = df.normality_test(NormalityTest::ShapiroWilk).unwrap();
+        assert_eq!(results.len(), 4);
+        assert!(results
+            .iter()
+            .all(|r| r.data != "constant" && r.data != "short"));
+    }
+}
@@ -0,0 +1,237 @@
+use crate::distributions::{ContinuousDistribution, FisherF, StudentT};
+use crate::error::check_confidence;
+use crate::*;
+
+impl DataFrame {
+    /// Fits a linear model by ordinary least squares, e.g.
+    /// `df.ols("y ~ x1 + x2 + C(group) + x1:x2", 0.95)`. Rows with a null
+    /// in any variable of the formula are dropped.
+    pub fn ols(&self, formula: &str, confidence: f64) -> Result<OlsResult, StatustError> {
+        let formula = Formula::parse(formula)?;
+        let design = formula.design(self)?;
+        ols(formula, design, confidence)
+    }
+}
+
+/// Fits `design.y` on `design.x` through a Householder QR decomposition of
+/// the design matrix, without forming `X^T X`.
+pub fn ols(formula: Formula, design: Design, confidence: f64) -> Result<OlsResult, StatustError> {
+    check_confidence(confidence)?;
+    let (x, y) = (&design.x, &design.y);
+    let (n, p) = (x.nrows(), x.ncols());
+    if n <= p {
+        return Err(StatustError::invalid(format!(
+            "OLS needs more observations ({}) than coefficients ({})",
+            n, p
+        )));
+    }
+    let qr = x.qr()?;
+    for j in 0..p {
+        let norm = x.col(j).iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm == 0.0 || qr.r[(j, j)].abs() <= 1e-7 * norm {
+            return Err(StatustError::invalid(format!(
+                "design column {} is collinear with earlier columns",
+                design.columns[j]
+            )));
+        }
+    }
+    let beta = qr.solve(y)?;
+    let fitted = x.mul_vec(&beta)?;
+    let residuals: Vec<f64> = y.iter().zip(&fitted).map(|(a, b)| a - b).collect();
+    let rss: f64 = residuals.iter().map(|r| r * r).sum();
+    let df_residual = (n - p) as f64;
+    let sigma2 = rss / df_residual;
+
+    let r_inv = qr.r.inverse()?;
+    let cov_unscaled = r_inv.matmul(&r_inv.transpose())?;
+    let dist = StudentT::new(df_residual)?;
+    let half_width = dist.quantile(0.5 + confidence / 2.0);
+    let coefficients = beta
+        .iter()
+        .enumerate()
+        .map(|(j, &estimate)| {
+            let std_error = (sigma2 * cov_unscaled[(j, j)]).sqrt();
+            let t = estimate / std_error;
+            Coefficient {
+                name: design.columns[j].clone(),
+                estimate,
+                std_error,
+                statistic: t,
+                p_value: Alternative::TwoSided.p_value(&dist, t),
+                lower: estimate - half_width * std_error,
+                upper: estimate + half_width * std_error,
+            }
+        })
+        .collect();
+
+    // Without an intercept, R-squared and the F-test compare against the
+    // model y = 0, as R does.
+    let intercept = usize::from(formula.intercept);
+    let tss = if formula.intercept {
+        let mean = stats::mean(y);
+        y.iter().map(|v| (v - mean).powi(2)).sum::<f64>()
+    } else {
+        y.iter().map(|v| v * v).sum::<f64>()
+    };
+    let r_squared = 1.0 - rss / tss;
+    let adj_r_squared = 1.0 - (1.0 - r_squared) * (n - intercept) as f64 / df_residual;
+    let f_test = if p > intercept {
+        let df_model = (p - intercept) as f64;
+        let f = (tss - rss) / df_model / sigma2;
+        let mut test = TestResult::new(
+            "F-test of overall significance",
+            f,
+            FisherF::new(df_model, df_residual)?.sf(f),
+            Alternative::Greater,
+        );
+        test.data = formula.to_string();
+        test.parameters = vec![
+            ("num df".to_string(), df_model),
+            ("denom df".to_string(), df_residual),
+        ];
+        Some(test)
+    } else {
+        None
+    };
+
+    let nf = n as f64;
+    let log_likelihood = -nf / 2.0 * ((2.0 * std::f64::consts::PI).ln() + (rss / nf).ln() + 1.0);
+    // The residual variance counts as a parameter.
+    let k = (p + 1) as f64;
+    Ok(OlsResult {
+        formula,
+        nobs: n,
+        confidence,
+        coefficients,
+        sigma: sigma2.sqrt(),
+        df_residual,
+        r_squared,
+        adj_r_squared,
+        f_test,
+        log_likelihood,
+        aic: -2.0 * log_likelihood + 2.0 * k,
+        bic: -2.0 * log_likelihood + nf.ln() * k,
+        fitted,
+        residuals,
+        cov_unscaled,
+        design,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    // Reference values from the normal equations solved in 50-digit
+    // arithmetic with mpmath on the f32 values the CSV reader stores; model 1
+    // agrees with R's lm(Sepal.Length ~ Sepal.Width + Species) to about 1e-7.
+
+    #[test]
+    fn test_iris_main_effects() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let fit = df
+            .ols("sepal.length ~ sepal.width + variety", 0.95)
+            .unwrap();
+        assert_eq!(fit.nobs, 150);
+        let names: Vec<&str> = fit.coefficients.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "(Intercept)",
+                "sepal.width",
+                "variety[Versicolor]",
+                "variety[Virginica]"
+            ]
+        );
+        let c = fit.coef("sepal.width").unwrap();
+        assert_close(c.estimate, 0.803560812711, 1e-9);
+        assert_close(c.std_error, 0.106338976269, 1e-9);
+        assert_close(c.statistic, 7.55659722245, 1e-8);
+        assert_close(c.p_value, 4.18735744e-12, 1e-19);
+        assert_close(c.lower, 0.593398239048, 1e-9);
+        assert_close(c.upper, 1.01372338637, 1e-9);
+        assert_close(
+            fit.coef("(Intercept)").unwrap().estimate,
+            2.25139353248,
+            1e-9,
+        );
+        assert_close(
+            fit.coef("variety[Virginica]").unwrap().estimate,
+            1.94681661932,
+            1e-9,
+        );
+        assert_close(fit.r_squared, 0.725906606997, 1e-10);
+        assert_close(fit.adj_r_squared, 0.720274550976, 1e-10);
+        assert_close(fit.sigma, 0.437956525942, 1e-10);
+        let f = fit.f_test.as_ref().unwrap();
+        assert_close(f.statistic, 128.888385403, 1e-7);
+        assert_close(f.p_value, 7.664378017e-41, 1e-48);
+        assert_close(fit.log_likelihood, -86.968285146, 1e-8);
+        assert_close(fit.aic, 183.936570292, 1e-8);
+        assert_close(fit.bic, 198.989746762, 1e-8);
+        assert_close(fit.residuals[0], 0.0361435276671, 1e-10);
+        assert_close(fit.fitted[0] + fit.residuals[0], 5.1_f32 as f64, 1e-12);
+        let text = fit.to_string();
+        assert!(text.contains("variety[Versicolor]") && text.contains("97.5%"));
+    }
+
+    #[test]
+    fn test_iris_interaction() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let fit = df
+            .ols("sepal.length ~ sepal.width*petal.length + variety", 0.9)
+            .unwrap();
+        assert_eq!(
+            fit.formula.to_string(),
+            "sepal.length ~ sepal.width + petal.length + variety + sepal.width:petal.length"
+        );
+        let c = fit.coef("sepal.width:petal.length").unwrap();
+        assert_close(c.estimate, -0.0846858541756, 1e-9);
+        assert_close(c.std_error, 0.0400452768328, 1e-9);
+        assert_close(c.p_value, 0.03617340033, 1e-9);
+        assert_close(c.lower, -0.150980977245, 1e-9);
+        assert_close(c.upper, -0.0183907311063, 1e-9);
+        assert_close(
+            fit.coef("variety[Versicolor]").unwrap().estimate,
+            -0.955540189788,
+            1e-9,
+        );
+        assert_close(fit.r_squared, 0.867426107675, 1e-10);
+        assert_close(fit.adj_r_squared, 0.862822847525, 1e-10);
+        assert_close(fit.f_test.as_ref().unwrap().statistic, 188.437342096, 1e-6);
+        assert_close(fit.aic, 78.9872606745, 1e-8);
+        assert_close(fit.bic, 100.061707733, 1e-8);
+    }
+
+    #[test]
+    fn test_no_intercept_and_errors() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let fit = df.ols("sepal.length ~ petal.length - 1", 0.95).unwrap();
+        assert_close(fit.coefficients[0].estimate, 1.34887773131, 1e-9);
+        assert_close(fit.r_squared, 0.899559956173, 1e-10);
+        assert_close(fit.adj_r_squared, 0.898885861919, 1e-10);
+        assert_close(fit.f_test.unwrap().statistic, 1334.47207272, 1e-6);
+        assert_close(fit.bic, 623.526883294, 1e-8);
+
+        assert!(df.ols("sepal.length ~ 1", 0.95).unwrap().f_test.is_none());
+        assert!(df.ols("sepal.length ~ variety - 1", 1.0).is_err());
+        assert!(df.ols("variety ~ sepal.width", 0.95).is_err());
+        let mut df = df;
+        let doubled: Vec<DataType> = df
+            .get_f64_col("sepal.width")
+            .unwrap()
+            .iter()
+            .map(|v| DataType::Float((v.unwrap() * 2.0) as f32))
+            .collect();
+        df.set_col("double", doubled).unwrap();
+        let err = df.ols("sepal.length ~ sepal.width + double", 0.95);
+        assert!(matches!(err, Err(StatustError::InvalidArgument(m)) if m.contains("double")));
+
+        let mut width = df.get_col_by_name("sepal.width").unwrap();
+        width[3] = DataType::Float(f32::NAN);
+        df.set_col("sepal.width", width).unwrap();
+        let err = df.ols("sepal.length ~ sepal.width", 0.95);
+        assert!(matches!(err, Err(StatustError::InvalidArgument(m)) if m.contains("row 3")));
+    }
+}
@@ -0,0 +1,978 @@
+use crate::linalg::Matrix;
+use crate::*;
+use std::fmt;
+
+const MAX_SWEEPS: usize = 100_000;
+const MAX_OUTER: usize = 100;
+const PATH_LENGTH: usize = 100;
+
+/// Penalty of a regularized regression, in the parametrization of glmnet:
+/// `lambda * (alpha * |b|_1 + (1 - alpha) / 2 * |b|_2^2)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Penalty {
+    Ridge,
+    Lasso,
+    /// Mixing weight `alpha` in `[0, 1]` of the L1 penalty.
+    ElasticNet(f64),
+}
+
+impl fmt::Display for Penalty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Penalty::Ridge => write!(f, "ridge"),
+            Penalty::Lasso => write!(f, "lasso"),
+            Penalty::ElasticNet(alpha) => write!(f, "elastic net (alpha = {})", alpha),
+        }
+    }
+}
+
+impl Penalty {
+    pub fn alpha(&self) -> f64 {
+        match self {
+            Penalty::Ridge => 0.0,
+            Penalty::Lasso => 1.0,
+            Penalty::ElasticNet(alpha) => *alpha,
+        }
+    }
+}
+
+/// Design columns centered and scaled to unit variance (divisor n), with
+/// the intercept column removed.
+struct Standardized {
+    x: Matrix,
+    means: Vec<f64>,
+    scales: Vec<f64>,
+}
+
+fn standardize(design: &Design) -> Result<Standardized, StatustError> {
+    let Some(intercept) = design.columns.iter().position(|c| c == "(Intercept)") else {
+        return Err(StatustError::invalid(
+            "penalized regression needs a model with an intercept".to_string(),
+        ));
+    };
+    let cols: Vec<usize> = (0..design.x.ncols()).filter(|&j| j != intercept).collect();
+    let rows: Vec<usize> = (0..design.x.nrows()).collect();
+    let mut x = design.x.select(&rows, &cols);
+    let n = x.nrows() as f64;
+    let (mut means, mut scales) = (Vec::new(), Vec::new());
+    for j in 0..x.ncols() {
+        let col = x.col(j);
+        let mean = col.iter().sum::<f64>() / n;
+        let scale = (col.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+        for i in 0..x.nrows() {
+            // Constant columns stay at zero and get a zero coefficient.
+            x[(i, j)] = if scale > 0.0 {
+                (x[(i, j)] - mean) / scale
+            } else {
+                0.0
+            };
+        }
+        means.push(mean);
+        scales.push(scale);
+    }
+    Ok(Standardized { x, means, scales })
+}
+
+/// Coordinate descent for the weighted elastic net
+/// `1/(2n) sum w (z - b0 - x b)^2 + lambda * penalty(b)` on standardized
+/// columns. The unpenalized intercept is profiled out by weighted
+/// centering. When there are no more columns than rows, sweeps use the
+/// weighted Gram matrix so that each costs `O(p^2)`; otherwise they update
+/// the residuals at `O(n)` per coordinate and never form the `p x p`
+/// matrix.
+struct CoordinateDescent {
+    w: Vec<f64>,
+    z_mean: f64,
+    means: Vec<f64>,
+    /// Columns centered at their weighted means.
+    cols: Vec<Vec<f64>>,
+    zc: Vec<f64>,
+    /// Weighted column variances `x_j' W x_j / n`.
+    variances: Vec<f64>,
+    /// Weighted Gram matrix and `X' W z / n`, for covariance updates.
+    gram: Option<(Matrix, Vec<f64>)>,
+    tolerance: f64,
+}
+
+impl CoordinateDescent {
+    fn new(x: &Matrix, z: &[f64], w: &[f64]) -> CoordinateDescent {
+        let (n, p) = (x.nrows(), x.ncols());
+        let w_sum: f64 = w.iter().sum();
+        let weighted_mean = |v: &[f64]| v.iter().zip(w).map(|(v, w)| v * w).sum::<f64>() / w_sum;
+        let z_mean = weighted_mean(z);
+        let means: Vec<f64> = (0..p).map(|j| weighted_mean(&x.col(j))).collect();
+        let cols: Vec<Vec<f64>> = (0..p)
+            .map(|j| x.col(j).iter().map(|v| v - means[j]).collect())
+            .collect();
+        let zc: Vec<f64> = z.iter().map(|v| v - z_mean).collect();
+        let mut solver = CoordinateDescent {
+            w: w.to_vec(),
+            z_mean,
+            means,
+            cols,
+            zc,
+            variances: Vec::new(),
+            gram: None,
+            tolerance: 0.0,
+        };
+        solver.variances = solver.cols.iter().map(|c| solver.inner(c, c)).collect();
+        solver.tolerance = 1e-22 * solver.inner(&solver.zc, &solver.zc).max(f64::MIN_POSITIVE);
+        if p <= n {
+            let mut gram = Matrix::zeros(p, p);
+            for j in 0..p {
+                for k in 0..=j {
+                    gram[(j, k)] = solver.inner(&solver.cols[j], &solver.cols[k]);
+                    gram[(k, j)] = gram[(j, k)];
+                }
+            }
+            let c = solver
+                .cols
+                .iter()
+                .map(|col| solver.inner(col, &solver.zc))
+                .collect();
+            solver.gram = Some((gram, c));
+        }
+        solver
+    }
+
+    /// Weighted inner product `a' W b / n`.
+    fn inner(&self, a: &[f64], b: &[f64]) -> f64 {
+        a.iter()
+            .zip(b)
+            .zip(&self.w)
+            .map(|((a, b), w)| a * b * w)
+            .sum::<f64>()
+            / self.w.len() as f64
+    }
+
+    /// Solves at `lambda` from the warm start `b0`, `beta`, updating them in
+    /// place.
+    fn solve(
+        &self,
+        lambda: f64,
+        alpha: f64,
+        b0: &mut f64,
+        beta: &mut [f64],
+    ) -> Result<(), StatustError> {
+        // `q = gram * beta` for covariance updates, the centered residuals
+        // `zc - X beta` otherwise; kept up to date as coefficients change.
+        let mut q = match &self.gram {
+            Some((gram, _)) => gram.mul_vec(beta)?,
+            None => {
+                let mut r = self.zc.clone();
+                for (col, b) in self.cols.iter().zip(beta.iter()) {
+                    for (r, x) in r.iter_mut().zip(col) {
+                        *r -= x * b;
+                    }
+                }
+                r
+            }
+        };
+        for _ in 0..MAX_SWEEPS {
+            let mut max_change = 0.0_f64;
+            for j in 0..beta.len() {
+                let v = self.variances[j];
+                if v == 0.0 {
+                    continue;
+                }
+                let old = beta[j];
+                let g = match &self.gram {
+                    Some((_, c)) => c[j] - q[j],
+                    None => self.inner(&self.cols[j], &q),
+                } + v * old;
+                let new = soft_threshold(g, lambda * alpha) / (v + lambda * (1.0 - alpha));
+                if new != old {
+                    let delta = new - old;
+                    match &self.gram {
+                        Some((gram, _)) => {
+                            for (k, q) in q.iter_mut().enumerate() {
+                                *q += gram[(k, j)] * delta;
+                            }
+                        }
+                        None => {
+                            for (r, x) in q.iter_mut().zip(&self.cols[j]) {
+                                *r -= x * delta;
+                            }
+                        }
+                    }
+                    beta[j] = new;
+                    max_change = max_change.max(v * delta * delta);
+                }
+            }
+            if max_change <= self.tolerance {
+                *b0 = self.z_mean
+                    - self
+                        .means
+                        .iter()
+                        .zip(beta.iter())
+                        .map(|(m, b)| m * b)
+                        .sum::<f64>();
+                return Ok(());
+            }
+        }
+        Err(StatustError::invalid(format!(
+            "coordinate descent did not converge in {} sweeps",
+            MAX_SWEEPS
+        )))
+    }
+}
+
+fn soft_threshold(g: f64, t: f64) -> f64 {
+    if g > t {
+        g - t
+    } else if g < -t {
+        g + t
+    } else {
+        0.0
+    }
+}
+
+fn sigmoid(eta: f64) -> f64 {
+    1.0 / (1.0 + (-eta).exp())
+}
+
+/// Deviance of the fitted linear predictor: the residual sum of squares
+/// for the Gaussian family, minus twice the log-likelihood for the
+/// binomial.
+fn deviance(family: Family, y: &[f64], eta: &[f64]) -> f64 {
+    y.iter()
+        .zip(eta)
+        .map(|(y, eta)| match family {
+            Family::Binomial => {
+                // log(1 + e^eta) - y * eta, computed without overflow.
+                2.0 * (eta.max(0.0) + (-eta.abs()).exp().ln_1p() - y * eta)
+            }
+            _ => (y - eta).powi(2),
+        })
+        .sum()
+}
+
+/// Fits the penalized binomial model at one lambda from the warm start
+/// `b0`, `beta`, with IRLS around coordinate descent.
+fn fit_binomial(
+    x: &Matrix,
+    y: &[f64],
+    lambda: f64,
+    alpha: f64,
+    b0: &mut f64,
+    beta: &mut [f64],
+) -> Result<(), StatustError> {
+    let n = y.len();
+    for _ in 0..MAX_OUTER {
+        let eta: Vec<f64> = x.mul_vec(beta)?.iter().map(|e| e + *b0).collect();
+        let mut w = vec![0.0; n];
+        let mut z = vec![0.0; n];
+        for i in 0..n {
+            let p = sigmoid(eta[i]);
+            // Bounded away from zero as in glmnet, so that near-separated
+            // observations keep a finite working response.
+            w[i] = (p * (1.0 - p)).max(1e-5);
+            z[i] = eta[i] + (y[i] - p) / w[i];
+        }
+        let (old_b0, old_beta) = (*b0, beta.to_vec());
+        CoordinateDescent::new(x, &z, &w).solve(lambda, alpha, b0, beta)?;
+        let settled = std::iter::once((*b0, old_b0))
+            .chain(beta.iter().copied().zip(old_beta))
+            .all(|(b, o)| (b - o).abs() <= 1e-9 * (1.0 + b.abs()));
+        if settled {
+            return Ok(());
+        }
+    }
+    Err(StatustError::invalid(format!(
+        "penalized IRLS did not converge in {} iterations",
+        MAX_OUTER
+    )))
+}
+
+/// The smallest lambda at which every coefficient is zero, with alpha
+/// floored at 0.001 for ridge as in glmnet.
+fn lambda_max(x: &Matrix, y: &[f64], alpha: f64) -> f64 {
+    let n = y.len() as f64;
+    let mean = y.iter().sum::<f64>() / n;
+    (0..x.ncols())
+        .map(|j| {
+            let g: f64 = (0..x.nrows()).map(|i| x[(i, j)] * (y[i] - mean)).sum();
+            (g / n).abs()
+        })
+        .fold(0.0, f64::max)
+        / alpha.max(1e-3)
+}
+
+struct PathFit {
+    intercepts: Vec<f64>,
+    /// Standardized-scale coefficients, one row per lambda.
+    beta: Vec<Vec<f64>>,
+}
+
+fn fit_path(
+    x: &Matrix,
+    y: &[f64],
+    family: Family,
+    alpha: f64,
+    lambdas: &[f64],
+) -> Result<PathFit, StatustError> {
+    let mean = y.iter().sum::<f64>() / y.len() as f64;
+    let mut b0 = match family {
+        Family::Binomial => (mean / (1.0 - mean)).ln(),
+        _ => mean,
+    };
+    let mut beta = vec![0.0; x.ncols()];
+    let mut path = PathFit {
+        intercepts: Vec::new(),
+        beta: Vec::new(),
+    };
+    // The Gaussian problem has unit weights, so its solver (and Gram
+    // matrix) is shared by the whole path.
+    let gaussian =
+        (family != Family::Binomial).then(|| CoordinateDescent::new(x, y, &vec![1.0; y.len()]));
+    for &lambda in lambdas {
+        match &gaussian {
+            Some(solver) => solver.solve(lambda, alpha, &mut b0, &mut beta)?,
+            None => fit_binomial(x, y, lambda, alpha, &mut b0, &mut beta)?,
+        }
+        path.intercepts.push(b0);
+        path.beta.push(beta.clone());
+    }
+    Ok(path)
+}
+
+fn check_arguments(
+    design: &Design,
+    family: Family,
+    penalty: Penalty,
+    lambdas: Option<&[f64]>,
+) -> Result<(), StatustError> {
+    let alpha = penalty.alpha();
+    if alpha.is_nan() || !(0.0..=1.0).contains(&alpha) {
+        return Err(StatustError::invalid(format!(
+            "alpha must be in [0, 1], got {}",
+            alpha
+        )));
+    }
+    match family {
+        Family::Gaussian => {
+            if design.response_level.is_some() {
+                return Err(StatustError::invalid(
+                    "the gaussian family needs a numeric response".to_string(),
+                ));
+            }
+        }
+        Family::Binomial => {
+            if design.y.iter().any(|v| *v != 0.0 && *v != 1.0) {
+                return Err(StatustError::invalid(
+                    "binomial response must be 0/1 or have two levels".to_string(),
+                ));
+            }
+            if design.y.iter().all(|v| *v == design.y[0]) {
+                return Err(StatustError::invalid(
+                    "binomial response has a single level".to_string(),
+                ));
+            }
+        }
+        _ => {
+            return Err(StatustError::invalid(format!(
+                "penalized regression supports the gaussian and binomial families, got {}",
+                family
+            )))
+        }
+    }
+    if let Some(lambdas) = lambdas {
+        if lambdas.is_empty() {
+            return Err(StatustError::invalid("lambda grid is empty".to_string()));
+        }
+        if let Some(bad) = lambdas.iter().find(|l| l.is_nan() || **l < 0.0) {
+            return Err(StatustError::invalid(format!(
+                "lambdas must be non-negative, got {}",
+                bad
+            )));
+        }
+    }
+    if design.x.nrows() < 2 {
+        return Err(StatustError::invalid(
+            "penalized regression needs at least two observations".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The decreasing lambda grid: the given values sorted, or 100 values
+/// log-spaced from `lambda_max` down to 1e-4 of it (1e-2 when there are
+/// more columns than observations).
+fn lambda_grid(std: &Standardized, y: &[f64], alpha: f64, lambdas: Option<&[f64]>) -> Vec<f64> {
+    if let Some(lambdas) = lambdas {
+        let mut grid = lambdas.to_vec();
+        grid.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        return grid;
+    }
+    let max = lambda_max(&std.x, y, alpha);
+    let ratio: f64 = if std.x.nrows() > std.x.ncols() {
+        1e-4
+    } else {
+        1e-2
+    };
+    (0..PATH_LENGTH)
+        .map(|k| max * ratio.powf(k as f64 / (PATH_LENGTH - 1) as f64))
+        .collect()
+}
+
+impl DataFrame {
+    /// Fits a ridge, lasso or elastic net regularization path, e.g.
+    /// `df.penalized("y ~ x1 + x2 + group", Family::Gaussian, Penalty::Lasso, None)`.
+    /// Only the gaussian (linear) and binomial (logistic) families are
+    /// supported. Rows with a null in any variable of the formula are
+    /// dropped.
+    pub fn penalized(
+        &self,
+        formula: &str,
+        family: Family,
+        penalty: Penalty,
+        lambdas: Option<&[f64]>,
+    ) -> Result<PenalizedPath, StatustError> {
+        let formula = Formula::parse(formula)?;
+        let design = formula.design(self)?;
+        penalized(formula, design, family, penalty, lambdas)
+    }
+
+    /// Chooses lambda by `k`-fold cross-validation of the regularization
+    /// path; see [`DataFrame::penalized`].
+    pub fn cv_penalized<R: Rng>(
+        &self,
+        formula: &str,
+        family: Family,
+        penalty: Penalty,
+        lambdas: Option<&[f64]>,
+        k: usize,
+        rng: &mut R,
+    ) -> Result<PenalizedCv, StatustError> {
+        let formula = Formula::parse(formula)?;
+        let design = formula.design(self)?;
+        cv_penalized(formula, design, family, penalty, lambdas, k, rng)
+    }
+}
+
+/// Fits the regularization path by cyclic coordinate descent with warm
+/// starts, minimizing `deviance / (2n) + penalty` on standardized columns.
+/// The intercept is not penalized and coefficients are reported on the
+/// original scale.
+pub fn penalized(
+    formula: Formula,
+    design: Design,
+    family: Family,
+    penalty: Penalty,
+    lambdas: Option<&[f64]>,
+) -> Result<PenalizedPath, StatustError> {
+    check_arguments(&design, family, penalty, lambdas)?;
+    let std = standardize(&design)?;
+    let alpha = penalty.alpha();
+    let y = &design.y;
+    let lambdas = lambda_grid(&std, y, alpha, lambdas);
+    let fit = fit_path(&std.x, y, family, alpha, &lambdas)?;
+
+    let p = std.x.ncols();
+    let mut coefficients = Matrix::zeros(lambdas.len(), p);
+    let mut intercepts = Vec::new();
+    let mut df = Vec::new();
+    let mut deviance_ratio = Vec::new();
+    let null_eta = match family {
+        Family::Binomial => {
+            let mean = stats::mean(y);
+            (mean / (1.0 - mean)).ln()
+        }
+        _ => stats::mean(y),
+    };
+    let null_deviance = deviance(family, y, &vec![null_eta; y.len()]);
+    for (l, beta) in fit.beta.iter().enumerate() {
+        let mut b0 = fit.intercepts[l];
+        for j in 0..p {
+            if std.scales[j] > 0.0 {
+                coefficients[(l, j)] = beta[j] / std.scales[j];
+                b0 -= coefficients[(l, j)] * std.means[j];
+            }
+        }
+        intercepts.push(b0);
+        df.push(beta.iter().filter(|b| **b != 0.0).count());
+        let eta: Vec<f64> = std
+            .x
+            .mul_vec(beta)?
+            .iter()
+            .map(|e| e + fit.intercepts[l])
+            .collect();
+        deviance_ratio.push(1.0 - deviance(family, y, &eta) / null_deviance);
+    }
+    Ok(PenalizedPath {
+        formula,
+        family,
+        penalty,
+        nobs: y.len(),
+        columns: design
+            .columns
+            .iter()
+            .filter(|c| *c != "(Intercept)")
+            .cloned()
+            .collect(),
+        lambdas,
+        intercepts,
+        coefficients,
+        df,
+        deviance_ratio,
+        null_deviance,
+    })
+}
+
+/// Cross-validates the regularization path over `k` folds of the model
+/// rows. Every fold is fitted on the lambda grid of the full data and
+/// scored by mean squared error (gaussian) or deviance per observation
+/// (binomial). `lambda_1se` is the largest lambda whose error is within
+/// one standard error of the minimum.
+pub fn cv_penalized<R: Rng>(
+    formula: Formula,
+    design: Design,
+    family: Family,
+    penalty: Penalty,
+    lambdas: Option<&[f64]>,
+    k: usize,
+    rng: &mut R,
+) -> Result<PenalizedCv, StatustError> {
+    let path = penalized(formula, design.clone(), family, penalty, lambdas)?;
+    let alpha = penalty.alpha();
+    let n = design.y.len();
+    let folds = k_fold_indices(n, k, rng)?;
+    let all_cols: Vec<usize> = (0..design.x.ncols()).collect();
+    let nl = path.lambdas.len();
+    // Mean error of each fold at each lambda, and the fold sizes.
+    let mut fold_errors = Vec::new();
+    let mut sizes = Vec::new();
+    for fold in &folds {
+        let take = |rows: &[usize]| Design {
+            columns: design.columns.clone(),
+            x: design.x.select(rows, &all_cols),
+            y: rows.iter().map(|&i| design.y[i]).collect(),
+            response_level: design.response_level.clone(),
+            rows: rows.iter().map(|&i| design.rows[i]).collect(),
+            levels: design.levels.clone(),
+        };
+        let (train, test) = (take(&fold.train), take(&fold.test));
+        check_arguments(&train, family, penalty, None)?;
+        let std = standardize(&train)?;
+        let fit = fit_path(&std.x, &train.y, family, alpha, &path.lambdas)?;
+        let test_std = standardize_like(&test, &std);
+        let errors = fit
+            .beta
+            .iter()
+            .zip(&fit.intercepts)
+            .map(|(beta, b0)| {
+                let eta: Vec<f64> = test_std.mul_vec(beta)?.iter().map(|e| e + b0).collect();
+                Ok(deviance(family, &test.y, &eta) / test.y.len() as f64)
+            })
+            .collect::<Result<Vec<f64>, StatustError>>()?;
+        fold_errors.push(errors);
+        sizes.push(test.y.len() as f64);
+    }
+    let total: f64 = sizes.iter().sum();
+    let cv_error: Vec<f64> = (0..nl)
+        .map(|l| {
+            fold_errors
+                .iter()
+                .zip(&sizes)
+                .map(|(e, size)| size * e[l])
+                .sum::<f64>()
+                / total
+        })
+        .collect();
+    let cv_se: Vec<f64> = (0..nl)
+        .map(|l| {
+            let var = fold_errors
+                .iter()
+                .zip(&sizes)
+                .map(|(e, size)| size * (e[l] - cv_error[l]).powi(2))
+                .sum::<f64>()
+                / total;
+            (var / (k - 1) as f64).sqrt()
+        })
+        .collect();
+    let index_min = (0..nl)
+        .min_by(|&a, &b| cv_error[a].partial_cmp(&cv_error[b]).unwrap())
+        .unwrap();
+    let bound = cv_error[index_min] + cv_se[index_min];
+    let index_1se = (0..=index_min).find(|&l| cv_error[l] <= bound).unwrap();
+    Ok(PenalizedCv {
+        path,
+        folds: k,
+        cv_error,
+        cv_se,
+        index_min,
+        index_1se,
+    })
+}
+
+/// Applies the centering and scaling of `fitted` to the columns of `design`.
+fn standardize_like(design: &Design, fitted: &Standardized) -> Matrix {
+    let intercept = design
+        .columns
+        .iter()
+        .position(|c| c == "(Intercept)")
+        .unwrap();
+    let cols: Vec<usize> = (0..design.x.ncols()).filter(|&j| j != intercept).collect();
+    let rows: Vec<usize> = (0..design.x.nrows()).collect();
+    let mut x = design.x.select(&rows, &cols);
+    for j in 0..x.ncols() {
+        for i in 0..x.nrows() {
+            x[(i, j)] = if fitted.scales[j] > 0.0 {
+                (x[(i, j)] - fitted.means[j]) / fitted.scales[j]
+            } else {
+                0.0
+            };
+        }
+    }
+    x
+}
+
+impl PenalizedPath {
+    /// Intercept and coefficients at the `index`-th lambda of the path.
+    pub fn coef(&self, index: usize) -> Vec<(String, f64)> {
+        std::iter::once(("(Intercept)".to_string(), self.intercepts[index]))
+            .chain(
+                self.columns
+                    .iter()
+                    .cloned()
+                    .zip(self.coefficients.row(index).iter().copied()),
+            )
+            .collect()
+    }
+}
+
+impl PenalizedCv {
+    pub fn lambda_min(&self) -> f64 {
+        self.path.lambdas[self.index_min]
+    }
+
+    pub fn lambda_1se(&self) -> f64 {
+        self.path.lambdas[self.index_1se]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{ContinuousDistribution, Normal};
+    use crate::test_util::assert_close;
+
+    /// Checks the optimality conditions of the elastic net at every lambda
+    /// of the path: a zero intercept gradient, and for each standardized
+    /// coefficient `g_j - lambda (1 - alpha) b_j = lambda alpha sign(b_j)`
+    /// when `b_j != 0`, `|g_j| <= lambda alpha` otherwise.
+    fn check_kkt(path: &PenalizedPath, design: &Design, tol: f64) {
+        let std = standardize(design).unwrap();
+        let alpha = path.penalty.alpha();
+        let n = design.y.len() as f64;
+        for (l, &lambda) in path.lambdas.iter().enumerate() {
+            let beta: Vec<f64> = (0..std.x.ncols())
+                .map(|j| path.coefficients[(l, j)] * std.scales[j])
+                .collect();
+            let b0 = path.intercepts[l]
+                + (0..beta.len())
+                    .map(|j| path.coefficients[(l, j)] * std.means[j])
+                    .sum::<f64>();
+            let eta: Vec<f64> = std
+                .x
+                .mul_vec(&beta)
+                .unwrap()
+                .iter()
+                .map(|e| e + b0)
+                .collect();
+            let r: Vec<f64> = design
+                .y
+                .iter()
+                .zip(&eta)
+                .map(|(y, e)| match path.family {
+                    Family::Binomial => y - sigmoid(*e),
+                    _ => y - e,
+                })
+                .collect();
+            assert_close(r.iter().sum::<f64>() / n, 0.0, tol);
+            for (j, b) in beta.iter().enumerate() {
+                let g = std.x.col(j).iter().zip(&r).map(|(x, r)| x * r).sum::<f64>() / n
+                    - lambda * (1.0 - alpha) * b;
+                if *b == 0.0 {
+                    assert!(g.abs() <= lambda * alpha + tol, "lambda {}: {}", lambda, g);
+                } else {
+                    assert_close(g, lambda * alpha * b.signum(), tol);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ridge_closed_form() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let formula = "sepal.length ~ sepal.width + petal.length + petal.width + variety";
+        let lambdas = [0.5, 0.1, 0.01];
+        let path = df
+            .penalized(formula, Family::Gaussian, Penalty::Ridge, Some(&lambdas))
+            .unwrap();
+        assert_eq!(path.lambdas, [0.5, 0.1, 0.01]);
+        assert_eq!(path.df, [5, 5, 5]);
+
+        // (X'X / n + lambda I) b = X'(y - mean y) / n on standardized columns.
+        let design = Formula::parse(formula).unwrap().design(&df).unwrap();
+        let std = standardize(&design).unwrap();
+        let n = design.y.len() as f64;
+        let mean = stats::mean(&design.y);
+        let centered: Vec<f64> = design.y.iter().map(|v| (v - mean) / n).collect();
+        let xty = std.x.transpose().mul_vec(&centered).unwrap();
+        let gram = std.x.transpose().matmul(&std.x).unwrap();
+        for (l, &lambda) in path.lambdas.iter().enumerate() {
+            let mut a = gram.clone();
+            for i in 0..a.nrows() {
+                for j in 0..a.ncols() {
+                    a[(i, j)] = a[(i, j)] / n + if i == j { lambda } else { 0.0 };
+                }
+            }
+            let b = a.inverse().unwrap().mul_vec(&xty).unwrap();
+            for (j, expected) in b.iter().enumerate() {
+                assert_close(path.coefficients[(l, j)] * std.scales[j], *expected, 1e-8);
+            }
+        }
+        // Almost no shrinkage reproduces OLS.
+        let small = df
+            .penalized(formula, Family::Gaussian, Penalty::Ridge, Some(&[1e-12]))
+            .unwrap();
+        let ols = df.ols(formula, 0.95).unwrap();
+        for (name, estimate) in small.coef(0) {
+            assert_close(estimate, ols.coef(&name).unwrap().estimate, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_lasso_path() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let formula = "sepal.length ~ sepal.width + petal.length + petal.width + variety";
+        let path = df
+            .penalized(formula, Family::Gaussian, Penalty::Lasso, None)
+            .unwrap();
+        assert_eq!(path.lambdas.len(), 100);
+        assert_eq!(path.df[0], 0);
+        assert!(path.coef(0)[1..].iter().all(|(_, b)| *b == 0.0));
+        assert_close(path.coef(0)[0].1, 5.843333333, 1e-8);
+        assert_close(path.lambdas[99] / path.lambdas[0], 1e-4, 1e-15);
+        assert_close(path.deviance_ratio[0], 0.0, 1e-12);
+        assert!(path.df[99] == 5 && path.deviance_ratio[99] > 0.85);
+        let design = Formula::parse(formula).unwrap().design(&df).unwrap();
+        check_kkt(&path, &design, 1e-7);
+
+        // With one predictor the lasso is a soft-thresholded correlation.
+        let path = df
+            .penalized(
+                "sepal.length ~ petal.length",
+                Family::Gaussian,
+                Penalty::Lasso,
+                Some(&[0.2]),
+            )
+            .unwrap();
+        let x = df.get_f64_col("petal.length").unwrap();
+        let y = df.get_f64_col("sepal.length").unwrap();
+        let x: Vec<f64> = x.iter().map(|v| v.unwrap()).collect();
+        let y: Vec<f64> = y.iter().map(|v| v.unwrap()).collect();
+        let (mx, my) = (stats::mean(&x), stats::mean(&y));
+        let sx = (x.iter().map(|v| (v - mx).powi(2)).sum::<f64>() / 150.0).sqrt();
+        let g = x
+            .iter()
+            .zip(&y)
+            .map(|(a, b)| (a - mx) * (b - my))
+            .sum::<f64>()
+            / 150.0
+            / sx;
+        assert_close(path.coefficients[(0, 0)], (g - 0.2) / sx, 1e-10);
+    }
+
+    #[test]
+    fn test_logistic_elastic_net() {
+        let df = DataFrame::read("./examples/credit_scoring.csv").unwrap();
+        let formula = "score ~ duration in months + credit amount + age in years + housing";
+        let path = df
+            .penalized(
+                formula,
+                Family::Binomial,
+                Penalty::ElasticNet(0.5),
+                Some(&[0.05, 0.02, 0.005, 0.001]),
+            )
+            .unwrap();
+        let design = Formula::parse(formula).unwrap().design(&df).unwrap();
+        check_kkt(&path, &design, 1e-7);
+        assert!(path.df.windows(2).all(|d| d[0] <= d[1]));
+        assert!(path.deviance_ratio.windows(2).all(|d| d[0] < d[1]));
+
+        // Without a penalty the fit is the maximum likelihood logistic
+        // regression of the glm module.
+        let path = df
+            .penalized(formula, Family::Binomial, Penalty::Lasso, Some(&[0.0]))
+            .unwrap();
+        let glm = df.glm(formula, Family::Binomial, None, 0.95).unwrap();
+        for (name, estimate) in path.coef(0) {
+            let expected = glm.coef(&name).unwrap().estimate;
+            assert_close(estimate, expected, 1e-7 * (1.0 + expected.abs()));
+        }
+        assert_close(
+            path.deviance_ratio[0],
+            1.0 - glm.deviance / glm.null_deviance,
+            1e-10,
+        );
+    }
+
+    #[test]
+    fn test_wide_design() {
+        // More columns than rows: sweeps update residuals instead of
+        // forming the Gram matrix.
+        let (n, p) = (20, 40);
+        let mut rng = Xoshiro256::seed_from_u64(7);
+        let normal = Normal::standard();
+        let mut header = vec!["y".to_string(), "yes".to_string()];
+        let mut x: Vec<Vec<f64>> = (0..p).map(|_| normal.sample_n(n, &mut rng)).collect();
+        let y: Vec<f64> = (0..n)
+            .map(|i| 3.0 * x[0][i] - 2.0 * x[1][i] + 0.5 * normal.sample(&mut rng))
+            .collect();
+        let yes: Vec<f64> = y.iter().map(|v| (*v > 0.0) as i32 as f64).collect();
+        header.extend((1..=p).map(|j| format!("x{}", j)));
+        let mut columns = vec![y, yes];
+        columns.append(&mut x);
+        let df = DataFrame::from_columns(
+            header,
+            columns
+                .into_iter()
+                .map(|c| c.into_iter().map(DataType::from).collect())
+                .collect(),
+        )
+        .unwrap();
+        let terms: Vec<String> = (1..=p).map(|j| format!("x{}", j)).collect();
+        let rhs = terms.join(" + ");
+
+        let formula = format!("y ~ {}", rhs);
+        let design = Formula::parse(&formula).unwrap().design(&df).unwrap();
+        let std = standardize(&design).unwrap();
+        assert!(CoordinateDescent::new(&std.x, &design.y, &vec![1.0; n])
+            .gram
+            .is_none());
+        let path = df
+            .penalized(&formula, Family::Gaussian, Penalty::Lasso, None)
+            .unwrap();
+        assert_close(path.lambdas[99] / path.lambdas[0], 1e-2, 1e-15);
+        check_kkt(&path, &design, 1e-7);
+        let last = path.coef(99);
+        assert!(last[1].1 > 2.0 && last[2].1 < -1.0);
+
+        let formula = format!("yes ~ {}", rhs);
+        let design = Formula::parse(&formula).unwrap().design(&df).unwrap();
+        let path = df
+            .penalized(
+                &formula,
+                Family::Binomial,
+                Penalty::ElasticNet(0.5),
+                Some(&[0.2, 0.1, 0.05]),
+            )
+            .unwrap();
+        check_kkt(&path, &design, 1e-7);
+    }
+
+    #[test]
+    fn test_update_modes_agree() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let formula = "sepal.length ~ sepal.width + petal.length + petal.width + variety";
+        let design = Formula::parse(formula).unwrap().design(&df).unwrap();
+        let std = standardize(&design).unwrap();
+        let w: Vec<f64> = (0..design.y.len()).map(|i| 1.0 + (i % 3) as f64).collect();
+        let covariance = CoordinateDescent::new(&std.x, &design.y, &w);
+        let mut naive = CoordinateDescent::new(&std.x, &design.y, &w);
+        assert!(covariance.gram.is_some());
+        naive.gram = None;
+        let p = std.x.ncols();
+        let (mut b0, mut beta) = (0.0, vec![0.0; p]);
+        let (mut naive_b0, mut naive_beta) = (0.0, vec![0.0; p]);
+        for lambda in [0.1, 0.01] {
+            covariance.solve(lambda, 0.7, &mut b0, &mut beta).unwrap();
+            naive
+                .solve(lambda, 0.7, &mut naive_b0, &mut naive_beta)
+                .unwrap();
+            assert_close(naive_b0, b0, 1e-9);
+            for (a, b) in naive_beta.iter().zip(&beta) {
+                assert_close(*a, *b, 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cross_validation() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let formula = "sepal.length ~ sepal.width + petal.length + petal.width";
+        let cv = df
+            .cv_penalized(
+                formula,
+                Family::Gaussian,
+                Penalty::ElasticNet(0.5),
+                None,
+                5,
+                &mut Xoshiro256::seed_from_u64(42),
+            )
+            .unwrap();
+        assert_eq!(cv.folds, 5);
+        assert_eq!(cv.cv_error.len(), cv.path.lambdas.len());
+        let min = cv.cv_error.iter().cloned().fold(f64::INFINITY, f64::min);
+        assert_eq!(cv.cv_error[cv.index_min], min);
+        assert!(cv.lambda_1se() >= cv.lambda_min());
+        assert!(cv.cv_error[cv.index_1se] <= min + cv.cv_se[cv.index_min]);
+        // The null model's error is about the variance of the response.
+        assert_close(cv.cv_error[0], 0.6811, 0.05);
+        assert!(min < 0.15);
+        let text = cv.to_string();
+        assert!(text.contains("lambda.min") && text.contains("petal.length"));
+
+        let credit = DataFrame::read("./examples/credit_scoring.csv").unwrap();
+        let cv = credit
+            .cv_penalized(
+                "score ~ duration in months + age in years + housing",
+                Family::Binomial,
+                Penalty::Lasso,
+                None,
+                5,
+                &mut Xoshiro256::seed_from_u64(1),
+            )
+            .unwrap();
+        assert!(cv.cv_error[cv.index_min] < cv.cv_error[0]);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        let fit =
+            |formula, family, penalty, lambdas| df.penalized(formula, family, penalty, lambdas);
+        let formula = "sepal.length ~ sepal.width + petal.length";
+        assert!(fit(formula, Family::Gaussian, Penalty::ElasticNet(1.5), None).is_err());
+        assert!(fit(formula, Family::Poisson, Penalty::Lasso, None).is_err());
+        assert!(fit(formula, Family::Binomial, Penalty::Lasso, None).is_err());
+        assert!(fit(formula, Family::Gaussian, Penalty::Lasso, Some(&[-1.0])).is_err());
+        assert!(fit(formula, Family::Gaussian, Penalty::Lasso, Some(&[])).is_err());
+        assert!(fit(
+            "sepal.length ~ sepal.width - 1",
+            Family::Gaussian,
+            Penalty::Lasso,
+            None
+        )
+        .is_err());
+        assert!(df
+            .cv_penalized(
+                formula,
+                Family::Gaussian,
+                Penalty::Lasso,
+                None,
+                1,
+                &mut Xoshiro256::seed_from_u64(1)
+            )
+            .is_err());
+
+        let mut df = df;
+        let mut length = df.get_col_by_name("petal.length").unwrap();
+        length[0] = DataType::Float(f32::INFINITY);
+        df.set_col("petal.length", length).unwrap();
+        let fit = df.penalized(formula, Family::Gaussian, Penalty::Ridge, None);
+        assert!(fit.is_err());
+    }
+}
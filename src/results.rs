@@ -1,3 +1,5 @@
+use crate::distributions::ContinuousDistribution;
+use crate::linalg::Matrix;
 use crate::*;
 use std::fmt;
 use std::fmt::{Display, Formatter};
@@ -125,6 +127,1042 @@ impl Display for BooleanDescribeResult {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub struct ColumnNullSummary {
+    pub name: String,
+    pub null_count: usize,
+    pub null_fraction: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NullPattern {
+    pub columns: Vec<String>,
+    pub count: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NullReport {
+    pub nrows: usize,
+    pub columns: Vec<ColumnNullSummary>,
+    pub patterns: Vec<NullPattern>,
+}
+
+impl Display for NullReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "Null Report ({} rows):", self.nrows).ok();
+        for c in &self.columns {
+            writeln!(
+                f,
+                "\t{}: {} ({:.2}%)",
+                c.name,
+                c.null_count,
+                c.null_fraction * 100.0
+            )
+            .ok();
+        }
+        write!(f, "Missing Patterns:")?;
+        for p in &self.patterns {
+            write!(f, "\n\t{:?}: {}", p.columns, p.count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Alternative hypothesis of a test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Alternative {
+    TwoSided,
+    Less,
+    Greater,
+}
+
+impl Display for Alternative {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Alternative::TwoSided => write!(f, "two-sided"),
+            Alternative::Less => write!(f, "less"),
+            Alternative::Greater => write!(f, "greater"),
+        }
+    }
+}
+
+impl Alternative {
+    /// P-value of `statistic` under the null distribution `dist`.
+    pub(crate) fn p_value(&self, dist: &dyn ContinuousDistribution, statistic: f64) -> f64 {
+        match self {
+            Alternative::TwoSided => (2.0 * dist.cdf(statistic).min(dist.sf(statistic))).min(1.0),
+            Alternative::Less => dist.cdf(statistic),
+            Alternative::Greater => dist.sf(statistic),
+        }
+    }
+}
+
+/// Outcome of a hypothesis test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestResult {
+    pub method: String,
+    /// Name of the tested data, e.g. the column name. May be empty.
+    pub data: String,
+    pub statistic: f64,
+    pub p_value: f64,
+    pub alternative: Alternative,
+    /// Named parameters of the null distribution, such as degrees of freedom.
+    pub parameters: Vec<(String, f64)>,
+    /// Named point estimates and effect sizes.
+    pub estimates: Vec<(String, f64)>,
+    pub conf_int: Option<ConfidenceInterval>,
+}
+
+/// Confidence interval; one-sided intervals have an infinite bound.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConfidenceInterval {
+    pub lower: f64,
+    pub upper: f64,
+    pub level: f64,
+}
+
+impl TestResult {
+    pub fn new(method: &str, statistic: f64, p_value: f64, alternative: Alternative) -> TestResult {
+        TestResult {
+            method: method.to_string(),
+            data: String::new(),
+            statistic,
+            p_value,
+            alternative,
+            parameters: Vec::new(),
+            estimates: Vec::new(),
+            conf_int: None,
+        }
+    }
+}
+
+/// Formats a p-value, switching to scientific notation for small values.
+pub(crate) fn format_p_value(p: f64) -> String {
+    if p != 0.0 && p < 1e-4 {
+        format!("{:.4e}", p)
+    } else {
+        format!("{:.4}", p)
+    }
+}
+
+impl Display for TestResult {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "{}", self.method).ok();
+        if !self.data.is_empty() {
+            writeln!(f, "\tData: {}", self.data).ok();
+        }
+        writeln!(f, "\tStatistic: {:.6}", self.statistic).ok();
+        for (name, value) in &self.parameters {
+            writeln!(f, "\t{}: {}", name, value).ok();
+        }
+        for (name, value) in &self.estimates {
+            writeln!(f, "\t{}: {:.6}", name, value).ok();
+        }
+        if let Some(ci) = &self.conf_int {
+            writeln!(
+                f,
+                "\t{}% CI: [{:.6}, {:.6}]",
+                ci.level * 100.0,
+                ci.lower,
+                ci.upper
+            )
+            .ok();
+        }
+        write!(
+            f,
+            "\tP-value: {}\n\tAlternative: {}",
+            format_p_value(self.p_value),
+            self.alternative
+        )
+    }
+}
+
+/// Pairwise correlations with p-values and confidence intervals, labelled
+/// by column name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CorrelationMatrix {
+    pub method: CorrMethod,
+    pub columns: Vec<String>,
+    pub coef: Matrix,
+    pub p_values: Matrix,
+    pub ci_lower: Matrix,
+    pub ci_upper: Matrix,
+    /// Number of pairwise-complete observations behind each entry.
+    pub n_obs: Vec<Vec<usize>>,
+    pub confidence: f64,
+    /// Columns partialled out; empty for ordinary correlations.
+    pub controls: Vec<String>,
+}
+
+impl CorrelationMatrix {
+    /// Coefficient for the pair of columns `a` and `b`.
+    pub fn get(&self, a: &str, b: &str) -> Option<f64> {
+        let i = self.columns.iter().position(|c| c == a)?;
+        let j = self.columns.iter().position(|c| c == b)?;
+        Some(self.coef[(i, j)])
+    }
+
+    /// One line per pair of columns:
+    /// `var1,var2,n,coef,p_value,ci_lower,ci_upper`.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("var1,var2,n,coef,p_value,ci_lower,ci_upper\n");
+        for i in 0..self.columns.len() {
+            for j in i + 1..self.columns.len() {
+                out.push_str(&format!(
+                    "{},{},{},{},{},{},{}\n",
+                    self.columns[i],
+                    self.columns[j],
+                    self.n_obs[i][j],
+                    self.coef[(i, j)],
+                    self.p_values[(i, j)],
+                    self.ci_lower[(i, j)],
+                    self.ci_upper[(i, j)]
+                ));
+            }
+        }
+        out
+    }
+}
+
+impl Display for CorrelationMatrix {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        if self.controls.is_empty() {
+            writeln!(
+                f,
+                "{} correlation (pairwise complete, {}% CI):",
+                self.method,
+                self.confidence * 100.0
+            )
+            .ok();
+        } else {
+            writeln!(
+                f,
+                "{} partial correlation controlling for {} (complete cases, {}% CI):",
+                self.method,
+                self.controls.join(", "),
+                self.confidence * 100.0
+            )
+            .ok();
+        }
+        let short = |s: &str| s.chars().take(10).collect::<String>();
+        write!(f, "{: <12}", "").ok();
+        for c in &self.columns {
+            write!(f, " {: >10}", short(c)).ok();
+        }
+        for (i, c) in self.columns.iter().enumerate() {
+            write!(f, "\n{: <12}", short(c)).ok();
+            for j in 0..self.columns.len() {
+                write!(f, " {: >10.4}", self.coef[(i, j)]).ok();
+            }
+        }
+        write!(f, "\nPairs:")?;
+        for i in 0..self.columns.len() {
+            for j in i + 1..self.columns.len() {
+                write!(
+                    f,
+                    "\n\t{} ~ {}: r = {:.4}, n = {}, p = {}, CI [{:.4}, {:.4}]",
+                    self.columns[i],
+                    self.columns[j],
+                    self.coef[(i, j)],
+                    self.n_obs[i][j],
+                    format_p_value(self.p_values[(i, j)]),
+                    self.ci_lower[(i, j)],
+                    self.ci_upper[(i, j)]
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A square matrix of values labelled by column name, such as a covariance
+/// or association matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelledMatrix {
+    pub title: String,
+    pub columns: Vec<String>,
+    pub values: Matrix,
+}
+
+impl LabelledMatrix {
+    /// Value for the pair of columns `a` and `b`.
+    pub fn get(&self, a: &str, b: &str) -> Option<f64> {
+        let i = self.columns.iter().position(|c| c == a)?;
+        let j = self.columns.iter().position(|c| c == b)?;
+        Some(self.values[(i, j)])
+    }
+
+    /// The matrix with a header row and a leading column of names.
+    pub fn to_csv(&self) -> String {
+        let mut out = format!(",{}\n", self.columns.join(","));
+        for (i, c) in self.columns.iter().enumerate() {
+            let row: Vec<String> = self.values.row(i).iter().map(|v| v.to_string()).collect();
+            out.push_str(&format!("{},{}\n", c, row.join(",")));
+        }
+        out
+    }
+}
+
+impl Display for LabelledMatrix {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "{}:", self.title).ok();
+        let short = |s: &str| s.chars().take(10).collect::<String>();
+        write!(f, "{: <12}", "").ok();
+        for c in &self.columns {
+            write!(f, " {: >10}", short(c)).ok();
+        }
+        for (i, c) in self.columns.iter().enumerate() {
+            write!(f, "\n{: <12}", short(c))?;
+            for j in 0..self.columns.len() {
+                write!(f, " {: >10.4}", self.values[(i, j)])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A test on a table of counts, with the expected counts under the null
+/// hypothesis and the cell residuals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CountTestResult {
+    pub test: TestResult,
+    pub observed: ContingencyTable,
+    pub expected: Matrix,
+    /// Pearson residuals, `(o - e) / sqrt(e)`.
+    pub pearson_residuals: Matrix,
+    /// Standardized residuals, which are approximately standard normal under
+    /// the null hypothesis.
+    pub std_residuals: Matrix,
+}
+
+impl Display for CountTestResult {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.test).ok();
+        let short = |s: &str| s.chars().take(10).collect::<String>();
+        let labels = &self.observed.col_labels;
+        for (title, m) in [
+            ("Expected counts", &self.expected),
+            ("Standardized residuals", &self.std_residuals),
+        ] {
+            write!(f, "\t{}:\n{: <12}", title, "").ok();
+            for c in labels {
+                write!(f, " {: >10}", short(c)).ok();
+            }
+            for (i, r) in self.observed.row_labels.iter().enumerate() {
+                write!(f, "\n{: <12}", short(r)).ok();
+                for j in 0..labels.len() {
+                    write!(f, " {: >10.4}", m[(i, j)]).ok();
+                }
+            }
+            writeln!(f).ok();
+        }
+        Ok(())
+    }
+}
+
+/// One line of an ANOVA table. `f`, `p_value` and the effect sizes are NaN
+/// for the residual row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnovaRow {
+    pub source: String,
+    pub df: f64,
+    pub ss: f64,
+    pub ms: f64,
+    pub f: f64,
+    pub p_value: f64,
+    pub eta_sq: f64,
+    pub omega_sq: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnovaTable {
+    pub title: String,
+    /// Effects followed by the residuals.
+    pub rows: Vec<AnovaRow>,
+}
+
+impl AnovaTable {
+    /// Row for the effect or residual named `source`.
+    pub fn get(&self, source: &str) -> Option<&AnovaRow> {
+        self.rows.iter().find(|r| r.source == source)
+    }
+}
+
+impl Display for AnovaTable {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "{}", self.title).ok();
+        write!(
+            f,
+            "{: <20} {: >6} {: >12} {: >12} {: >10} {: >10} {: >8} {: >8}",
+            "", "Df", "Sum Sq", "Mean Sq", "F", "P-value", "eta2", "omega2"
+        )?;
+        for r in &self.rows {
+            write!(
+                f,
+                "\n{: <20} {: >6} {: >12.4} {: >12.4}",
+                r.source.chars().take(20).collect::<String>(),
+                r.df,
+                r.ss,
+                r.ms
+            )?;
+            if !r.f.is_nan() {
+                write!(
+                    f,
+                    " {: >10.4} {: >10} {: >8.4} {: >8.4}",
+                    r.f,
+                    format_p_value(r.p_value),
+                    r.eta_sq,
+                    r.omega_sq
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Difference `mean(group2) - mean(group1)` with its adjusted p-value and
+/// simultaneous confidence interval.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairwiseComparison {
+    pub group1: String,
+    pub group2: String,
+    pub diff: f64,
+    pub lower: f64,
+    pub upper: f64,
+    pub p_value: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PostHocResult {
+    pub method: PostHocMethod,
+    /// Name of the tested data, e.g. `value by group`. May be empty.
+    pub data: String,
+    pub confidence: f64,
+    pub comparisons: Vec<PairwiseComparison>,
+}
+
+impl PostHocResult {
+    /// Comparison of the groups `a` and `b`, in either order.
+    pub fn get(&self, a: &str, b: &str) -> Option<&PairwiseComparison> {
+        self.comparisons
+            .iter()
+            .find(|c| (c.group1 == a && c.group2 == b) || (c.group1 == b && c.group2 == a))
+    }
+}
+
+impl Display for PostHocResult {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "{} ({}% CI)", self.method, self.confidence * 100.0).ok();
+        if !self.data.is_empty() {
+            writeln!(f, "\tData: {}", self.data).ok();
+        }
+        write!(f, "\tComparisons:")?;
+        for c in &self.comparisons {
+            write!(
+                f,
+                "\n\t{} - {}: diff = {:.4}, CI [{:.4}, {:.4}], p = {}",
+                c.group2,
+                c.group1,
+                c.diff,
+                c.lower,
+                c.upper,
+                format_p_value(c.p_value)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Raw and adjusted p-values for a family of tests, one column per
+/// adjustment method.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdjustedPValues {
+    pub labels: Vec<String>,
+    pub p_values: Vec<f64>,
+    pub methods: Vec<PAdjustMethod>,
+    /// One row per test and one column per method.
+    pub adjusted: Matrix,
+}
+
+impl AdjustedPValues {
+    /// Adjusted p-value of the test labelled `label`.
+    pub fn get(&self, label: &str, method: PAdjustMethod) -> Option<f64> {
+        let i = self.labels.iter().position(|l| l == label)?;
+        let j = self.methods.iter().position(|m| *m == method)?;
+        Some(self.adjusted[(i, j)])
+    }
+
+    /// A table with a header row and a leading column of test labels.
+    pub fn to_csv(&self) -> String {
+        let methods: Vec<String> = self.methods.iter().map(|m| m.to_string()).collect();
+        let mut out = format!(",p,{}\n", methods.join(","));
+        for (i, label) in self.labels.iter().enumerate() {
+            let row: Vec<String> = self.adjusted.row(i).iter().map(|v| v.to_string()).collect();
+            out.push_str(&format!(
+                "{},{},{}\n",
+                label,
+                self.p_values[i],
+                row.join(",")
+            ));
+        }
+        out
+    }
+}
+
+impl Display for AdjustedPValues {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "Adjusted p-values:").ok();
+        let short = |s: &str| s.chars().take(10).collect::<String>();
+        write!(f, "{: <12} {: >10}", "", "p").ok();
+        for m in &self.methods {
+            write!(f, " {: >10}", m.to_string()).ok();
+        }
+        for (i, label) in self.labels.iter().enumerate() {
+            write!(
+                f,
+                "\n{: <12} {: >10}",
+                short(label),
+                format_p_value(self.p_values[i])
+            )?;
+            for j in 0..self.methods.len() {
+                write!(f, " {: >10}", format_p_value(self.adjusted[(i, j)]))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One row of a regression coefficient table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coefficient {
+    pub name: String,
+    pub estimate: f64,
+    pub std_error: f64,
+    /// t or z statistic for `H0: coefficient = 0`.
+    pub statistic: f64,
+    pub p_value: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Writes a coefficient table with the given statistic column header.
+fn write_coefficients(
+    f: &mut Formatter,
+    coefficients: &[Coefficient],
+    statistic: &str,
+    confidence: f64,
+) -> fmt::Result {
+    let width = coefficients
+        .iter()
+        .map(|c| c.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let tail = (1.0 - confidence) / 2.0 * 100.0;
+    write!(
+        f,
+        "\t{: <width$} {: >12} {: >12} {: >10} {: >10} {: >12} {: >12}",
+        "",
+        "Estimate",
+        "Std. Error",
+        statistic,
+        "p-value",
+        format!("{:.1}%", tail),
+        format!("{:.1}%", 100.0 - tail),
+        width = width
+    )?;
+    for c in coefficients {
+        write!(
+            f,
+            "\n\t{: <width$} {: >12.6} {: >12.6} {: >10.4} {: >10} {: >12.6} {: >12.6}",
+            c.name,
+            c.estimate,
+            c.std_error,
+            c.statistic,
+            format_p_value(c.p_value),
+            c.lower,
+            c.upper,
+            width = width
+        )?;
+    }
+    Ok(())
+}
+
+/// A linear model fitted by ordinary least squares.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OlsResult {
+    pub formula: Formula,
+    pub nobs: usize,
+    /// Confidence level of the coefficient intervals.
+    pub confidence: f64,
+    pub coefficients: Vec<Coefficient>,
+    /// Residual standard error.
+    pub sigma: f64,
+    pub df_residual: f64,
+    pub r_squared: f64,
+    pub adj_r_squared: f64,
+    /// F-test against the intercept-only model; `None` when the model has
+    /// no other terms.
+    pub f_test: Option<TestResult>,
+    pub log_likelihood: f64,
+    pub aic: f64,
+    pub bic: f64,
+    pub fitted: Vec<f64>,
+    pub residuals: Vec<f64>,
+    /// `(X^T X)^-1`, the coefficient covariance divided by `sigma^2`.
+    pub cov_unscaled: Matrix,
+    pub design: Design,
+}
+
+impl OlsResult {
+    /// Coefficient of the design column `name`, e.g. `x1` or `group[b]`.
+    pub fn coef(&self, name: &str) -> Option<&Coefficient> {
+        self.coefficients.iter().find(|c| c.name == name)
+    }
+}
+
+impl Display for OlsResult {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "Ordinary least squares: {}", self.formula).ok();
+        writeln!(f, "\tObservations: {}", self.nobs).ok();
+        let r = &self.residuals;
+        writeln!(
+            f,
+            "\tResiduals: min {:.4}, 1Q {:.4}, median {:.4}, 3Q {:.4}, max {:.4}",
+            stats::quantile(r, 0.0),
+            stats::quantile(r, 0.25),
+            stats::quantile(r, 0.5),
+            stats::quantile(r, 0.75),
+            stats::quantile(r, 1.0)
+        )
+        .ok();
+        write_coefficients(f, &self.coefficients, "t value", self.confidence)?;
+        write!(
+            f,
+            "\n\tResidual standard error: {:.6} on {} df",
+            self.sigma, self.df_residual
+        )?;
+        write!(
+            f,
+            "\n\tR-squared: {:.6}, adjusted R-squared: {:.6}",
+            self.r_squared, self.adj_r_squared
+        )?;
+        if let Some(t) = &self.f_test {
+            write!(
+                f,
+                "\n\tF-statistic: {:.4} on {} and {} df, p-value: {}",
+                t.statistic,
+                t.parameters[0].1,
+                t.parameters[1].1,
+                format_p_value(t.p_value)
+            )?;
+        }
+        write!(
+            f,
+            "\n\tLog-likelihood: {:.4}, AIC: {:.4}, BIC: {:.4}",
+            self.log_likelihood, self.aic, self.bic
+        )
+    }
+}
+
+/// An exponentiated coefficient with its confidence interval.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OddsRatio {
+    pub name: String,
+    pub odds_ratio: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// A generalized linear model fitted by iteratively reweighted least
+/// squares.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlmResult {
+    pub formula: Formula,
+    /// The family, with the estimated theta for the negative binomial.
+    pub family: Family,
+    pub link: Link,
+    pub nobs: usize,
+    /// Confidence level of the coefficient intervals.
+    pub confidence: f64,
+    /// Whether the Wald statistics are t (estimated dispersion) or z.
+    pub t_statistics: bool,
+    pub coefficients: Vec<Coefficient>,
+    /// Pearson estimate for the Gaussian and Gamma families, otherwise 1.
+    pub dispersion: f64,
+    pub deviance: f64,
+    pub null_deviance: f64,
+    pub df_residual: f64,
+    pub df_null: f64,
+    pub log_likelihood: f64,
+    pub aic: f64,
+    pub bic: f64,
+    pub iterations: usize,
+    /// Whether IRLS converged. When it did not, the estimates are those of
+    /// the last iteration; see [`GlmResult::separated`].
+    pub converged: bool,
+    /// Likelihood-ratio test against the model with only an intercept;
+    /// `None` when the model has no other terms.
+    pub lr_test: Option<TestResult>,
+    /// Fitted means.
+    pub fitted: Vec<f64>,
+    /// `(X^T W X)^-1`, the coefficient covariance divided by the dispersion.
+    pub cov_unscaled: Matrix,
+    pub design: Design,
+}
+
+impl Display for GlmResult {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "Generalized linear model: {}", self.formula).ok();
+        writeln!(f, "\tFamily: {}, link: {}", self.family, self.link).ok();
+        if let Some(level) = &self.design.response_level {
+            writeln!(f, "\tModelling P({} = {})", self.formula.response, level).ok();
+        }
+        writeln!(f, "\tObservations: {}", self.nobs).ok();
+        let statistic = if self.t_statistics {
+            "t value"
+        } else {
+            "z value"
+        };
+        write_coefficients(f, &self.coefficients, statistic, self.confidence)?;
+        write!(f, "\n\tDispersion: {:.6}", self.dispersion)?;
+        write!(
+            f,
+            "\n\tNull deviance: {:.4} on {} df\n\tResidual deviance: {:.4} on {} df",
+            self.null_deviance, self.df_null, self.deviance, self.df_residual
+        )?;
+        if let Some(t) = &self.lr_test {
+            write!(
+                f,
+                "\n\tLR chi-square: {:.4} on {} df, p-value: {}",
+                t.statistic,
+                t.parameters[0].1,
+                format_p_value(t.p_value)
+            )?;
+        }
+        write!(
+            f,
+            "\n\tLog-likelihood: {:.4}, AIC: {:.4}, BIC: {:.4}",
+            self.log_likelihood, self.aic, self.bic
+        )?;
+        write!(f, "\n\tIRLS iterations: {}", self.iterations)?;
+        if !self.converged {
+            write!(f, "\n\tWarning: IRLS did not converge")?;
+        }
+        if self.separated() {
+            write!(
+                f,
+                "\n\tWarning: fitted probabilities of 0 or 1; the data may be separated"
+            )?;
+        }
+        if self.link == Link::Logit {
+            write!(f, "\n\tOdds ratios ({}% CI):", self.confidence * 100.0)?;
+            for o in self.odds_ratios() {
+                write!(
+                    f,
+                    "\n\t\t{}: {:.4} [{:.4}, {:.4}]",
+                    o.name, o.odds_ratio, o.lower, o.upper
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Residual diagnostics of a linear model. The per-observation vectors
+/// follow the rows of the model's design.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionDiagnostics {
+    pub formula: String,
+    pub leverage: Vec<f64>,
+    pub cooks_distance: Vec<f64>,
+    pub studentized_residuals: Vec<f64>,
+    /// Data frame rows with a Cook's distance above `4 / n`.
+    pub influential: Vec<usize>,
+    pub vif: Vec<(String, f64)>,
+    pub durbin_watson: f64,
+    pub breusch_pagan: TestResult,
+    pub white: TestResult,
+}
+
+impl Display for RegressionDiagnostics {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "Regression diagnostics: {}", self.formula).ok();
+        writeln!(f, "\tDurbin-Watson: {:.6}", self.durbin_watson).ok();
+        for t in [&self.breusch_pagan, &self.white] {
+            writeln!(
+                f,
+                "\t{}: {:.4} on {} df, p-value: {}",
+                t.method,
+                t.statistic,
+                t.parameters[0].1,
+                format_p_value(t.p_value)
+            )
+            .ok();
+        }
+        write!(f, "\tVariance inflation factors:")?;
+        for (name, v) in &self.vif {
+            write!(f, "\n\t\t{}: {:.4}", name, v)?;
+        }
+        let max = |x: &[f64]| x.iter().fold(f64::NEG_INFINITY, |m, v| m.max(v.abs()));
+        write!(
+            f,
+            "\n\tMax leverage: {:.4}, max |studentized residual|: {:.4}, max Cook's distance: {:.4}",
+            max(&self.leverage),
+            max(&self.studentized_residuals),
+            max(&self.cooks_distance)
+        )?;
+        write!(
+            f,
+            "\n\tInfluential rows (Cook's distance > 4/n): {:?}",
+            self.influential
+        )
+    }
+}
+
+/// Model predictions with intervals for the mean response (confidence) and
+/// for a new observation (prediction).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prediction {
+    pub confidence: f64,
+    /// Data frame rows predicted; rows with nulls in a predictor are skipped.
+    pub rows: Vec<usize>,
+    pub fit: Vec<f64>,
+    /// Standard error of the fitted mean.
+    pub se_fit: Vec<f64>,
+    pub conf_lower: Vec<f64>,
+    pub conf_upper: Vec<f64>,
+    pub pred_lower: Vec<f64>,
+    pub pred_upper: Vec<f64>,
+}
+
+impl Display for Prediction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        let level = format!("{}%", self.confidence * 100.0);
+        write!(
+            f,
+            "Predictions:\n\t{: >6} {: >12} {: >12} {: >27} {: >27}",
+            "row",
+            "fit",
+            "std. error",
+            format!("{} CI", level),
+            format!("{} PI", level)
+        )?;
+        for i in 0..self.rows.len() {
+            write!(
+                f,
+                "\n\t{: >6} {: >12.6} {: >12.6} [{: >12.6}, {: >12.6}] [{: >12.6}, {: >12.6}]",
+                self.rows[i],
+                self.fit[i],
+                self.se_fit[i],
+                self.conf_lower[i],
+                self.conf_upper[i],
+                self.pred_lower[i],
+                self.pred_upper[i]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Coefficients of a penalized regression along a decreasing lambda grid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PenalizedPath {
+    pub formula: Formula,
+    pub family: Family,
+    pub penalty: Penalty,
+    pub nobs: usize,
+    /// Names of the penalized design columns; the intercept is excluded.
+    pub columns: Vec<String>,
+    pub lambdas: Vec<f64>,
+    pub intercepts: Vec<f64>,
+    /// One row per lambda and one column per design column, on the
+    /// original scale.
+    pub coefficients: Matrix,
+    /// Number of nonzero coefficients at each lambda.
+    pub df: Vec<usize>,
+    /// Fraction of the null deviance explained at each lambda.
+    pub deviance_ratio: Vec<f64>,
+    pub null_deviance: f64,
+}
+
+impl Display for PenalizedPath {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(f, "Penalized regression: {}", self.formula).ok();
+        writeln!(f, "\tFamily: {}, penalty: {}", self.family, self.penalty).ok();
+        writeln!(f, "\tObservations: {}", self.nobs).ok();
+        write!(f, "\t{: >14} {: >6} {: >10}", "lambda", "df", "%dev")?;
+        for l in 0..self.lambdas.len() {
+            write!(
+                f,
+                "\n\t{: >14.6e} {: >6} {: >10.4}",
+                self.lambdas[l],
+                self.df[l],
+                self.deviance_ratio[l] * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Cross-validation of a penalized regression path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PenalizedCv {
+    pub path: PenalizedPath,
+    pub folds: usize,
+    /// Mean squared error or deviance per observation at each lambda.
+    pub cv_error: Vec<f64>,
+    pub cv_se: Vec<f64>,
+    /// Index of the lambda with the smallest error.
+    pub index_min: usize,
+    /// Index of the largest lambda within one standard error of the minimum.
+    pub index_1se: usize,
+}
+
+impl Display for PenalizedCv {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "==============================================================="
+        )
+        .ok();
+        writeln!(
+            f,
+            "Cross-validated penalized regression: {}",
+            self.path.formula
+        )
+        .ok();
+        writeln!(
+            f,
+            "\tFamily: {}, penalty: {}",
+            self.path.family, self.path.penalty
+        )
+        .ok();
+        writeln!(
+            f,
+            "\tObservations: {}, folds: {}",
+            self.path.nobs, self.folds
+        )
+        .ok();
+        let measure = if self.path.family == Family::Binomial {
+            "deviance"
+        } else {
+            "MSE"
+        };
+        let width = self
+            .path
+            .columns
+            .iter()
+            .map(|c| c.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(11);
+        write!(
+            f,
+            "\t{: <width$} {: >14} {: >14}",
+            "",
+            "lambda.min",
+            "lambda.1se",
+            width = width
+        )?;
+        let (min, se) = (self.index_min, self.index_1se);
+        write!(
+            f,
+            "\n\t{: <width$} {: >14.6e} {: >14.6e}",
+            "lambda",
+            self.path.lambdas[min],
+            self.path.lambdas[se],
+            width = width
+        )?;
+        write!(
+            f,
+            "\n\t{: <width$} {: >14.6} {: >14.6}",
+            format!("CV {}", measure),
+            self.cv_error[min],
+            self.cv_error[se],
+            width = width
+        )?;
+        write!(
+            f,
+            "\n\t{: <width$} {: >14.6} {: >14.6}",
+            "std. error",
+            self.cv_se[min],
+            self.cv_se[se],
+            width = width
+        )?;
+        for ((name, a), (_, b)) in self.path.coef(min).into_iter().zip(self.path.coef(se)) {
+            write!(
+                f,
+                "\n\t{: <width$} {: >14.6} {: >14.6}",
+                name,
+                a,
+                b,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,255 @@
+/// Source of uniformly distributed random bits shared by every sampling,
+/// resampling and simulation routine in the crate.
+///
+/// Implementors only provide `next_u64`; everything else is derived from it.
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Uniform float in `[0, 1)` with 53 bits of precision.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Uniform float in the open interval `(0, 1)`, safe to pass to `ln`.
+    fn next_f64_open(&mut self) -> f64 {
+        ((self.next_u64() >> 12) as f64 + 0.5) * (1.0 / (1u64 << 52) as f64)
+    }
+
+    /// Uniform integer in `[0, n)` without modulo bias. Panics if `n` is zero.
+    fn gen_index(&mut self, n: usize) -> usize {
+        self.gen_below(n as u64) as usize
+    }
+
+    /// Uniform integer in `[0, n)` without modulo bias. Panics if `n` is zero.
+    fn gen_below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "gen_below called with n = 0");
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let v = self.next_u64();
+            if v >= threshold {
+                return v % n;
+            }
+        }
+    }
+
+    /// Uniform integer in `[low, high)`. Panics if the range is empty.
+    fn gen_range_i64(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low < high, "empty range {}..{}", low, high);
+        let span = high.wrapping_sub(low) as u64;
+        low.wrapping_add(self.gen_below(span) as i64)
+    }
+
+    /// Uniform float in `[low, high)`.
+    fn gen_range_f64(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+
+    /// Returns `true` with probability `p`.
+    fn gen_bool(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Fisher-Yates shuffle.
+    fn shuffle<T>(&mut self, values: &mut [T])
+    where
+        Self: Sized,
+    {
+        for i in (1..values.len()).rev() {
+            let j = self.gen_index(i + 1);
+            values.swap(i, j);
+        }
+    }
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// SplitMix64 generator. Fast and statistically decent, mainly used to expand
+/// a single `u64` seed into the state of other generators.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn seed_from_u64(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// xoshiro256++ pseudo-random number generator, the default generator of the
+/// crate.
+///
+/// Not cryptographically secure; meant for reproducible sampling and
+/// simulation. Independent streams for parallel or nested simulations are
+/// obtained with [`Xoshiro256::split`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Xoshiro256 {
+    s: [u64; 4],
+}
+
+impl Xoshiro256 {
+    pub fn seed_from_u64(seed: u64) -> Xoshiro256 {
+        let mut sm = SplitMix64::seed_from_u64(seed);
+        Xoshiro256 {
+            s: [sm.next_u64(), sm.next_u64(), sm.next_u64(), sm.next_u64()],
+        }
+    }
+
+    /// Creates a generator from a raw state. The state must not be all zeros.
+    pub fn from_state(s: [u64; 4]) -> Xoshiro256 {
+        assert!(s != [0; 4], "xoshiro256 state must not be all zeros");
+        Xoshiro256 { s }
+    }
+
+    /// Advances the state by 2^128 steps.
+    pub fn jump(&mut self) {
+        const JUMP: [u64; 4] = [
+            0x180ec6d33cfd0aba,
+            0xd5a61266f0c9392c,
+            0xa9582618e03fc9aa,
+            0x39abdc4529b1661c,
+        ];
+        let mut s = [0u64; 4];
+        for j in JUMP {
+            for b in 0..64 {
+                if j & (1u64 << b) != 0 {
+                    for (acc, v) in s.iter_mut().zip(self.s) {
+                        *acc ^= v;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+        self.s = s;
+    }
+
+    /// Returns a generator for a new stream and moves this one 2^128 steps
+    /// ahead, so repeated calls hand out non-overlapping streams.
+    pub fn split(&mut self) -> Xoshiro256 {
+        let stream = self.clone();
+        self.jump();
+        stream
+    }
+}
+
+impl Rng for Xoshiro256 {
+    fn next_u64(&mut self) -> u64 {
+        let result = (self.s[0].wrapping_add(self.s[3]))
+            .rotate_left(23)
+            .wrapping_add(self.s[0]);
+        let t = self.s[1] << 17;
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rng, SplitMix64, Xoshiro256};
+
+    #[test]
+    fn test_reference_values() {
+        let mut rng = Xoshiro256::from_state([1, 2, 3, 4]);
+        let expected = [
+            41943041,
+            58720359,
+            3588806011781223,
+            3591011842654386,
+            9228616714210784205,
+            9973669472204895162,
+            14011001112246962877,
+            12406186145184390807,
+            15849039046786891736,
+            10450023813501588000,
+        ];
+        for e in expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+
+        let mut rng = SplitMix64::seed_from_u64(1234567);
+        let expected = [
+            6457827717110365317,
+            3203168211198807973,
+            9817491932198370423,
+            4593380528125082431,
+            16408922859458223821,
+        ];
+        for e in expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let mut a = Xoshiro256::seed_from_u64(42);
+        let mut b = Xoshiro256::seed_from_u64(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        let mut c = Xoshiro256::seed_from_u64(43);
+        assert_ne!(a.next_u64(), c.next_u64());
+    }
+
+    #[test]
+    fn test_split() {
+        let mut root = Xoshiro256::seed_from_u64(42);
+        let mut s1 = root.split();
+        let mut s2 = root.split();
+        assert_ne!(s1, s2);
+        let a: Vec<u64> = (0..10).map(|_| s1.next_u64()).collect();
+        let b: Vec<u64> = (0..10).map(|_| s2.next_u64()).collect();
+        assert!(a.iter().all(|x| !b.contains(x)));
+
+        let mut expected = Xoshiro256::seed_from_u64(42);
+        assert_eq!(expected.clone().next_u64(), a[0]);
+        expected.jump();
+        expected.jump();
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn test_uniform() {
+        let mut rng = Xoshiro256::seed_from_u64(1);
+        let mut counts = [0; 5];
+        for _ in 0..50_000 {
+            counts[rng.gen_index(5)] += 1;
+        }
+        assert!(counts.iter().all(|&c| (9_500..10_500).contains(&c)));
+
+        for _ in 0..1000 {
+            let i = rng.gen_range_i64(-3, 3);
+            assert!((-3..3).contains(&i));
+            let f = rng.gen_range_f64(2.0, 2.5);
+            assert!((2.0..2.5).contains(&f));
+            let u = rng.next_f64_open();
+            assert!(u > 0.0 && u < 1.0);
+        }
+        let mean = (0..100_000).map(|_| rng.next_f64()).sum::<f64>() / 100_000.0;
+        assert!((mean - 0.5).abs() < 0.005);
+        let heads = (0..10_000).filter(|_| rng.gen_bool(0.3)).count();
+        assert!((2_850..3_150).contains(&heads));
+    }
+}
@@ -0,0 +1,358 @@
+use crate::*;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SampleSize {
+    Count(usize),
+    Fraction(f64),
+}
+
+impl SampleSize {
+    fn resolve(&self, n: usize) -> Result<usize, StatustError> {
+        match *self {
+            SampleSize::Count(c) => Ok(c),
+            SampleSize::Fraction(f) if f >= 0.0 => Ok((f * n as f64).round() as usize),
+            SampleSize::Fraction(f) => Err(StatustError::invalid(format!(
+                "sample fraction must be non-negative, got {}",
+                f
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fold {
+    pub train: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+impl DataFrame {
+    /// Returns a new frame made of the given rows, in order.
+    pub fn take_rows(&self, rows: &[usize]) -> DataFrame {
+        DataFrame {
+            header: self.header.clone(),
+            data: rows.iter().map(|&r| self.data[r].clone()).collect(),
+        }
+    }
+
+    pub fn sample<R: Rng>(
+        &self,
+        size: SampleSize,
+        replace: bool,
+        rng: &mut R,
+    ) -> Result<DataFrame, StatustError> {
+        let rows = sample_indices(self.nrows(), size.resolve(self.nrows())?, replace, rng)?;
+        Ok(self.take_rows(&rows))
+    }
+
+    /// Samples rows with probability proportional to the numeric `weights`
+    /// column. Null or negative weights are errors.
+    pub fn sample_weighted<R: Rng>(
+        &self,
+        size: SampleSize,
+        replace: bool,
+        weights: &str,
+        rng: &mut R,
+    ) -> Result<DataFrame, StatustError> {
+        let w: Vec<f64> = self
+            .get_col_by_name(weights)
+            .ok_or_else(|| StatustError::ColumnNotFound(weights.to_string()))?
+            .iter()
+            .map(|v| v.as_f64().filter(|w| *w >= 0.0))
+            .collect::<Option<_>>()
+            .ok_or_else(|| {
+                StatustError::invalid(format!(
+                    "weights column {} must hold non-negative numbers",
+                    weights
+                ))
+            })?;
+        let n = size.resolve(self.nrows())?;
+        let positive = w.iter().filter(|w| **w > 0.0).count();
+        if (replace && n > 0 && positive == 0) || (!replace && n > positive) {
+            return Err(StatustError::invalid(format!(
+                "cannot draw {} rows from {} rows with positive weight",
+                n, positive
+            )));
+        }
+
+        let rows: Vec<usize> = if replace {
+            let mut cumulative = Vec::with_capacity(w.len());
+            let mut total = 0.0;
+            for x in &w {
+                total += x;
+                cumulative.push(total);
+            }
+            (0..n)
+                .map(|_| {
+                    let u = rng.next_f64() * total;
+                    cumulative.partition_point(|c| *c <= u).min(w.len() - 1)
+                })
+                .collect()
+        } else {
+            // Efraimidis-Spirakis: keep the n largest u^(1/w).
+            let mut keys: Vec<(f64, usize)> = w
+                .iter()
+                .enumerate()
+                .filter(|(_, w)| **w > 0.0)
+                .map(|(i, w)| (rng.next_f64_open().ln() / w, i))
+                .collect();
+            keys.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+            keys.into_iter().take(n).map(|(_, i)| i).collect()
+        };
+        Ok(self.take_rows(&rows))
+    }
+
+    /// Samples without replacement within each level of the `by` column, so
+    /// that every stratum is represented in proportion (`Fraction`) or by the
+    /// same number of rows (`Count`). Strata keep their order of appearance.
+    pub fn sample_stratified<R: Rng>(
+        &self,
+        size: SampleSize,
+        by: &str,
+        rng: &mut R,
+    ) -> Result<DataFrame, StatustError> {
+        let mut rows = Vec::new();
+        for group in self.strata(by)? {
+            let picked = sample_indices(group.len(), size.resolve(group.len())?, false, rng)?;
+            rows.extend(picked.into_iter().map(|i| group[i]));
+        }
+        Ok(self.take_rows(&rows))
+    }
+
+    pub fn shuffle<R: Rng>(&self, rng: &mut R) -> DataFrame {
+        let mut rows: Vec<usize> = (0..self.nrows()).collect();
+        rng.shuffle(&mut rows);
+        self.take_rows(&rows)
+    }
+
+    /// Splits the rows into shuffled train and test frames. With `stratify`,
+    /// the test fraction is applied within each level of that column.
+    pub fn train_test_split<R: Rng>(
+        &self,
+        test_size: f64,
+        stratify: Option<&str>,
+        rng: &mut R,
+    ) -> Result<(DataFrame, DataFrame), StatustError> {
+        if !(0.0..=1.0).contains(&test_size) {
+            return Err(StatustError::invalid(format!(
+                "test size must be in [0, 1], got {}",
+                test_size
+            )));
+        }
+        let groups = match stratify {
+            Some(by) => self.strata(by)?,
+            None => vec![(0..self.nrows()).collect()],
+        };
+        let (mut train, mut test) = (Vec::new(), Vec::new());
+        for mut group in groups {
+            rng.shuffle(&mut group);
+            let n_test = (test_size * group.len() as f64).round() as usize;
+            test.extend_from_slice(&group[..n_test]);
+            train.extend_from_slice(&group[n_test..]);
+        }
+        rng.shuffle(&mut train);
+        rng.shuffle(&mut test);
+        Ok((self.take_rows(&train), self.take_rows(&test)))
+    }
+
+    /// Row indices for k-fold cross-validation. Rows are shuffled once and
+    /// dealt into `k` folds whose sizes differ by at most one.
+    pub fn k_fold<R: Rng>(&self, k: usize, rng: &mut R) -> Result<Vec<Fold>, StatustError> {
+        k_fold_indices(self.nrows(), k, rng)
+    }
+
+    fn strata(&self, by: &str) -> Result<Vec<Vec<usize>>, StatustError> {
+        let col = self
+            .get_col_by_name(by)
+            .ok_or_else(|| StatustError::ColumnNotFound(by.to_string()))?;
+        let mut order: Vec<Option<&DataType>> = Vec::new();
+        let mut groups: HashMap<Option<&DataType>, Vec<usize>> = HashMap::new();
+        for (i, v) in col.iter().enumerate() {
+            let key = Some(v).filter(|v| !v.is_null());
+            groups
+                .entry(key)
+                .or_insert_with(|| {
+                    order.push(key);
+                    Vec::new()
+                })
+                .push(i);
+        }
+        Ok(order.iter().map(|k| groups.remove(k).unwrap()).collect())
+    }
+}
+
+pub fn sample_indices<R: Rng>(
+    n: usize,
+    k: usize,
+    replace: bool,
+    rng: &mut R,
+) -> Result<Vec<usize>, StatustError> {
+    if replace {
+        if n == 0 && k > 0 {
+            return Err(StatustError::invalid(
+                "cannot sample from an empty frame".to_string(),
+            ));
+        }
+        return Ok((0..k).map(|_| rng.gen_index(n)).collect());
+    }
+    if k > n {
+        return Err(StatustError::invalid(format!(
+            "cannot draw {} rows without replacement from {} rows",
+            k, n
+        )));
+    }
+    // Partial Fisher-Yates shuffle.
+    let mut idx: Vec<usize> = (0..n).collect();
+    for i in 0..k {
+        let j = i + rng.gen_index(n - i);
+        idx.swap(i, j);
+    }
+    idx.truncate(k);
+    Ok(idx)
+}
+
+pub fn k_fold_indices<R: Rng>(n: usize, k: usize, rng: &mut R) -> Result<Vec<Fold>, StatustError> {
+    if k < 2 || k > n {
+        return Err(StatustError::invalid(format!(
+            "k must be between 2 and {}, got {}",
+            n, k
+        )));
+    }
+    let mut idx: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut idx);
+    Ok((0..k)
+        .map(|f| {
+            let (mut train, mut test) = (Vec::new(), Vec::new());
+            for (pos, &i) in idx.iter().enumerate() {
+                if pos % k == f {
+                    test.push(i);
+                } else {
+                    train.push(i);
+                }
+            }
+            Fold { train, test }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn iris() -> DataFrame {
+        DataFrame::read("./examples/iris.csv").unwrap()
+    }
+
+    fn count(df: &DataFrame, variety: &str) -> usize {
+        df.get_col_by_name("variety")
+            .unwrap()
+            .iter()
+            .filter(|v| **v == DataType::from(variety))
+            .count()
+    }
+
+    #[test]
+    fn test_sample() {
+        let df = iris();
+        let a = df
+            .sample(
+                SampleSize::Count(10),
+                false,
+                &mut Xoshiro256::seed_from_u64(7),
+            )
+            .unwrap();
+        let b = df
+            .sample(
+                SampleSize::Count(10),
+                false,
+                &mut Xoshiro256::seed_from_u64(7),
+            )
+            .unwrap();
+        assert_eq!(a.nrows(), 10);
+        assert_eq!(format!("{:?}", a), format!("{:?}", b));
+
+        let mut rng = Xoshiro256::seed_from_u64(1);
+        assert_eq!(
+            df.sample(SampleSize::Fraction(0.2), false, &mut rng)
+                .unwrap()
+                .nrows(),
+            30
+        );
+        assert_eq!(
+            df.sample(SampleSize::Count(500), true, &mut rng)
+                .unwrap()
+                .nrows(),
+            500
+        );
+        assert!(df.sample(SampleSize::Count(151), false, &mut rng).is_err());
+
+        let idx = sample_indices(150, 150, false, &mut rng).unwrap();
+        assert_eq!(idx.iter().collect::<HashSet<_>>().len(), 150);
+    }
+
+    #[test]
+    fn test_sample_weighted() {
+        let mut df = iris();
+        df.with_column(
+            "w",
+            when(col("variety").equal(lit("Setosa")))
+                .then(lit(1))
+                .otherwise(lit(0)),
+        )
+        .unwrap();
+        let mut rng = Xoshiro256::seed_from_u64(3);
+        let s = df
+            .sample_weighted(SampleSize::Count(40), false, "w", &mut rng)
+            .unwrap();
+        assert_eq!(count(&s, "Setosa"), 40);
+        let s = df
+            .sample_weighted(SampleSize::Count(200), true, "w", &mut rng)
+            .unwrap();
+        assert_eq!(count(&s, "Setosa"), 200);
+        assert!(df
+            .sample_weighted(SampleSize::Count(51), false, "w", &mut rng)
+            .is_err());
+        assert!(df
+            .sample_weighted(SampleSize::Count(1), false, "variety", &mut rng)
+            .is_err());
+    }
+
+    #[test]
+    fn test_sample_stratified() {
+        let df = iris();
+        let mut rng = Xoshiro256::seed_from_u64(11);
+        let s = df
+            .sample_stratified(SampleSize::Fraction(0.1), "variety", &mut rng)
+            .unwrap();
+        assert_eq!(s.nrows(), 15);
+        for v in ["Setosa", "Versicolor", "Virginica"] {
+            assert_eq!(count(&s, v), 5);
+        }
+    }
+
+    #[test]
+    fn test_train_test_split() {
+        let df = iris();
+        let mut rng = Xoshiro256::seed_from_u64(5);
+        let (train, test) = df.train_test_split(0.2, Some("variety"), &mut rng).unwrap();
+        assert_eq!((train.nrows(), test.nrows()), (120, 30));
+        assert_eq!(count(&test, "Virginica"), 10);
+
+        let shuffled = df.shuffle(&mut rng);
+        assert_eq!(shuffled.nrows(), 150);
+        assert_eq!(count(&shuffled, "Setosa"), 50);
+    }
+
+    #[test]
+    fn test_k_fold() {
+        let folds = iris().k_fold(4, &mut Xoshiro256::seed_from_u64(9)).unwrap();
+        assert_eq!(folds.len(), 4);
+        let sizes: Vec<usize> = folds.iter().map(|f| f.test.len()).collect();
+        assert_eq!(sizes, vec![38, 38, 37, 37]);
+        let all: HashSet<usize> = folds.iter().flat_map(|f| f.test.clone()).collect();
+        assert_eq!(all.len(), 150);
+        assert!(folds.iter().all(|f| f.train.len() + f.test.len() == 150));
+        assert!(k_fold_indices(3, 4, &mut Xoshiro256::seed_from_u64(9)).is_err());
+    }
+}
@@ -0,0 +1,387 @@
+use crate::distributions::*;
+use crate::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How simulated columns are combined with an existing DataFrame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SimulateMode {
+    /// Build a new DataFrame from the simulated columns only.
+    Replace,
+    /// Add the simulated columns to the existing DataFrame, replacing
+    /// columns with the same name.
+    Add,
+}
+
+pub enum SimDistribution {
+    Continuous(Box<dyn ContinuousDistribution>),
+    Discrete(Box<dyn DiscreteDistribution>),
+}
+
+impl SimDistribution {
+    /// Draws `n` values. Discrete draws that do not fit in an `Int` column
+    /// are an error rather than being saturated.
+    fn sample_column(&self, n: usize, rng: &mut dyn Rng) -> Result<Vec<DataType>, i64> {
+        match self {
+            SimDistribution::Continuous(d) => {
+                Ok((0..n).map(|_| DataType::from(d.sample(rng))).collect())
+            }
+            SimDistribution::Discrete(d) => (0..n)
+                .map(|_| {
+                    let x = d.sample(rng);
+                    i32::try_from(x).map(DataType::Int).map_err(|_| x)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A parsed `simulate` specification such as
+/// `n=1000 x~normal(0,1) y~poisson(3) seed=42 mode=add`.
+pub struct Simulation {
+    pub n: Option<usize>,
+    pub seed: Option<u64>,
+    pub mode: SimulateMode,
+    pub columns: Vec<(String, SimDistribution)>,
+}
+
+/// Splits on whitespace outside of parentheses.
+fn tokenize(spec: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in spec.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => (),
+        }
+        if c.is_whitespace() {
+            if depth == 0 {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+impl Simulation {
+    pub fn parse(spec: &str) -> Result<Simulation, StatustError> {
+        let mut sim = Simulation {
+            n: None,
+            seed: None,
+            mode: SimulateMode::Replace,
+            columns: Vec::new(),
+        };
+        for token in tokenize(spec) {
+            if let Some((name, dist)) = token.split_once('~') {
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(StatustError::invalid(format!(
+                        "missing column name in '{}'",
+                        token
+                    )));
+                }
+                if sim.columns.iter().any(|(n, _)| n == name) {
+                    return Err(StatustError::DuplicateColumn(name.to_string()));
+                }
+                sim.columns
+                    .push((name.to_string(), parse_distribution(dist)?));
+            } else if let Some((key, value)) = token.split_once('=') {
+                match key {
+                    "n" => {
+                        sim.n = Some(value.parse().map_err(|_| {
+                            StatustError::invalid(format!(
+                                "n must be a non-negative integer, got '{}'",
+                                value
+                            ))
+                        })?)
+                    }
+                    "seed" => {
+                        sim.seed = Some(value.parse().map_err(|_| {
+                            StatustError::invalid(format!(
+                                "seed must be a non-negative integer, got '{}'",
+                                value
+                            ))
+                        })?)
+                    }
+                    "mode" => {
+                        sim.mode = match value {
+                            "replace" => SimulateMode::Replace,
+                            "add" => SimulateMode::Add,
+                            _ => {
+                                return Err(StatustError::invalid(format!(
+                                    "mode must be 'replace' or 'add', got '{}'",
+                                    value
+                                )))
+                            }
+                        }
+                    }
+                    _ => return Err(StatustError::invalid(format!("unknown option '{}'", key))),
+                }
+            } else {
+                return Err(StatustError::invalid(format!(
+                    "expected 'name~dist(...)' or 'key=value', got '{}'",
+                    token
+                )));
+            }
+        }
+        if sim.columns.is_empty() {
+            return Err(StatustError::invalid(
+                "simulate needs at least one 'name~dist(...)' column".to_string(),
+            ));
+        }
+        Ok(sim)
+    }
+
+    /// Draws `n` rows of every column, in the order they were specified.
+    pub fn generate(&self, n: usize, rng: &mut dyn Rng) -> Result<DataFrame, StatustError> {
+        let header = self.columns.iter().map(|(name, _)| name.clone()).collect();
+        let columns = self
+            .columns
+            .iter()
+            .map(|(name, d)| {
+                d.sample_column(n, rng).map_err(|x| {
+                    StatustError::invalid(format!(
+                        "column {} drew {}, which does not fit in a 32-bit integer",
+                        name, x
+                    ))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        DataFrame::from_columns(header, columns)
+    }
+
+    /// Runs the simulation against `current` according to the mode. When
+    /// adding, `n` defaults to the number of rows of `current`.
+    pub fn run(&self, current: &DataFrame) -> Result<DataFrame, StatustError> {
+        let add = self.mode == SimulateMode::Add && current.ncols() > 0;
+        let n = match (self.n, add) {
+            (Some(n), true) if n != current.nrows() => {
+                return Err(StatustError::LengthMismatch {
+                    expected: current.nrows(),
+                    found: n,
+                })
+            }
+            (Some(n), _) => n,
+            (None, true) => current.nrows(),
+            (None, false) => {
+                return Err(StatustError::invalid("simulate needs n=<rows>".to_string()))
+            }
+        };
+        let seed = self.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+        });
+        let mut rng = Xoshiro256::seed_from_u64(seed);
+        let simulated = self.generate(n, &mut rng)?;
+        if !add {
+            return Ok(simulated);
+        }
+        let mut df = current.clone();
+        for (i, name) in simulated.header().iter().enumerate() {
+            df.set_col(name, simulated.get_col(i).unwrap())?;
+        }
+        Ok(df)
+    }
+}
+
+/// Parses a distribution such as `normal(0, 1)` or `poisson(3)`.
+pub fn parse_distribution(spec: &str) -> Result<SimDistribution, StatustError> {
+    let spec = spec.trim();
+    let (name, args) = spec
+        .strip_suffix(')')
+        .and_then(|s| s.split_once('('))
+        .ok_or_else(|| StatustError::invalid(format!("expected 'dist(params)', got '{}'", spec)))?;
+    let params = args
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<f64>().map_err(|_| {
+                StatustError::invalid(format!("invalid parameter '{}' in '{}'", s, spec))
+            })
+        })
+        .collect::<Result<Vec<f64>, StatustError>>()?;
+    let name = name.trim().to_lowercase();
+    let arity = |k: usize| {
+        if params.len() == k {
+            Ok(())
+        } else {
+            Err(StatustError::invalid(format!(
+                "{} takes {} parameter(s), got {}",
+                name,
+                k,
+                params.len()
+            )))
+        }
+    };
+    let count = |v: f64| {
+        if v >= 0.0 && v.fract() == 0.0 {
+            Ok(v as u64)
+        } else {
+            Err(StatustError::invalid(format!(
+                "{} expects a non-negative integer, got {}",
+                name, v
+            )))
+        }
+    };
+    use SimDistribution::{Continuous as C, Discrete as D};
+    let p = &params;
+    Ok(match name.as_str() {
+        "normal" | "norm" => {
+            arity(2)?;
+            C(Box::new(Normal::new(p[0], p[1])?))
+        }
+        "lognormal" | "lnorm" => {
+            arity(2)?;
+            C(Box::new(LogNormal::new(p[0], p[1])?))
+        }
+        "uniform" | "unif" => {
+            arity(2)?;
+            C(Box::new(Uniform::new(p[0], p[1])?))
+        }
+        "exponential" | "exp" => {
+            arity(1)?;
+            C(Box::new(Exponential::new(p[0])?))
+        }
+        "gamma" => {
+            arity(2)?;
+            C(Box::new(Gamma::new(p[0], p[1])?))
+        }
+        "beta" => {
+            arity(2)?;
+            C(Box::new(Beta::new(p[0], p[1])?))
+        }
+        "chisq" | "chi2" => {
+            arity(1)?;
+            C(Box::new(ChiSquared::new(p[0])?))
+        }
+        "t" | "student" => {
+            arity(1)?;
+            C(Box::new(StudentT::new(p[0])?))
+        }
+        "f" => {
+            arity(2)?;
+            C(Box::new(FisherF::new(p[0], p[1])?))
+        }
+        "cauchy" => {
+            arity(2)?;
+            C(Box::new(Cauchy::new(p[0], p[1])?))
+        }
+        "weibull" => {
+            arity(2)?;
+            C(Box::new(Weibull::new(p[0], p[1])?))
+        }
+        "laplace" => {
+            arity(2)?;
+            C(Box::new(Laplace::new(p[0], p[1])?))
+        }
+        "logistic" => {
+            arity(2)?;
+            C(Box::new(Logistic::new(p[0], p[1])?))
+        }
+        "bernoulli" => {
+            arity(1)?;
+            D(Box::new(Bernoulli::new(p[0])?))
+        }
+        "binomial" | "binom" => {
+            arity(2)?;
+            D(Box::new(Binomial::new(count(p[0])?, p[1])?))
+        }
+        "poisson" | "pois" => {
+            arity(1)?;
+            D(Box::new(Poisson::new(p[0])?))
+        }
+        "geometric" | "geom" => {
+            arity(1)?;
+            D(Box::new(Geometric::new(p[0])?))
+        }
+        "negbinomial" | "nbinom" => {
+            arity(2)?;
+            D(Box::new(NegativeBinomial::new(p[0], p[1])?))
+        }
+        "hypergeometric" | "hyper" => {
+            arity(3)?;
+            D(Box::new(Hypergeometric::new(
+                count(p[0])?,
+                count(p[1])?,
+                count(p[2])?,
+            )?))
+        }
+        "categorical" | "cat" => D(Box::new(Categorical::new(p)?)),
+        _ => {
+            return Err(StatustError::invalid(format!(
+                "unknown distribution '{}'",
+                name
+            )))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let sim = Simulation::parse("n=1000 x~normal(0, 1) y~poisson(3) seed=42").unwrap();
+        assert_eq!(sim.n, Some(1000));
+        assert_eq!(sim.seed, Some(42));
+        assert_eq!(sim.mode, SimulateMode::Replace);
+        assert_eq!(sim.columns.len(), 2);
+        assert!(matches!(sim.columns[1].1, SimDistribution::Discrete(_)));
+
+        assert!(Simulation::parse("n=10").is_err());
+        assert!(Simulation::parse("n=10 x~normal(0)").is_err());
+        assert!(Simulation::parse("n=10 x~normal(0,-1)").is_err());
+        assert!(Simulation::parse("n=10 x~foo(1)").is_err());
+        assert!(Simulation::parse("n=10 x~binom(2.5,0.3)").is_err());
+        assert!(Simulation::parse("n=10 x~normal(0,1) bogus").is_err());
+        assert_eq!(
+            Simulation::parse("x~exp(1) x~exp(2)").err(),
+            Some(StatustError::DuplicateColumn("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let sim = Simulation::parse("n=1000 x~normal(10,2) y~poisson(3) seed=42").unwrap();
+        let df = sim.run(&DataFrame::new()).unwrap();
+        assert_eq!(df.header(), &["x", "y"]);
+        assert_eq!(df.nrows(), 1000);
+        assert!(matches!(df.get_row(0).unwrap()[1], DataType::Int(_)));
+        let x: Vec<f64> = df
+            .get_col_by_name("x")
+            .unwrap()
+            .iter()
+            .map(|v| v.as_f64().unwrap())
+            .collect();
+        assert!((stats::mean(&x) - 10.0).abs() < 0.3);
+
+        let again = sim.run(&DataFrame::new()).unwrap();
+        assert_eq!(df.get_col(0), again.get_col(0));
+
+        let add = Simulation::parse("mode=add z~bernoulli(0.5) x~uniform(0,1) seed=1").unwrap();
+        let combined = add.run(&df).unwrap();
+        assert_eq!(combined.header(), &["x", "y", "z"]);
+        assert_eq!(combined.get_col(1), df.get_col(1));
+        assert_ne!(combined.get_col(0), df.get_col(0));
+
+        let wrong_n = Simulation::parse("n=5 mode=add z~exp(1)").unwrap();
+        assert!(wrong_n.run(&df).is_err());
+        let no_n = Simulation::parse("z~exp(1)").unwrap();
+        assert!(no_n.run(&df).is_err());
+
+        let huge = Simulation::parse("n=3 k~geometric(1e-15) seed=1").unwrap();
+        assert!(huge.run(&DataFrame::new()).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+pub fn mean(x: &[f64]) -> f64 {
+    x.iter().sum::<f64>() / x.len() as f64
+}
+
+/// Sample variance with `n - 1` in the denominator.
+pub fn variance(x: &[f64]) -> f64 {
+    let m = mean(x);
+    x.iter().map(|v| (v - m).powi(2)).sum::<f64>() / (x.len() as f64 - 1.0)
+}
+
+pub fn std_dev(x: &[f64]) -> f64 {
+    variance(x).sqrt()
+}
+
+/// Central moment of order `k`, with `n` in the denominator.
+pub fn central_moment(x: &[f64], k: i32) -> f64 {
+    let m = mean(x);
+    x.iter().map(|v| (v - m).powi(k)).sum::<f64>() / x.len() as f64
+}
+
+/// Moment coefficient of skewness `m3 / m2^1.5` (biased).
+pub fn skewness(x: &[f64]) -> f64 {
+    central_moment(x, 3) / central_moment(x, 2).powf(1.5)
+}
+
+/// Moment coefficient of kurtosis `m4 / m2^2` (biased, not excess), which
+/// is 3 for a normal distribution.
+pub fn kurtosis(x: &[f64]) -> f64 {
+    central_moment(x, 4) / central_moment(x, 2).powi(2)
+}
+
+/// Sample median, or NaN if `x` is empty or contains NaN.
+pub fn median(x: &[f64]) -> f64 {
+    let mut sorted = x.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len();
+    if n == 0 || sorted.iter().any(|v| v.is_nan()) {
+        f64::NAN
+    } else if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    }
+}
+
+/// Sample quantile for `p` in [0, 1], interpolating linearly between order
+/// statistics (R's default type 7). NaN if `x` is empty or contains NaN.
+pub fn quantile(x: &[f64], p: f64) -> f64 {
+    let mut sorted = x.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    if sorted.is_empty() || sorted.iter().any(|v| v.is_nan()) || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    let h = (sorted.len() - 1) as f64 * p;
+    let lo = h.floor() as usize;
+    let hi = h.ceil() as usize;
+    sorted[lo] + (h - lo as f64) * (sorted[hi] - sorted[lo])
+}
+
+/// Ranks starting at 1, with ties given the average of their ranks. NaNs
+/// rank after every other value.
+pub fn rank(x: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..x.len()).collect();
+    order.sort_by(|&a, &b| x[a].total_cmp(&x[b]));
+    let mut ranks = vec![0.0; x.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i;
+        while j + 1 < order.len() && x[order[j + 1]] == x[order[i]] {
+            j += 1;
+        }
+        let r = (i + j) as f64 / 2.0 + 1.0;
+        for &k in &order[i..=j] {
+            ranks[k] = r;
+        }
+        i = j + 1;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_moments() {
+        let x = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(super::mean(&x), 5.0);
+        assert!((super::variance(&x) - 32.0 / 7.0).abs() < 1e-12);
+        assert_eq!(super::median(&x), 4.5);
+        assert_eq!(super::median(&[3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(super::quantile(&x, 0.25), 4.0);
+        assert!((super::quantile(&x, 0.9) - 7.6).abs() < 1e-12);
+        assert!(super::quantile(&x, 1.5).is_nan());
+        assert!(super::median(&[1.0, f64::NAN, 2.0]).is_nan());
+        assert!(super::quantile(&[1.0, f64::NAN], 0.5).is_nan());
+        assert!((super::skewness(&x) - 0.65625).abs() < 1e-12);
+        assert!((super::kurtosis(&x) - 2.78125).abs() < 1e-12);
+        assert_eq!(
+            super::rank(&[3.0, 1.0, 4.0, 1.0, 5.0]),
+            vec![3.0, 1.5, 4.0, 1.5, 5.0]
+        );
+        assert_eq!(super::rank(&[2.0, f64::NAN, 1.0]), vec![2.0, 3.0, 1.0]);
+    }
+}
@@ -0,0 +1,25 @@
+//! Assertions shared by the unit tests.
+
+/// Asserts that `actual` is within `tol` of `expected`.
+#[track_caller]
+pub(crate) fn assert_close(actual: f64, expected: f64, tol: f64) {
+    assert!(
+        (actual - expected).abs() <= tol,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+/// Asserts that `actual` is within relative error `tol` of `expected`.
+#[track_caller]
+pub(crate) fn assert_rel_close(actual: f64, expected: f64, tol: f64) {
+    let err = (actual - expected).abs() / expected.abs().max(1e-300);
+    assert!(
+        err < tol,
+        "expected {}, got {} (rel. error {:e})",
+        expected,
+        actual,
+        err
+    );
+}
@@ -0,0 +1,320 @@
+use crate::contingency::levels;
+use crate::distributions::{ContinuousDistribution, StudentT};
+use crate::error::check_confidence;
+use crate::*;
+
+/// Shared tail of all t-tests: statistic, p-value and confidence interval for
+/// an estimate with standard error `se` on `df` degrees of freedom.
+fn t_result(
+    method: &str,
+    estimate: f64,
+    se: f64,
+    df: f64,
+    mu: f64,
+    alternative: Alternative,
+    confidence: f64,
+) -> Result<TestResult, StatustError> {
+    check_confidence(confidence)?;
+    if se.is_nan() || se <= 0.0 {
+        return Err(StatustError::invalid(
+            "data are essentially constant".to_string(),
+        ));
+    }
+    let dist = StudentT::new(df)?;
+    let t = (estimate - mu) / se;
+    let (lower, upper) = match alternative {
+        Alternative::TwoSided => {
+            let half = dist.quantile(0.5 + confidence / 2.0) * se;
+            (estimate - half, estimate + half)
+        }
+        Alternative::Less => (f64::NEG_INFINITY, estimate + dist.quantile(confidence) * se),
+        Alternative::Greater => (estimate - dist.quantile(confidence) * se, f64::INFINITY),
+    };
+    let mut result = TestResult::new(method, t, alternative.p_value(&dist, t), alternative);
+    result.parameters.push(("df".to_string(), df));
+    result.conf_int = Some(ConfidenceInterval {
+        lower,
+        upper,
+        level: confidence,
+    });
+    Ok(result)
+}
+
+fn check_len(x: &[f64], name: &str) -> Result<(), StatustError> {
+    if x.len() < 2 {
+        return Err(StatustError::invalid(format!(
+            "{} needs at least 2 observations, got {}",
+            name,
+            x.len()
+        )));
+    }
+    Ok(())
+}
+
+/// One-sample t-test of `H0: mean(x) = mu`. Cohen's d is
+/// `(mean - mu) / sd`.
+pub fn t_test_one_sample(
+    x: &[f64],
+    mu: f64,
+    alternative: Alternative,
+    confidence: f64,
+) -> Result<TestResult, StatustError> {
+    check_len(x, "x")?;
+    let n = x.len() as f64;
+    let (mean, sd) = (stats::mean(x), stats::std_dev(x));
+    let mut result = t_result(
+        "One Sample t-test",
+        mean,
+        sd / n.sqrt(),
+        n - 1.0,
+        mu,
+        alternative,
+        confidence,
+    )?;
+    result.parameters.push(("mu".to_string(), mu));
+    result.estimates = vec![
+        ("mean of x".to_string(), mean),
+        ("Cohen's d".to_string(), (mean - mu) / sd),
+    ];
+    Ok(result)
+}
+
+/// Two-sample t-test of `H0: mean(x) - mean(y) = mu`. With `equal_var`
+/// the variances are pooled (Student), otherwise Welch's approximation is
+/// used. Cohen's d always uses the pooled standard deviation.
+pub fn t_test_two_sample(
+    x: &[f64],
+    y: &[f64],
+    mu: f64,
+    equal_var: bool,
+    alternative: Alternative,
+    confidence: f64,
+) -> Result<TestResult, StatustError> {
+    check_len(x, "x")?;
+    check_len(y, "y")?;
+    let (nx, ny) = (x.len() as f64, y.len() as f64);
+    let (mx, my) = (stats::mean(x), stats::mean(y));
+    let (vx, vy) = (stats::variance(x), stats::variance(y));
+    let pooled = ((nx - 1.0) * vx + (ny - 1.0) * vy) / (nx + ny - 2.0);
+    let (method, se, df) = if equal_var {
+        (
+            "Two Sample t-test",
+            (pooled * (1.0 / nx + 1.0 / ny)).sqrt(),
+            nx + ny - 2.0,
+        )
+    } else {
+        let (sx, sy) = (vx / nx, vy / ny);
+        (
+            "Welch Two Sample t-test",
+            (sx + sy).sqrt(),
+            (sx + sy).powi(2) / (sx * sx / (nx - 1.0) + sy * sy / (ny - 1.0)),
+        )
+    };
+    let diff = mx - my;
+    let mut result = t_result(method, diff, se, df, mu, alternative, confidence)?;
+    result.parameters.push(("mu".to_string(), mu));
+    result.estimates = vec![
+        ("mean of x".to_string(), mx),
+        ("mean of y".to_string(), my),
+        ("mean difference".to_string(), diff),
+        ("Cohen's d".to_string(), diff / pooled.sqrt()),
+    ];
+    Ok(result)
+}
+
+/// Paired t-test of `H0: mean(x - y) = mu`. Cohen's d is the mean
+/// difference over the standard deviation of the differences (d_z).
+pub fn t_test_paired(
+    x: &[f64],
+    y: &[f64],
+    mu: f64,
+    alternative: Alternative,
+    confidence: f64,
+) -> Result<TestResult, StatustError> {
+    if x.len() != y.len() {
+        return Err(StatustError::LengthMismatch {
+            expected: x.len(),
+            found: y.len(),
+        });
+    }
+    let d: Vec<f64> = x.iter().zip(y).map(|(a, b)| a - b).collect();
+    check_len(&d, "paired data")?;
+    let n = d.len() as f64;
+    let (mean, sd) = (stats::mean(&d), stats::std_dev(&d));
+    let mut result = t_result(
+        "Paired t-test",
+        mean,
+        sd / n.sqrt(),
+        n - 1.0,
+        mu,
+        alternative,
+        confidence,
+    )?;
+    result.parameters.push(("mu".to_string(), mu));
+    result.estimates = vec![
+        ("mean difference".to_string(), mean),
+        ("Cohen's d".to_string(), (mean - mu) / sd),
+    ];
+    Ok(result)
+}
+
+impl DataFrame {
+    /// Two-sample t-test of the numeric column `value` between the two
+    /// levels of `group`, testing `mean(first) - mean(second) = 0` with the
+    /// levels in sorted order. Rows with a null in either column are skipped.
+    pub fn t_test(
+        &self,
+        value: &str,
+        group: &str,
+        equal_var: bool,
+        alternative: Alternative,
+        confidence: f64,
+    ) -> Result<TestResult, StatustError> {
+        let (labels, samples) = self.grouped_f64(value, group)?;
+        if samples.len() != 2 {
+            return Err(StatustError::invalid(format!(
+                "t-test needs exactly two groups in {}, found {}",
+                group,
+                samples.len()
+            )));
+        }
+        let mut result = t_test_two_sample(
+            &samples[0],
+            &samples[1],
+            0.0,
+            equal_var,
+            alternative,
+            confidence,
+        )?;
+        result.data = format!("{} by {} ({} - {})", value, group, labels[0], labels[1]);
+        Ok(result)
+    }
+
+    /// Values of the numeric column `value` split by the levels of `group`,
+    /// in sorted level order. Rows with a null in either column are skipped.
+    pub fn grouped_f64(
+        &self,
+        value: &str,
+        group: &str,
+    ) -> Result<(Vec<String>, Vec<Vec<f64>>), StatustError> {
+        let values = self.get_f64_col(value)?;
+        let groups = self
+            .get_col_by_name(group)
+            .ok_or_else(|| StatustError::ColumnNotFound(group.to_string()))?;
+        let complete: Vec<(f64, &DataType)> = values
+            .iter()
+            .zip(&groups)
+            .filter_map(|(v, g)| Some(((*v)?, g)))
+            .filter(|(_, g)| !g.is_null())
+            .collect();
+        let levels = levels(
+            &complete
+                .iter()
+                .map(|(_, g)| (*g).clone())
+                .collect::<Vec<_>>(),
+        );
+        let samples = levels
+            .iter()
+            .map(|l| {
+                complete
+                    .iter()
+                    .filter(|(_, g)| *g == l)
+                    .map(|(v, _)| *v)
+                    .collect()
+            })
+            .collect();
+        Ok((levels.iter().map(|l| l.to_string()).collect(), samples))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_close;
+
+    fn estimate(result: &TestResult, name: &str) -> f64 {
+        result.estimates.iter().find(|(n, _)| n == name).unwrap().1
+    }
+
+    // R's sleep data.
+    const GROUP1: [f64; 10] = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+    const GROUP2: [f64; 10] = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+
+    // Reference values from R's t.test, checked against mpmath.
+
+    #[test]
+    fn test_two_sample() {
+        let welch =
+            t_test_two_sample(&GROUP1, &GROUP2, 0.0, false, Alternative::TwoSided, 0.95).unwrap();
+        assert_close(welch.statistic, -1.860813, 1e-6);
+        assert_close(welch.parameters[0].1, 17.77647, 1e-5);
+        assert_close(welch.p_value, 0.07939414, 1e-7);
+        let ci = welch.conf_int.unwrap();
+        assert_close(ci.lower, -3.3654832, 1e-7);
+        assert_close(ci.upper, 0.2054832, 1e-7);
+
+        let student =
+            t_test_two_sample(&GROUP1, &GROUP2, 0.0, true, Alternative::TwoSided, 0.95).unwrap();
+        assert_close(student.statistic, -1.860813, 1e-6);
+        assert_close(student.p_value, 0.07918671, 1e-7);
+        let ci = student.conf_int.unwrap();
+        assert_close(ci.lower, -3.363874, 1e-6);
+        assert_close(ci.upper, 0.203874, 1e-6);
+        assert_close(estimate(&student, "Cohen's d"), -0.8321811, 1e-6);
+
+        let less = t_test_two_sample(&GROUP1, &GROUP2, 0.0, true, Alternative::Less, 0.95).unwrap();
+        assert_close(less.p_value, student.p_value / 2.0, 1e-12);
+        assert_eq!(less.conf_int.unwrap().lower, f64::NEG_INFINITY);
+        assert!(
+            t_test_two_sample(&GROUP1, &[1.0], 0.0, true, Alternative::TwoSided, 0.95).is_err()
+        );
+    }
+
+    #[test]
+    fn test_one_sample_and_paired() {
+        let paired = t_test_paired(&GROUP1, &GROUP2, 0.0, Alternative::TwoSided, 0.95).unwrap();
+        assert_close(paired.statistic, -4.062128, 1e-6);
+        assert_close(paired.p_value, 0.002832890, 1e-8);
+        let ci = paired.conf_int.unwrap();
+        assert_close(ci.lower, -2.4598858, 1e-7);
+        assert_close(ci.upper, -0.7001142, 1e-7);
+        assert_close(estimate(&paired, "mean difference"), -1.58, 1e-12);
+        assert!(t_test_paired(&GROUP1, &GROUP2[..9], 0.0, Alternative::TwoSided, 0.95).is_err());
+
+        let one = t_test_one_sample(&GROUP1, 1.0, Alternative::Greater, 0.9).unwrap();
+        assert_close(one.statistic, -0.4419034, 1e-6);
+        assert_close(one.p_value, 0.6655067, 1e-7);
+        assert_close(one.conf_int.unwrap().lower, -0.03242711, 1e-7);
+        assert_close(estimate(&one, "Cohen's d"), -0.1397421, 1e-7);
+        assert!(t_test_one_sample(&[2.0, 2.0, 2.0], 0.0, Alternative::TwoSided, 0.95).is_err());
+    }
+
+    #[test]
+    fn test_dataframe_t_test() {
+        let df = DataFrame::read("./examples/iris.csv").unwrap();
+        assert!(df
+            .t_test(
+                "sepal.length",
+                "variety",
+                false,
+                Alternative::TwoSided,
+                0.95
+            )
+            .is_err());
+        let mut df = df;
+        let setosa: Vec<DataType> = df
+            .get_col_by_name("variety")
+            .unwrap()
+            .iter()
+            .map(|v| DataType::Bool(v.to_string() == "Setosa"))
+            .collect();
+        df.set_col("setosa", setosa).unwrap();
+        let result = df
+            .t_test("sepal.length", "setosa", false, Alternative::TwoSided, 0.95)
+            .unwrap();
+        assert_eq!(result.data, "sepal.length by setosa (false - true)");
+        assert_close(estimate(&result, "mean of y"), 5.006, 1e-6);
+        assert!(result.statistic > 0.0);
+        assert!(result.to_string().contains("95% CI"));
+    }
+}